@@ -9,6 +9,7 @@ use crate::{
         custom_module::{self, Custom},
         keyboard_layout::KeyboardLayout,
         keyboard_submap::KeyboardSubmap,
+        lock_keys::LockKeys,
         media_player::MediaPlayer,
         notifications::Notifications,
         privacy::Privacy,
@@ -20,15 +21,19 @@ use crate::{
         window_title::WindowTitle,
         workspaces::Workspaces,
     },
-    outputs::{HasOutput, Outputs},
+    outputs::{HasOutput, Outputs, SurfaceSettings},
     popup::PopupState,
-    services::ReadOnlyService,
+    services::{
+        ReadOnlyService,
+        notifications::{InternalPopup, Notification, NotificationIcon},
+    },
+    set_log_format,
     theme::{AshellTheme, backdrop_color, darken_color},
     widgets::{ButtonUIRef, Centerbox},
 };
 use flexi_logger::LoggerHandle;
 use iced::{
-    Alignment, Color, Element, Gradient, Length, Radians, Subscription, Task, Theme,
+    Alignment, Border, Color, Element, Gradient, Length, Radians, Subscription, Task, Theme,
     daemon::Appearance,
     event::{
         listen_with,
@@ -40,18 +45,27 @@ use iced::{
     window::Id,
 };
 use log::{debug, info, warn};
-use std::{collections::HashMap, f32::consts::PI, path::PathBuf, time::{Duration, Instant}};
+use std::{
+    collections::HashMap,
+    f32::consts::PI,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 use wayland_client::protocol::wl_output::WlOutput;
 
 pub struct GeneralConfig {
     outputs: config::Outputs,
     pub modules: Modules,
-    pub layer: config::Layer,
+    surface: SurfaceSettings,
     enable_esc_key: bool,
+    esc_closes_menus: bool,
+    esc_dismisses_popups: bool,
+    pub pause_when_menu_open: bool,
 }
 
 pub struct App {
     config_path: PathBuf,
+    active_profile: Option<String>,
     pub theme: AshellTheme,
     logger: LoggerHandle,
     pub general_config: GeneralConfig,
@@ -63,6 +77,7 @@ pub struct App {
     pub system_info: SystemInfo,
     pub keyboard_layout: KeyboardLayout,
     pub keyboard_submap: KeyboardSubmap,
+    pub lock_keys: LockKeys,
     pub tray: TrayModule,
     pub clock: Clock,
     pub tempo: Tempo,
@@ -76,6 +91,9 @@ pub struct App {
 #[derive(Debug, Clone)]
 pub enum Message {
     ConfigChanged(Box<Config>),
+    ConfigError(String),
+    ApplyProfile(String),
+    ResetProfile,
     ToggleMenu(MenuType, Id, ButtonUIRef),
     CloseMenu(Id),
     Custom(String, custom_module::Message),
@@ -85,6 +103,7 @@ pub enum Message {
     SystemInfo(modules::system_info::Message),
     KeyboardLayout(modules::keyboard_layout::Message),
     KeyboardSubmap(modules::keyboard_submap::Message),
+    LockKeys(modules::lock_keys::Message),
     Tray(modules::tray::Message),
     Clock(modules::clock::Message),
     Tempo(modules::tempo::Message),
@@ -94,24 +113,30 @@ pub enum Message {
     MediaPlayer(modules::media_player::Message),
     OutputEvent((OutputEvent, WlOutput)),
     PopupTick,
+    MenuTick,
+    BarHover(Id, bool),
+    AutoHideTick,
     PopupDismiss(u32),
     PopupClicked(u32),
+    PopupActionKey(usize),
     CloseAllMenus,
+    EscapePressed,
     ResumeFromSleep,
     None,
 }
 
 impl App {
     pub fn new(
-        (logger, config, config_path): (LoggerHandle, Config, PathBuf),
+        (logger, config, config_path, active_profile): (
+            LoggerHandle,
+            Config,
+            PathBuf,
+            Option<String>,
+        ),
     ) -> impl FnOnce() -> (Self, Task<Message>) {
         move || {
-            let (outputs, task) = Outputs::new(
-                config.appearance.style,
-                config.position,
-                config.layer,
-                config.appearance.scale_factor,
-            );
+            let surface = SurfaceSettings::new(&config);
+            let (outputs, task) = Outputs::new(&surface);
 
             let custom = config
                 .custom_modules
@@ -120,16 +145,26 @@ impl App {
                 .map(|o| (o.name.clone(), Custom::new(o)))
                 .collect();
 
+            let mut settings = Settings::new(config.settings);
+            settings.update(modules::settings::Message::ProfilesChanged(
+                config.profile_names(),
+                active_profile.clone(),
+            ));
+
             (
                 App {
                     config_path,
+                    active_profile,
                     theme: AshellTheme::new(config.position, &config.appearance),
                     logger,
                     general_config: GeneralConfig {
                         outputs: config.outputs,
                         modules: config.modules,
-                        layer: config.layer,
+                        surface,
                         enable_esc_key: config.enable_esc_key,
+                        esc_closes_menus: config.esc_closes_menus,
+                        esc_dismisses_popups: config.esc_dismisses_popups,
+                        pause_when_menu_open: config.pause_when_menu_open,
                     },
                     outputs,
                     custom,
@@ -138,14 +173,15 @@ impl App {
                     window_title: WindowTitle::new(config.window_title),
                     system_info: SystemInfo::new(config.system_info),
                     keyboard_layout: KeyboardLayout::new(config.keyboard_layout),
-                    keyboard_submap: KeyboardSubmap::default(),
+                    keyboard_submap: KeyboardSubmap::new(config.keyboard_submap),
+                    lock_keys: LockKeys::new(config.lock_keys),
                     popup_state: PopupState::new(&config.notifications),
                     notifications: Notifications::new(config.notifications.clone()),
-                    tray: TrayModule::default(),
+                    tray: TrayModule::new(config.tray),
                     clock: Clock::new(config.clock),
                     tempo: Tempo::new(config.tempo),
                     privacy: Privacy::default(),
-                    settings: Settings::new(config.settings),
+                    settings,
                     media_player: MediaPlayer::new(config.media_player),
                 },
                 task,
@@ -155,20 +191,42 @@ impl App {
 
     fn refresh_config(&mut self, config: Box<Config>) {
         self.general_config = GeneralConfig {
+            surface: SurfaceSettings::new(&config),
             outputs: config.outputs,
             modules: config.modules,
-            layer: config.layer,
             enable_esc_key: config.enable_esc_key,
+            esc_closes_menus: config.esc_closes_menus,
+            esc_dismisses_popups: config.esc_dismisses_popups,
+            pause_when_menu_open: config.pause_when_menu_open,
         };
         self.theme = AshellTheme::new(config.position, &config.appearance);
-        let custom = config
+
+        // Reuse the modules that are still configured so they keep their state
+        let mut custom = std::mem::take(&mut self.custom);
+        self.custom = config
             .custom_modules
             .into_iter()
-            .map(|o| (o.name.clone(), Custom::new(o)))
+            .map(|o| {
+                let name = o.name.clone();
+                let module = match custom.remove(&name) {
+                    Some(mut module) => {
+                        module.update(custom_module::Message::ConfigReloaded(o));
+                        module
+                    }
+                    None => Custom::new(o),
+                };
+
+                (name, module)
+            })
             .collect();
 
-        self.custom = custom;
-        self.updates = config.updates.map(Updates::new);
+        self.updates = match (self.updates.take(), config.updates) {
+            (Some(mut updates), Some(config)) => {
+                let _ = updates.update(modules::updates::Message::ConfigReloaded(config));
+                Some(updates)
+            }
+            (_, config) => config.map(Updates::new),
+        };
 
         // ignore task, since config change should not generate any
         let _ = self
@@ -183,7 +241,10 @@ impl App {
                 config.window_title,
             ));
 
-        self.system_info = SystemInfo::new(config.system_info);
+        self.system_info
+            .update(modules::system_info::Message::ConfigReloaded(
+                config.system_info,
+            ));
 
         let _ = self
             .keyboard_layout
@@ -191,12 +252,29 @@ impl App {
                 config.keyboard_layout,
             ))
             .map(Message::KeyboardLayout);
+        self.keyboard_submap
+            .update(modules::keyboard_submap::Message::ConfigReloaded(
+                config.keyboard_submap,
+            ));
 
-        self.notifications.config = config.notifications.clone();
+        self.notifications.update_config(config.notifications.clone());
         self.popup_state.update_config(&config.notifications);
-        self.keyboard_submap = KeyboardSubmap::default();
-        self.clock = Clock::new(config.clock);
-        self.tempo = Tempo::new(config.tempo);
+        self.lock_keys
+            .update(modules::lock_keys::Message::ConfigReloaded(
+                config.lock_keys,
+            ));
+        self.tray
+            .update(modules::tray::Message::ConfigReloaded(config.tray));
+        self.clock
+            .update(modules::clock::Message::ConfigReloaded(config.clock));
+        let _ = self
+            .tempo
+            .update(modules::tempo::Message::ConfigReloaded(config.tempo));
+        self.settings
+            .update(modules::settings::Message::ProfilesChanged(
+                config.profile_names(),
+                self.active_profile.clone(),
+            ));
         self.settings
             .update(modules::settings::Message::ConfigReloaded(config.settings));
         self.media_player
@@ -205,6 +283,19 @@ impl App {
             ));
     }
 
+    /// Re-reads the config file with the given profile merged on top, the file
+    /// itself is left untouched.
+    fn switch_profile(&mut self, profile: Option<String>) -> Task<Message> {
+        match config::read_config(&self.config_path, profile.as_deref()) {
+            Ok(config) => {
+                info!("Switching to config profile {profile:?}");
+                self.active_profile = profile;
+                self.update(Message::ConfigChanged(Box::new(config)))
+            }
+            Err(e) => self.update(Message::ConfigError(e.to_string())),
+        }
+    }
+
     pub fn title(&self, _id: Id) -> String {
         String::from("ashell")
     }
@@ -221,8 +312,12 @@ impl App {
         }
     }
 
-    pub fn scale_factor(&self, _id: Id) -> f64 {
-        self.theme.scale_factor
+    /// Extra scaling applied on top of the output scale. The Wayland backend
+    /// already follows each output's (fractional) scale per surface.
+    pub fn scale_factor(&self, id: Id) -> f64 {
+        self.outputs
+            .scale_factor(id)
+            .unwrap_or(self.theme.scale_factor)
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
@@ -234,27 +329,40 @@ impl App {
                     "Current outputs: {:?}, new outputs: {:?}",
                     self.general_config.outputs, config.outputs
                 );
+                let surface = SurfaceSettings::new(&config);
                 if self.general_config.outputs != config.outputs
-                    || self.theme.bar_position != config.position
-                    || self.theme.bar_style != config.appearance.style
-                    || self.theme.scale_factor != config.appearance.scale_factor
-                    || self.general_config.layer != config.layer
+                    || self.general_config.surface != surface
                 {
                     warn!("Outputs changed, syncing");
-                    tasks.push(self.outputs.sync(
-                        config.appearance.style,
-                        &config.outputs,
-                        config.position,
-                        config.layer,
-                        config.appearance.scale_factor,
-                    ));
+                    tasks.push(self.outputs.sync(&surface, &config.outputs));
+                }
+
+                if self.theme.bar_auto_hide && !config.appearance.auto_hide {
+                    tasks.push(self.outputs.reset_auto_hide());
                 }
 
                 self.logger.set_new_spec(get_log_spec(&config.log_level));
+                set_log_format(config.log_format);
                 self.refresh_config(config);
 
                 Task::batch(tasks)
             }
+            Message::ConfigError(error) => {
+                warn!("Keeping the current config, the new one is invalid: {error}");
+
+                let notification = Notification::internal(
+                    InternalPopup::ConfigError,
+                    "ashell".to_string(),
+                    "Invalid config file".to_string(),
+                    error,
+                    None,
+                );
+                let duration = Duration::from_millis(self.notifications.config.popup_duration_ms);
+                self.popup_state.enqueue(notification, duration);
+                Task::none()
+            }
+            Message::ApplyProfile(name) => self.switch_profile(Some(name)),
+            Message::ResetProfile => self.switch_profile(None),
             Message::ToggleMenu(menu_type, id, button_ui_ref) => {
                 let mut cmd = vec![];
                 match &menu_type {
@@ -270,7 +378,19 @@ impl App {
                     MenuType::Notifications => {
                         self.notifications
                             .update(modules::notifications::Message::MenuOpened);
-                        self.popup_state.entries.clear();
+                        self.popup_state.clear();
+                        // The search box needs keyboard focus while the menu is open
+                        if !self.outputs.notification_menu_is_open() {
+                            cmd.push(self.outputs.request_keyboard(id));
+                        }
+                    }
+                    MenuType::Tempo => {
+                        cmd.push(
+                            match self.tempo.update(modules::tempo::Message::MenuOpened) {
+                                modules::tempo::Action::Command(task) => task.map(Message::Tempo),
+                                modules::tempo::Action::None => Task::none(),
+                            },
+                        );
                     }
                     MenuType::Settings => {
                         cmd.push(
@@ -324,10 +444,12 @@ impl App {
                 }
             }
             Message::Workspaces(msg) => self.workspaces.update(msg).map(Message::Workspaces),
-            Message::WindowTitle(msg) => {
-                self.window_title.update(msg);
-                Task::none()
-            }
+            Message::WindowTitle(msg) => match self.window_title.update(msg) {
+                modules::window_title::Action::None => Task::none(),
+                modules::window_title::Action::CopyToClipboard(contents) => {
+                    iced::clipboard::write(contents)
+                }
+            },
             Message::SystemInfo(msg) => {
                 self.system_info.update(msg);
                 Task::none()
@@ -340,6 +462,10 @@ impl App {
                 self.keyboard_submap.update(message);
                 Task::none()
             }
+            Message::LockKeys(message) => {
+                self.lock_keys.update(message);
+                Task::none()
+            }
             Message::Tray(msg) => match self.tray.update(msg) {
                 modules::tray::Action::None => Task::none(),
                 modules::tray::Action::ToggleMenu(name, id, button_ui_ref) => {
@@ -355,6 +481,7 @@ impl App {
                         .close_all_menus(self.general_config.enable_esc_key),
                     task.map(Message::Tray),
                 ]),
+                modules::tray::Action::Command(task) => task.map(Message::Tray),
                 modules::tray::Action::CloseTrayMenu(name) => self
                     .outputs
                     .close_all_menu_if(MenuType::Tray(name), self.general_config.enable_esc_key),
@@ -365,6 +492,7 @@ impl App {
             }
             Message::Tempo(message) => match self.tempo.update(message) {
                 modules::tempo::Action::None => Task::none(),
+                modules::tempo::Action::Command(task) => task.map(Message::Tempo),
             },
             Message::Privacy(msg) => {
                 self.privacy.update(msg);
@@ -384,6 +512,23 @@ impl App {
                         self.outputs.release_keyboard(id),
                     ])
                 }
+                modules::settings::Action::ApplyProfile(name) => {
+                    self.update(Message::ApplyProfile(name))
+                }
+                modules::settings::Action::ResetProfile => self.update(Message::ResetProfile),
+                modules::settings::Action::LowBattery(capacity) => {
+                    let notification = Notification::internal(
+                        InternalPopup::LowBattery,
+                        "ashell".to_string(),
+                        "Low battery".to_string(),
+                        format!("{capacity}% remaining"),
+                        None,
+                    );
+                    let duration =
+                        Duration::from_millis(self.notifications.config.popup_duration_ms);
+                    self.popup_state.enqueue(notification, duration);
+                    Task::none()
+                }
             },
             Message::OutputEvent((event, wl_output)) => match event {
                 iced::event::wayland::OutputEvent::Created(info) => {
@@ -394,24 +539,15 @@ impl App {
                         .unwrap_or("");
 
                     self.outputs.add(
-                        self.theme.bar_style,
+                        &self.general_config.surface,
                         &self.general_config.outputs,
-                        self.theme.bar_position,
-                        self.general_config.layer,
                         name,
                         wl_output,
-                        self.theme.scale_factor,
                     )
                 }
                 iced::event::wayland::OutputEvent::Removed => {
                     info!("Output destroyed");
-                    self.outputs.remove(
-                        self.theme.bar_style,
-                        self.theme.bar_position,
-                        self.general_config.layer,
-                        wl_output,
-                        self.theme.scale_factor,
-                    )
+                    self.outputs.remove(&self.general_config.surface, wl_output)
                 }
                 _ => Task::none(),
             },
@@ -420,10 +556,14 @@ impl App {
                 modules::notifications::Action::EmitSignal(task) => {
                     task.map(Message::Notifications)
                 }
+                modules::notifications::Action::CopyToClipboard(contents) => {
+                    iced::clipboard::write(contents)
+                }
                 modules::notifications::Action::ShowPopup(notification) => {
-                    if !self.notifications.config.popup_enabled
-                        || self.outputs.notification_menu_is_open()
-                    {
+                    let config = &self.notifications.config;
+                    let popup_enabled =
+                        config.popup_enabled || config.is_always_popup(&notification.app_name);
+                    if !popup_enabled || self.outputs.notification_menu_is_open() {
                         return Task::none();
                     }
                     let duration =
@@ -431,17 +571,65 @@ impl App {
                     self.popup_state.enqueue(notification, duration);
                     Task::none()
                 }
+                modules::notifications::Action::ReplayPopups(notifications) => {
+                    // Popups are hidden behind the open menu, close it first
+                    let task = self
+                        .outputs
+                        .close_all_menus(self.general_config.enable_esc_key);
+                    let config = &self.notifications.config;
+                    let duration = Duration::from_millis(config.popup_duration_ms);
+                    for notification in notifications {
+                        if config.popup_enabled || config.is_always_popup(&notification.app_name) {
+                            self.popup_state.enqueue(notification, duration);
+                        }
+                    }
+                    task
+                }
             },
             Message::MediaPlayer(msg) => match self.media_player.update(msg) {
                 modules::media_player::Action::None => Task::none(),
                 modules::media_player::Action::Command(task) => task.map(Message::MediaPlayer),
+                modules::media_player::Action::TrackChanged(track) => {
+                    let notification = Notification::internal(
+                        InternalPopup::TrackChange,
+                        track.player,
+                        track.title,
+                        track.body,
+                        track.art.map(NotificationIcon::Image),
+                    );
+                    let config = &self.notifications.config;
+                    if !(config.popup_enabled || config.is_always_popup(&notification.app_name))
+                        || !self.notifications.can_popup_now(&notification)
+                        || self.outputs.notification_menu_is_open()
+                    {
+                        return Task::none();
+                    }
+
+                    let duration = Duration::from_millis(config.popup_duration_ms);
+                    self.popup_state.enqueue(notification, duration);
+                    Task::none()
+                }
             },
             Message::PopupTick => {
                 self.popup_state.tick();
                 Task::none()
             }
+            Message::MenuTick => self
+                .outputs
+                .tick_menus(Duration::from_millis(self.theme.menu.animation_ms)),
+            Message::BarHover(id, hovered) => {
+                self.outputs.set_bar_hovered(id, hovered);
+                Task::none()
+            }
+            Message::AutoHideTick => self.outputs.tick_auto_hide(
+                Duration::from_millis(self.theme.auto_hide_reveal_delay_ms),
+                Duration::from_millis(self.theme.menu.animation_ms),
+            ),
             Message::PopupDismiss(id) => {
                 self.popup_state.dismiss(id);
+                if InternalPopup::from_id(id).is_some() {
+                    return Task::none();
+                }
                 // Also dismiss from notification service
                 match self
                     .notifications
@@ -455,12 +643,16 @@ impl App {
             }
             Message::PopupClicked(id) => {
                 // Check if notification has a default action
-                let has_default = self
+                let entry = self
                     .popup_state
                     .entries
                     .iter()
-                    .find(|e| e.notification.id == id)
+                    .find(|e| e.notification.id == id);
+                let has_default = entry
                     .is_some_and(|e| e.notification.actions.iter().any(|(k, _)| k == "default"));
+                let desktop_exec = entry
+                    .and_then(|e| e.notification.desktop_exec.clone())
+                    .filter(|_| self.notifications.config.open_app_on_click);
 
                 if has_default {
                     self.popup_state.dismiss(id);
@@ -472,10 +664,23 @@ impl App {
                         }
                         _ => Task::none(),
                     }
+                } else if let Some(exec) = desktop_exec {
+                    // No default action, open the sending app instead
+                    crate::utils::launcher::execute_command(exec);
+                    self.update(Message::PopupDismiss(id))
                 } else {
                     Task::none()
                 }
             }
+            Message::PopupActionKey(index) => {
+                let Some((id, key)) = self.popup_state.front_action(index) else {
+                    return Task::none();
+                };
+                self.popup_state.dismiss(id);
+                self.update(Message::Notifications(
+                    modules::notifications::Message::InvokeAction(id, key),
+                ))
+            }
             Message::CloseAllMenus => {
                 if self.outputs.menu_is_open() {
                     self.outputs
@@ -484,13 +689,22 @@ impl App {
                     Task::none()
                 }
             }
-            Message::ResumeFromSleep => self.outputs.sync(
-                self.theme.bar_style,
-                &self.general_config.outputs,
-                self.theme.bar_position,
-                self.general_config.layer,
-                self.theme.scale_factor,
-            ),
+            // Menus take precedence, a single press never closes a menu and a popup
+            Message::EscapePressed => {
+                if self.outputs.menu_is_open() {
+                    if self.general_config.esc_closes_menus {
+                        return self.update(Message::CloseAllMenus);
+                    }
+                } else if self.general_config.esc_dismisses_popups
+                    && let Some(id) = self.popup_state.front_id()
+                {
+                    return self.update(Message::PopupDismiss(id));
+                }
+                Task::none()
+            }
+            Message::ResumeFromSleep => self
+                .outputs
+                .sync(&self.general_config.surface, &self.general_config.outputs),
             Message::None => Task::none(),
         }
     }
@@ -500,15 +714,27 @@ impl App {
             Some(HasOutput::Main) => {
                 let [left, center, right] = self.modules_section(id, &self.theme);
 
+                let thickness = if self.theme.bar_style == AppearanceStyle::Islands {
+                    HEIGHT
+                } else {
+                    HEIGHT - 8.
+                } as f32;
+                let vertical = self.theme.bar_position.is_vertical();
+
                 let centerbox = Centerbox::new([left, center, right])
                     .spacing(self.theme.space.xxs)
-                    .width(Length::Fill)
+                    .vertical(vertical)
+                    .width(if vertical {
+                        Length::Fixed(thickness)
+                    } else {
+                        Length::Fill
+                    })
                     .align_items(Alignment::Center)
-                    .height(if self.theme.bar_style == AppearanceStyle::Islands {
-                        HEIGHT
+                    .height(if vertical {
+                        Length::Fill
                     } else {
-                        HEIGHT - 8.
-                    } as f32)
+                        Length::Fixed(thickness)
+                    })
                     .padding(if self.theme.bar_style == AppearanceStyle::Islands {
                         [self.theme.space.xxs, self.theme.space.xxs]
                     } else {
@@ -533,22 +759,18 @@ impl App {
                                 Color::TRANSPARENT
                             };
 
+                            // Fade out from the screen edge the bar is anchored to
+                            let (angle, from_edge) = match self.theme.bar_position {
+                                Position::Top => (PI, true),
+                                Position::Bottom => (PI, false),
+                                Position::Left => (PI / 2., true),
+                                Position::Right => (PI / 2., false),
+                            };
+
                             Gradient::Linear(
-                                Linear::new(Radians(PI))
-                                    .add_stop(
-                                        0.0,
-                                        match self.theme.bar_position {
-                                            Position::Top => start_color,
-                                            Position::Bottom => end_color,
-                                        },
-                                    )
-                                    .add_stop(
-                                        1.0,
-                                        match self.theme.bar_position {
-                                            Position::Top => end_color,
-                                            Position::Bottom => start_color,
-                                        },
-                                    ),
+                                Linear::new(Radians(angle))
+                                    .add_stop(0.0, if from_edge { start_color } else { end_color })
+                                    .add_stop(1.0, if from_edge { end_color } else { start_color }),
                             )
                             .into()
                         }),
@@ -569,6 +791,10 @@ impl App {
                             }
                         }
                     },
+                    border: Border {
+                        radius: self.theme.bar_radius.into(),
+                        ..Default::default()
+                    },
                     ..Default::default()
                 });
 
@@ -633,7 +859,13 @@ impl App {
                 ),
                 None => Row::new().into(),
             },
-            Some(HasOutput::Popup) => self.render_popup_bubble(),
+            Some(HasOutput::Popup)
+                if self.notifications.config.popup_all_outputs
+                    || self.outputs.is_primary_popup(id) =>
+            {
+                self.render_popup_bubble()
+            }
+            Some(HasOutput::Popup) => Row::new().into(),
             None => Row::new().into(),
         }
     }
@@ -643,7 +875,11 @@ impl App {
             Subscription::batch(self.modules_subscriptions(&self.general_config.modules.left)),
             Subscription::batch(self.modules_subscriptions(&self.general_config.modules.center)),
             Subscription::batch(self.modules_subscriptions(&self.general_config.modules.right)),
-            config::subscription(&self.config_path),
+            config::subscription(
+                &self.config_path,
+                self.theme.palette_source.path(),
+                self.active_profile.clone(),
+            ),
             crate::services::logind::LogindService::subscribe().map(|event| match event {
                 crate::services::ServiceEvent::Update(_) => Message::ResumeFromSleep,
                 _ => Message::None,
@@ -658,8 +894,8 @@ impl App {
                 iced::Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) => {
                     debug!("Keyboard event received: {key:?}");
                     if matches!(key, keyboard::Key::Named(keyboard::key::Named::Escape)) {
-                        debug!("ESC key pressed, closing all menus");
-                        Some(Message::CloseAllMenus)
+                        debug!("ESC key pressed");
+                        Some(Message::EscapePressed)
                     } else {
                         None
                     }
@@ -668,20 +904,69 @@ impl App {
             }),
         ];
 
+        // Not paused with the modules, a frozen popup would jump ahead when the menu closes
         if self.popup_state.is_active() {
             subs.push(
                 iced::time::every(Duration::from_millis(16)).map(|_| Message::PopupTick),
             );
         }
 
+        // Digits invoke popup actions only while there is something to invoke and no menu
+        // (with possible text inputs) is open
+        if self.popup_state.has_actions() && !self.outputs.menu_is_open() {
+            subs.push(listen_with(|evt, status, _| match (evt, status) {
+                (
+                    iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                        key: keyboard::Key::Character(c),
+                        ..
+                    }),
+                    iced::event::Status::Ignored,
+                ) => match c.parse::<usize>() {
+                    Ok(n @ 1..=9) => Some(Message::PopupActionKey(n - 1)),
+                    _ => None,
+                },
+                _ => None,
+            }));
+        }
+
+        // Keep "5m ago" labels accurate while the notifications menu is open
+        if self.notifications.config.relative_timestamps && self.outputs.notification_menu_is_open()
+        {
+            subs.push(
+                iced::time::every(Duration::from_secs(30)).map(|_| {
+                    Message::Notifications(modules::notifications::Message::TimestampTick)
+                }),
+            );
+        }
+
+        if self.outputs.menu_is_animating() {
+            subs.push(iced::time::every(Duration::from_millis(16)).map(|_| Message::MenuTick));
+        }
+
+        if self.theme.bar_auto_hide {
+            subs.push(listen_with(|evt, _, id| match evt {
+                iced::Event::Mouse(iced::mouse::Event::CursorEntered) => {
+                    Some(Message::BarHover(id, true))
+                }
+                iced::Event::Mouse(iced::mouse::Event::CursorLeft) => {
+                    Some(Message::BarHover(id, false))
+                }
+                _ => None,
+            }));
+
+            if self.outputs.auto_hide_is_animating() {
+                subs.push(
+                    iced::time::every(Duration::from_millis(16)).map(|_| Message::AutoHideTick),
+                );
+            }
+        }
+
         Subscription::batch(subs)
     }
 
     fn render_popup_bubble(&self) -> Element<'_, Message> {
         use iced::widget::{Column, Image, Svg, column, container, horizontal_rule, row, text};
-        use iced::Border;
         use crate::components::icons::{StaticIcon, icon_button};
-        use crate::services::notifications::NotificationIcon;
 
         if self.popup_state.entries.is_empty() {
             return container(Row::new())
@@ -697,49 +982,56 @@ impl App {
         let mut items: Vec<Element<'_, Message>> = Vec::new();
         for (i, entry) in self.popup_state.entries.iter().enumerate() {
             let entry_progress = self.popup_state.entry_progress_staggered_at(entry, i, now);
-            let entry_height = 80.0 * entry_progress.min(1.0); // clamp overshoot for clip
+            // Clamp overshoot for clip
+            let entry_height = self.popup_state.entry_height() * entry_progress.min(1.0);
+            let icon_size = self.popup_state.icon_size;
 
             let n = &entry.notification;
             let id = n.id;
             let time = n.timestamp.format("%H:%M").to_string();
-            let has_default_action = n.actions.iter().any(|(k, _)| k == "default");
+            let clickable = n.actions.iter().any(|(k, _)| k == "default")
+                || (self.notifications.config.open_app_on_click && n.desktop_exec.is_some());
 
             // Icon element
             let icon_element: Option<Element<'_, Message>> =
                 n.icon.as_ref().map(|icon| match icon {
-                    NotificationIcon::Image(handle) => {
-                        Image::new(handle.clone())
-                            .height(Length::Fixed(24.))
-                            .into()
-                    }
+                    NotificationIcon::Image(handle) => Image::new(handle.clone())
+                        .height(Length::Fixed(icon_size))
+                        .into(),
                     NotificationIcon::Svg(handle) => Svg::new(handle.clone())
-                        .height(Length::Fixed(24.))
-                        .width(Length::Fixed(24.))
+                        .height(Length::Fixed(icon_size))
+                        .width(Length::Fixed(icon_size))
                         .into(),
                 });
 
-            let mut text_col = column!(
-                row!(
-                    text(&n.app_name).size(theme.font_size.xs),
-                    text(time)
-                        .size(theme.font_size.xs)
-                        .color(
-                            theme
-                                .get_theme()
-                                .extended_palette()
-                                .secondary
-                                .base
-                                .text
-                        ),
+            let text_col = if self.popup_state.compact {
+                column!(text(&n.summary).size(theme.font_size.sm))
+            } else {
+                column!(
+                    row!(
+                        text(n.display_app_name(&self.notifications.config.fallback_app_name))
+                            .size(theme.font_size.xs),
+                        text(time)
+                            .size(theme.font_size.xs)
+                            .color(
+                                theme
+                                    .get_theme()
+                                    .extended_palette()
+                                    .secondary
+                                    .base
+                                    .text
+                            ),
+                    )
+                    .spacing(theme.space.xs),
+                    text(&n.summary).size(theme.font_size.sm),
                 )
-                .spacing(theme.space.xs),
-                text(&n.summary).size(theme.font_size.sm),
-            )
-            .spacing(2)
-            .width(Length::Fill);
+            };
+            let mut text_col = text_col.spacing(2).width(Length::Fill);
 
-            if !n.body.is_empty() {
-                let truncated = crate::utils::truncate_chars(&n.body, 100);
+            if !self.popup_state.compact && !n.body.is_empty() {
+                let max_lines = self.notifications.config.popup_max_body_lines;
+                let body = crate::utils::truncate_lines(&n.body, max_lines);
+                let truncated = crate::utils::truncate_chars(&body, 100);
                 text_col = text_col.push(text(truncated.to_owned()).size(theme.font_size.xs));
             }
 
@@ -760,7 +1052,7 @@ impl App {
                 .padding([theme.space.xs, 0])
                 .into();
 
-            let notification_or_mouse_area: Element<'_, Message> = if has_default_action {
+            let notification_or_mouse_area: Element<'_, Message> = if clickable {
                 iced::widget::mouse_area(notification_content)
                     .on_press(Message::PopupClicked(id))
                     .into()
@@ -792,6 +1084,9 @@ impl App {
         let width_progress = bubble_progress.min(1.0);
         let extra_h_pad = (1.0 - width_progress) * 40.0;
 
+        // Popups follow the menu opacity unless they have their own
+        let opacity = self.popup_state.opacity.unwrap_or(theme.menu.opacity);
+
         // Styled bubble at full content height
         // Use tighter top padding and smaller top border radius for flush appearance
         let styled_bubble = container(content)
@@ -806,19 +1101,14 @@ impl App {
                 right: theme.space.md as f32 + extra_h_pad,
             })
             .style(move |t: &iced::Theme| iced::widget::container::Style {
-                background: Some(
-                    t.palette()
-                        .background
-                        .scale_alpha(theme.menu.opacity)
-                        .into(),
-                ),
+                background: Some(t.palette().background.scale_alpha(opacity).into()),
                 border: Border {
                     color: t
                         .extended_palette()
                         .secondary
                         .base
                         .color
-                        .scale_alpha(theme.menu.opacity),
+                        .scale_alpha(opacity),
                     width: 1.,
                     radius: if theme.bar_style == AppearanceStyle::Islands {
                         [theme.radius.lg as f32; 4].into()
@@ -828,7 +1118,8 @@ impl App {
                 },
                 ..Default::default()
             })
-            .width(Length::Fill);
+            .width(Length::Fill)
+            .max_width(self.popup_state.max_width);
 
         // Fixed surface height: locks the Wayland surface size to prevent per-frame resizes.
         // Content is aligned toward the bar edge; the transparent gap is invisible on overlay.
@@ -841,14 +1132,16 @@ impl App {
         let target_height = self.popup_state.target_surface_height(top_pad, bottom_pad);
 
         match self.theme.bar_position {
-            Position::Top => container(styled_bubble)
+            Position::Top | Position::Left | Position::Right => container(styled_bubble)
                 .clip(true)
-                .width(Length::Fill)
+                .width(Length::Shrink)
+                .max_width(self.popup_state.max_width)
                 .align_top(target_height)
                 .into(),
             Position::Bottom => container(styled_bubble)
                 .clip(true)
-                .width(Length::Fill)
+                .width(Length::Shrink)
+                .max_width(self.popup_state.max_width)
                 .align_bottom(target_height)
                 .into(),
         }
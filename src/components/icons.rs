@@ -1,4 +1,7 @@
-use crate::theme::AshellTheme;
+use crate::{
+    config::IconsAppearance,
+    theme::{AshellTheme, intern_font_name},
+};
 use iced::{
     Color, Element, Font, Length, Theme,
     widget::{
@@ -7,14 +10,59 @@ use iced::{
         container, text,
     },
 };
+use log::warn;
+use serde::{
+    Deserialize,
+    de::{IntoDeserializer, value::StrDeserializer},
+};
+use std::collections::HashMap;
+
+const NERD_FONT: &str = "Symbols Nerd Font";
+const NERD_FONT_MONO: &str = "Symbols Nerd Font Mono";
+const CUSTOM_ICON_FONT: &str = "Ashell Custom Icon";
+
+/// User overrides for the static icon set, consulted every time an icon is rendered.
+#[derive(Debug, Default, Clone)]
+pub struct IconTheme {
+    font: Option<&'static str>,
+    mono_font: Option<&'static str>,
+    overrides: HashMap<StaticIcon, String>,
+}
+
+impl IconTheme {
+    pub fn new(config: &IconsAppearance) -> Self {
+        let overrides = config
+            .overrides
+            .iter()
+            .filter_map(|(name, glyph)| {
+                let deserializer: StrDeserializer<'_, serde::de::value::Error> =
+                    name.as_str().into_deserializer();
+                match StaticIcon::deserialize(deserializer) {
+                    Ok(icon) => Some((icon, glyph.clone())),
+                    Err(e) => {
+                        warn!("Ignoring the override of icon '{name}': {e}");
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self {
+            font: config.font.as_deref().map(intern_font_name),
+            mono_font: config.mono_font.as_deref().map(intern_font_name),
+            overrides,
+        }
+    }
+}
 
 pub trait Icon {
-    fn to_text<'a>(self) -> Text<'a>;
+    fn to_text<'a>(self, icon_theme: &IconTheme) -> Text<'a>;
 
-    fn to_text_mono<'a>(self) -> Text<'a>;
+    fn to_text_mono<'a>(self, icon_theme: &IconTheme) -> Text<'a>;
 }
 
-#[derive(Copy, Clone, Default)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
 #[allow(dead_code)]
 pub enum StaticIcon {
     #[default]
@@ -25,6 +73,7 @@ pub enum StaticIcon {
     MenuClosed,
     MenuOpen,
     Cpu,
+    Gpu,
     Mem,
     Temp,
     Speaker0,
@@ -72,6 +121,7 @@ pub enum StaticIcon {
     Logout,
     RightArrow,
     Brightness,
+    NightLight,
     Point,
     Close,
     Airplane,
@@ -86,8 +136,12 @@ pub enum StaticIcon {
     DownloadSpeed,
     UploadSpeed,
     Copy,
+    Pin,
+    PinOutline,
     LeftChevron,
     RightChevron,
+    UpChevron,
+    DownChevron,
     Keyboard,
     Mouse,
     Gamepad,
@@ -114,6 +168,7 @@ pub enum StaticIcon {
     Remove,
     Bell,
     BellAlert,
+    BellOff,
 }
 
 impl StaticIcon {
@@ -126,6 +181,7 @@ impl StaticIcon {
             StaticIcon::MenuClosed => "\u{f035f}",
             StaticIcon::MenuOpen => "\u{f035d}",
             StaticIcon::Cpu => "\u{f0502}",
+            StaticIcon::Gpu => "\u{f08ae}",
             StaticIcon::Mem => "\u{efc5}",
             StaticIcon::Temp => "\u{f050f}",
             StaticIcon::Speaker0 => "\u{f0e08}",
@@ -173,6 +229,7 @@ impl StaticIcon {
             StaticIcon::Logout => "\u{f05fd}",
             StaticIcon::RightArrow => "\u{f0054}",
             StaticIcon::Brightness => "\u{f00e0}",
+            StaticIcon::NightLight => "\u{f0594}",
             StaticIcon::Point => "\u{f444}",
             StaticIcon::Close => "\u{f0156}",
             StaticIcon::Airplane => "\u{f001d}",
@@ -187,8 +244,12 @@ impl StaticIcon {
             StaticIcon::DownloadSpeed => "\u{f06f4}",
             StaticIcon::UploadSpeed => "\u{f06f6}",
             StaticIcon::Copy => "\u{f018f}",
+            StaticIcon::Pin => "\u{f0403}",
+            StaticIcon::PinOutline => "\u{f0931}",
             StaticIcon::LeftChevron => "\u{f0141}",
             StaticIcon::RightChevron => "\u{f0142}",
+            StaticIcon::UpChevron => "\u{f0143}",
+            StaticIcon::DownChevron => "\u{f0140}",
             StaticIcon::Keyboard => "\u{f030c}",
             StaticIcon::Mouse => "\u{f037d}",
             StaticIcon::Gamepad => "\u{f05ba}",
@@ -215,6 +276,7 @@ impl StaticIcon {
             StaticIcon::Remove => "\u{f0377}",
             StaticIcon::Bell => "\u{f009a}",
             StaticIcon::BellAlert => "\u{f0205}",
+            StaticIcon::BellOff => "\u{f009b}",
         }
     }
 
@@ -234,8 +296,8 @@ impl StaticIcon {
             | StaticIcon::HeadphoneBatteryMedium
             | StaticIcon::HeadphoneBatteryLow
             | StaticIcon::HeadphoneBatteryAlert
-            | StaticIcon::HeadphoneBatteryCharging => "Ashell Custom Icon",
-            _ => "Symbols Nerd Font",
+            | StaticIcon::HeadphoneBatteryCharging => CUSTOM_ICON_FONT,
+            _ => NERD_FONT,
         }
     }
 
@@ -255,19 +317,44 @@ impl StaticIcon {
             | StaticIcon::HeadphoneBatteryMedium
             | StaticIcon::HeadphoneBatteryLow
             | StaticIcon::HeadphoneBatteryAlert
-            | StaticIcon::HeadphoneBatteryCharging => "Ashell Custom Icon",
-            _ => "Symbols Nerd Font Mono",
+            | StaticIcon::HeadphoneBatteryCharging => CUSTOM_ICON_FONT,
+            _ => NERD_FONT_MONO,
+        }
+    }
+
+    fn themed_text<'a>(self, icon_theme: &IconTheme, mono: bool) -> Text<'a> {
+        let (default_font, nerd_font, configured_font) = if mono {
+            (
+                self.get_font_mono(),
+                NERD_FONT_MONO,
+                icon_theme.mono_font.or(icon_theme.font),
+            )
+        } else {
+            (self.get_font(), NERD_FONT, icon_theme.font)
+        };
+
+        match icon_theme.overrides.get(&self) {
+            // Overridden glyphs never come from the bundled custom icon font
+            Some(glyph) => {
+                text(glyph.clone()).font(Font::with_name(configured_font.unwrap_or(nerd_font)))
+            }
+            None if default_font == CUSTOM_ICON_FONT => {
+                text(self.get_str()).font(Font::with_name(default_font))
+            }
+            None => {
+                text(self.get_str()).font(Font::with_name(configured_font.unwrap_or(default_font)))
+            }
         }
     }
 }
 
 impl Icon for StaticIcon {
-    fn to_text<'a>(self) -> Text<'a> {
-        text(self.get_str()).font(Font::with_name(self.get_font()))
+    fn to_text<'a>(self, icon_theme: &IconTheme) -> Text<'a> {
+        self.themed_text(icon_theme, false)
     }
 
-    fn to_text_mono<'a>(self) -> Text<'a> {
-        text(self.get_str()).font(Font::with_name(self.get_font_mono()))
+    fn to_text_mono<'a>(self, icon_theme: &IconTheme) -> Text<'a> {
+        self.themed_text(icon_theme, true)
     }
 }
 
@@ -275,23 +362,23 @@ impl Icon for StaticIcon {
 pub struct DynamicIcon(pub String);
 
 impl Icon for DynamicIcon {
-    fn to_text<'a>(self) -> Text<'a> {
-        text(self.0).font(Font::with_name("Symbols Nerd Font"))
+    fn to_text<'a>(self, _: &IconTheme) -> Text<'a> {
+        text(self.0).font(Font::with_name(NERD_FONT))
     }
 
-    fn to_text_mono<'a>(self) -> Text<'a> {
+    fn to_text_mono<'a>(self, _: &IconTheme) -> Text<'a> {
         text(self.0)
-            .font(Font::with_name("Symbols Nerd Font Mono"))
+            .font(Font::with_name(NERD_FONT_MONO))
             .line_height(1.0)
     }
 }
 
-pub fn icon<'a>(icon: impl Icon) -> Text<'a> {
-    icon.to_text()
+pub fn icon<'a>(theme: &AshellTheme, icon: impl Icon) -> Text<'a> {
+    icon.to_text(&theme.icons)
 }
 
-pub fn icon_mono<'a>(icon: impl Icon) -> Text<'a> {
-    icon.to_text_mono()
+pub fn icon_mono<'a>(theme: &AshellTheme, icon: impl Icon) -> Text<'a> {
+    icon.to_text_mono(&theme.icons)
 }
 
 pub enum IconButtonSize {
@@ -369,7 +456,7 @@ impl<'a, I: Icon, Message: 'static + Clone> From<IconButton<'a, I, Message>>
 
         let btn = button_fn(
             container(
-                icon_mono(value.icon)
+                icon_mono(value.theme, value.icon)
                     .size(font_size)
                     .color_maybe(value.color),
             )
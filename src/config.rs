@@ -1,8 +1,12 @@
 use crate::app::Message;
 use crate::services::upower::PeripheralDeviceKind;
+use chrono::NaiveTime;
+use chrono_tz::Tz;
 use hex_color::HexColor;
 use iced::futures::StreamExt;
-use iced::{Color, Subscription, futures::SinkExt, stream::channel, theme::palette};
+use iced::{
+    Color, Subscription, futures::SinkExt, stream::channel, theme::palette, widget::tooltip,
+};
 use inotify::EventMask;
 use inotify::Inotify;
 use inotify::WatchMask;
@@ -22,8 +26,11 @@ pub const DEFAULT_CONFIG_FILE_PATH: &str = "~/.config/ashell/config.toml";
 #[serde(default)]
 pub struct Config {
     pub log_level: String,
+    pub log_format: LogFormat,
     pub position: Position,
     pub layer: Layer,
+    pub namespace: String,
+    pub keyboard_interactivity: KeyboardInteractivity,
     pub outputs: Outputs,
     pub modules: Modules,
     #[serde(rename = "CustomModule")]
@@ -38,16 +45,26 @@ pub struct Config {
     pub appearance: Appearance,
     pub media_player: MediaPlayerModuleConfig,
     pub keyboard_layout: KeyboardLayoutModuleConfig,
+    pub keyboard_submap: KeyboardSubmapModuleConfig,
+    pub lock_keys: LockKeysModuleConfig,
+    pub tray: TrayModuleConfig,
     pub notifications: NotificationsModuleConfig,
     pub enable_esc_key: bool,
+    pub esc_closes_menus: bool,
+    pub esc_dismisses_popups: bool,
+    pub pause_when_menu_open: bool,
+    pub profiles: HashMap<String, toml::Table>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             log_level: "warn".to_owned(),
+            log_format: LogFormat::default(),
             position: Position::default(),
             layer: Layer::default(),
+            namespace: "ashell".to_owned(),
+            keyboard_interactivity: KeyboardInteractivity::default(),
             outputs: Outputs::default(),
             modules: Modules::default(),
             updates: None,
@@ -60,25 +77,47 @@ impl Default for Config {
             appearance: Appearance::default(),
             media_player: MediaPlayerModuleConfig::default(),
             keyboard_layout: KeyboardLayoutModuleConfig::default(),
+            keyboard_submap: KeyboardSubmapModuleConfig::default(),
+            lock_keys: LockKeysModuleConfig::default(),
+            tray: TrayModuleConfig::default(),
             notifications: NotificationsModuleConfig::default(),
             custom_modules: vec![],
             enable_esc_key: false,
+            esc_closes_menus: true,
+            esc_dismisses_popups: false,
+            pause_when_menu_open: false,
+            profiles: HashMap::new(),
         }
     }
 }
 
+impl Config {
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names = self.profiles.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct UpdatesModuleConfig {
+    #[serde(alias = "check_command")]
     pub check_cmd: String,
     pub update_cmd: String,
     #[serde(default = "UpdatesModuleConfig::default_interval")]
     pub interval: u64,
+    #[serde(default = "UpdatesModuleConfig::default_check_cooldown")]
+    pub check_cooldown: u64,
 }
 
 impl UpdatesModuleConfig {
     const fn default_interval() -> u64 {
         3600
     }
+
+    const fn default_check_cooldown() -> u64 {
+        10
+    }
 }
 
 #[derive(Deserialize, Copy, Clone, Default, PartialEq, Eq, Debug)]
@@ -89,7 +128,7 @@ pub enum WorkspaceVisibilityMode {
     MonitorSpecificExclusive,
 }
 
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 #[serde(default)]
 pub struct WorkspacesModuleConfig {
     pub visibility_mode: WorkspaceVisibilityMode,
@@ -99,6 +138,29 @@ pub struct WorkspacesModuleConfig {
     pub max_workspaces: Option<u32>,
     pub workspace_names: Vec<String>,
     pub enable_virtual_desktops: bool,
+    /// map of workspace id or name -> label
+    pub label_map: HashMap<String, String>,
+    pub scroll_to_switch: bool,
+    pub invert_scroll: bool,
+    pub animation_ms: u64,
+}
+
+impl Default for WorkspacesModuleConfig {
+    fn default() -> Self {
+        Self {
+            visibility_mode: WorkspaceVisibilityMode::default(),
+            group_by_monitor: false,
+            enable_workspace_filling: false,
+            disable_special_workspaces: false,
+            max_workspaces: None,
+            workspace_names: Vec::new(),
+            enable_virtual_desktops: false,
+            label_map: HashMap::new(),
+            scroll_to_switch: true,
+            invert_scroll: false,
+            animation_ms: 150,
+        }
+    }
 }
 
 #[derive(Deserialize, Copy, Clone, Default, PartialEq, Eq, Debug)]
@@ -110,11 +172,25 @@ pub enum WindowTitleMode {
     InitialClass,
 }
 
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct WindowTitleRewriteRule {
+    /// regex matched against the title
+    #[serde(rename = "match")]
+    pub pattern: String,
+    /// replacement, can reference capture groups (e.g. `$1`)
+    pub replace: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
 #[serde(default)]
 pub struct WindowTitleConfig {
     pub mode: WindowTitleMode,
+    #[serde(alias = "max_length")]
     pub truncate_title_after_length: u32,
+    pub rewrite_rules: Vec<WindowTitleRewriteRule>,
+    pub hide_when_empty: bool,
+    pub click_cmd: Option<String>,
+    pub middle_click_copy: bool,
 }
 
 impl Default for WindowTitleConfig {
@@ -122,14 +198,90 @@ impl Default for WindowTitleConfig {
         Self {
             mode: Default::default(),
             truncate_title_after_length: 150,
+            rewrite_rules: Vec::new(),
+            hide_when_empty: false,
+            click_cmd: None,
+            middle_click_copy: false,
         }
     }
 }
 
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 #[serde(default)]
 pub struct KeyboardLayoutModuleConfig {
     pub labels: HashMap<String, String>,
+    pub cycle_on_click: bool,
+}
+
+impl Default for KeyboardLayoutModuleConfig {
+    fn default() -> Self {
+        Self {
+            labels: HashMap::new(),
+            cycle_on_click: true,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct KeyboardSubmapModuleConfig {
+    pub labels: HashMap<String, String>,
+    pub hide_when_default: bool,
+}
+
+impl Default for KeyboardSubmapModuleConfig {
+    fn default() -> Self {
+        Self {
+            labels: HashMap::new(),
+            hide_when_default: true,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrayClickAction {
+    Activate,
+    SecondaryActivate,
+    Menu,
+    None,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TrayModuleConfig {
+    pub left_click: TrayClickAction,
+    pub right_click: TrayClickAction,
+    pub middle_click: TrayClickAction,
+}
+
+impl Default for TrayModuleConfig {
+    fn default() -> Self {
+        Self {
+            left_click: TrayClickAction::Activate,
+            right_click: TrayClickAction::Menu,
+            middle_click: TrayClickAction::SecondaryActivate,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct LockKeysModuleConfig {
+    pub caps_lock: bool,
+    pub num_lock: bool,
+    pub always_show: bool,
+    pub interval_ms: u64,
+}
+
+impl Default for LockKeysModuleConfig {
+    fn default() -> Self {
+        Self {
+            caps_lock: true,
+            num_lock: false,
+            always_show: false,
+            interval_ms: 250,
+        }
+    }
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -150,6 +302,59 @@ impl Default for SystemInfoCpu {
     }
 }
 
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GpuSource {
+    Nvidia,
+    Amd,
+}
+
+/// Temperature coloring uses the `temperature` thresholds
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SystemInfoGpu {
+    pub source: Option<GpuSource>,
+    pub warn_threshold: u32,
+    pub alert_threshold: u32,
+}
+
+impl Default for SystemInfoGpu {
+    fn default() -> Self {
+        Self {
+            source: None,
+            warn_threshold: 60,
+            alert_threshold: 80,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadAveragePeriod {
+    One,
+    Five,
+    Fifteen,
+}
+
+/// Thresholds are relative to the number of cores, 1.0 means every core is busy
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SystemInfoLoadAverage {
+    pub warn_threshold: f64,
+    pub alert_threshold: f64,
+    pub bar: Vec<LoadAveragePeriod>,
+    pub menu: Vec<LoadAveragePeriod>,
+}
+
+impl Default for SystemInfoLoadAverage {
+    fn default() -> Self {
+        Self {
+            warn_threshold: 0.7,
+            alert_threshold: 1.0,
+            bar: vec![LoadAveragePeriod::One],
+            menu: Vec::new(),
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(default)]
 pub struct SystemInfoMemory {
@@ -200,23 +405,66 @@ impl Default for SystemInfoDisk {
     }
 }
 
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct SystemInfoNetwork {
+    pub interfaces: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SystemInfoGraph {
+    Cpu,
+    Memory,
+    Temperature,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SystemInfoHistory {
+    /// Number of samples kept per graph, 0 disables the graphs
+    pub length: usize,
+    pub graphs: Vec<SystemInfoGraph>,
+}
+
+impl Default for SystemInfoHistory {
+    fn default() -> Self {
+        Self {
+            length: 60,
+            graphs: Vec::new(),
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct SystemInfoDiskIndicatorConfig {
     #[serde(rename = "Disk")]
     pub path: String,
     #[serde(rename = "Name")]
     pub name: Option<String>,
+    #[serde(rename = "Format", default)]
+    pub format: SystemInfoDiskFormat,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SystemInfoDiskFormat {
+    #[default]
+    Percentage,
+    UsedTotal,
 }
 
 #[derive(Clone, Debug, Deserialize)]
 pub enum SystemInfoIndicator {
     Cpu,
+    LoadAverage,
+    GpuUsage,
+    GpuTemperature,
     Memory,
     MemorySwap,
     Temperature,
     IpAddress,
     DownloadSpeed,
     UploadSpeed,
+    NetworkThroughput,
     #[serde(untagged)]
     Disk(SystemInfoDiskIndicatorConfig),
 }
@@ -226,9 +474,14 @@ pub enum SystemInfoIndicator {
 pub struct SystemInfoModuleConfig {
     pub indicators: Vec<SystemInfoIndicator>,
     pub cpu: SystemInfoCpu,
+    pub load_average: SystemInfoLoadAverage,
+    pub gpu: SystemInfoGpu,
     pub memory: SystemInfoMemory,
     pub temperature: SystemInfoTemperature,
     pub disk: SystemInfoDisk,
+    pub network: SystemInfoNetwork,
+    pub history: SystemInfoHistory,
+    pub interval_ms: u64,
 }
 
 impl Default for SystemInfoModuleConfig {
@@ -240,9 +493,14 @@ impl Default for SystemInfoModuleConfig {
                 SystemInfoIndicator::Temperature,
             ],
             cpu: SystemInfoCpu::default(),
+            load_average: SystemInfoLoadAverage::default(),
+            gpu: SystemInfoGpu::default(),
             memory: SystemInfoMemory::default(),
             temperature: SystemInfoTemperature::default(),
             disk: SystemInfoDisk::default(),
+            network: SystemInfoNetwork::default(),
+            history: SystemInfoHistory::default(),
+            interval_ms: 5000,
         }
     }
 }
@@ -266,9 +524,13 @@ pub struct TempoModuleConfig {
     pub clock_format: String,
     #[serde(default)]
     pub weather_location: Option<WeatherLocation>,
+    pub events_cmd: Option<String>,
+    pub locale: Option<String>,
+    pub tooltip_format: Option<String>,
+    pub timezones: Vec<Tz>,
 }
 
-#[derive(Deserialize, Default, Clone, Debug)]
+#[derive(Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum WeatherLocation {
     #[default]
     Current,
@@ -280,6 +542,10 @@ impl Default for TempoModuleConfig {
         Self {
             clock_format: "%a %d %b %R".to_string(),
             weather_location: None,
+            events_cmd: None,
+            locale: None,
+            tooltip_format: None,
+            timezones: Vec::new(),
         }
     }
 }
@@ -327,6 +593,9 @@ pub struct SettingsModuleConfig {
     pub reboot_cmd: String,
     pub logout_cmd: String,
     pub battery_format: SettingsFormat,
+    pub battery_warn_threshold: u8,
+    pub battery_alert_threshold: u8,
+    pub battery_low_notification: bool,
     pub peripheral_indicators: PeripheralIndicators,
     pub peripheral_battery_format: SettingsFormat,
     pub audio_indicator_format: SettingsFormat,
@@ -334,13 +603,20 @@ pub struct SettingsModuleConfig {
     pub network_indicator_format: SettingsFormat,
     pub bluetooth_indicator_format: SettingsFormat,
     pub brightness_indicator_format: SettingsFormat,
+    pub volume_scroll_step: u8,
+    pub volume_scroll_max: u8,
+    pub brightness_scroll_step: u8,
     pub audio_sinks_more_cmd: Option<String>,
     pub audio_sources_more_cmd: Option<String>,
     pub wifi_more_cmd: Option<String>,
     pub vpn_more_cmd: Option<String>,
     pub bluetooth_more_cmd: Option<String>,
+    pub night_light_cmd: Option<String>,
+    pub night_light_warm_temperature: u32,
+    pub night_light_cool_temperature: u32,
     pub remove_airplane_btn: bool,
     pub remove_idle_btn: bool,
+    pub persist_sub_menu: bool,
     pub indicators: Vec<SettingsIndicator>,
     #[serde(rename = "CustomButton")]
     pub custom_buttons: Vec<SettingsCustomButton>,
@@ -356,6 +632,9 @@ impl Default for SettingsModuleConfig {
             reboot_cmd: "systemctl reboot".to_string(),
             logout_cmd: "loginctl kill-user $(whoami)".to_string(),
             battery_format: SettingsFormat::IconAndPercentage,
+            battery_warn_threshold: 30,
+            battery_alert_threshold: 20,
+            battery_low_notification: true,
             peripheral_indicators: Default::default(),
             peripheral_battery_format: SettingsFormat::Icon,
             audio_indicator_format: SettingsFormat::Icon,
@@ -363,13 +642,20 @@ impl Default for SettingsModuleConfig {
             network_indicator_format: SettingsFormat::Icon,
             bluetooth_indicator_format: SettingsFormat::Icon,
             brightness_indicator_format: SettingsFormat::Icon,
+            volume_scroll_step: 5,
+            volume_scroll_max: 100,
+            brightness_scroll_step: 5,
             audio_sinks_more_cmd: Default::default(),
             audio_sources_more_cmd: Default::default(),
             wifi_more_cmd: Default::default(),
             vpn_more_cmd: Default::default(),
             bluetooth_more_cmd: Default::default(),
+            night_light_cmd: Default::default(),
+            night_light_warm_temperature: 3500,
+            night_light_cool_temperature: 6500,
             remove_airplane_btn: Default::default(),
             remove_idle_btn: Default::default(),
+            persist_sub_menu: Default::default(),
             indicators: vec![
                 SettingsIndicator::IdleInhibitor,
                 SettingsIndicator::PowerProfile,
@@ -407,6 +693,17 @@ pub enum MediaPlayerFormat {
 pub struct MediaPlayerModuleConfig {
     pub max_title_length: u32,
     pub indicator_format: MediaPlayerFormat,
+    pub hide_when_empty: bool,
+    pub preferred_players: Vec<String>,
+    pub scroll_to_skip: bool,
+    pub invert_scroll: bool,
+    pub scroll_title: bool,
+    /// Marquee speed in characters per second
+    pub scroll_speed: u32,
+    pub playback_control: bool,
+    pub notify_on_track_change: bool,
+    /// How long a track has to stay current before its popup is shown
+    pub track_change_debounce_ms: u64,
 }
 
 impl Default for MediaPlayerModuleConfig {
@@ -414,10 +711,27 @@ impl Default for MediaPlayerModuleConfig {
         MediaPlayerModuleConfig {
             max_title_length: 100,
             indicator_format: MediaPlayerFormat::default(),
+            hide_when_empty: false,
+            preferred_players: Vec::new(),
+            scroll_to_skip: false,
+            invert_scroll: false,
+            scroll_title: false,
+            scroll_speed: 5,
+            playback_control: false,
+            notify_on_track_change: false,
+            track_change_debounce_ms: 1000,
         }
     }
 }
 
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum NotificationSortOrder {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+    UrgencyThenTime,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(default)]
 pub struct NotificationsModuleConfig {
@@ -425,8 +739,28 @@ pub struct NotificationsModuleConfig {
     pub default_timeout: i32,
     pub popup_enabled: bool,
     pub popup_max_visible: usize,
+    pub popup_queue_size: usize,
     pub popup_duration_ms: u64,
     pub popup_animation_ms: u64,
+    #[serde(deserialize_with = "optional_opacity_deserializer")]
+    pub popup_opacity: Option<f32>,
+    pub popup_max_width: u32,
+    pub badge_show_critical: bool,
+    pub quiet_hours: Option<QuietHours>,
+    pub popup_all_outputs: bool,
+    pub icon_size: u16,
+    pub popup_compact: bool,
+    pub fallback_app_name: String,
+    pub always_popup: Vec<String>,
+    pub relative_timestamps: bool,
+    pub show_sender: bool,
+    pub popup_max_body_lines: usize,
+    pub sound: bool,
+    pub default_sound: String,
+    pub app_sounds: HashMap<String, String>,
+    pub open_app_on_click: bool,
+    pub detect_links: bool,
+    pub sort_order: NotificationSortOrder,
 }
 
 impl Default for NotificationsModuleConfig {
@@ -436,8 +770,55 @@ impl Default for NotificationsModuleConfig {
             default_timeout: 5000,
             popup_enabled: true,
             popup_max_visible: 3,
+            popup_queue_size: 10,
             popup_duration_ms: 5000,
             popup_animation_ms: 200,
+            popup_opacity: None,
+            popup_max_width: 400,
+            badge_show_critical: false,
+            quiet_hours: None,
+            popup_all_outputs: true,
+            icon_size: 24,
+            popup_compact: false,
+            fallback_app_name: "Notification".to_string(),
+            always_popup: Vec::new(),
+            relative_timestamps: false,
+            show_sender: false,
+            popup_max_body_lines: 3,
+            sound: false,
+            default_sound: "message-new-instant".to_string(),
+            app_sounds: HashMap::new(),
+            open_app_on_click: false,
+            detect_links: true,
+            sort_order: NotificationSortOrder::default(),
+        }
+    }
+}
+
+impl NotificationsModuleConfig {
+    /// Whether notifications from `app_name` bypass quiet hours and popup filtering.
+    pub fn is_always_popup(&self, app_name: &str) -> bool {
+        self.always_popup
+            .iter()
+            .any(|app| app.eq_ignore_ascii_case(app_name))
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuietHours {
+    #[serde(deserialize_with = "time_of_day_deserializer")]
+    pub start: NaiveTime,
+    #[serde(deserialize_with = "time_of_day_deserializer")]
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Whether `time` falls inside the window; windows may cross midnight.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
         }
     }
 }
@@ -506,12 +887,50 @@ pub enum AppearanceStyle {
     Gradient,
 }
 
+#[derive(Deserialize, Default, Clone, Eq, PartialEq, Debug)]
+pub enum PaletteSource {
+    #[default]
+    Static,
+    Pywal,
+    File(PathBuf),
+}
+
+impl PaletteSource {
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            PaletteSource::Static => None,
+            PaletteSource::Pywal => std::env::var_os("XDG_CACHE_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+                .map(|cache| cache.join("wal").join("colors.json")),
+            PaletteSource::File(path) => expand_path(path.clone()).ok(),
+        }
+    }
+}
+
+// Subset of the colors.json file written by pywal
+#[derive(Deserialize, Debug)]
+struct PywalColors {
+    special: PywalSpecialColors,
+    colors: HashMap<String, HexColor>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PywalSpecialColors {
+    background: HexColor,
+    foreground: HexColor,
+}
+
 #[derive(Deserialize, Clone, Copy, Debug)]
 #[serde(default)]
 pub struct MenuAppearance {
     #[serde(deserialize_with = "opacity_deserializer")]
     pub opacity: f32,
     pub backdrop: f32,
+    pub blur: bool,
+    pub animation_ms: u64,
+    /// Corner radii as top left, top right, bottom right, bottom left
+    pub radius: Option<[f32; 4]>,
 }
 
 impl Default for MenuAppearance {
@@ -519,20 +938,73 @@ impl Default for MenuAppearance {
         Self {
             opacity: default_opacity(),
             backdrop: f32::default(),
+            blur: false,
+            animation_ms: 150,
+            radius: None,
         }
     }
 }
 
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppearanceMargin {
+    pub top: u16,
+    pub bottom: u16,
+    pub left: u16,
+    pub right: u16,
+}
+
+/// Space reserved for the bar: on (the bar height), off (overlap windows) or a pixel value.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ExclusiveZone {
+    Enabled(bool),
+    Pixels(u32),
+}
+
+impl Default for ExclusiveZone {
+    fn default() -> Self {
+        Self::Enabled(true)
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct SectionAppearance {
+    pub spacing: Option<u16>,
+    pub padding: Option<u16>,
+}
+
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct SectionsAppearance {
+    pub left: SectionAppearance,
+    pub center: SectionAppearance,
+    pub right: SectionAppearance,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(default)]
 pub struct Appearance {
     pub font_name: Option<String>,
+    pub monospace_font_name: Option<String>,
     #[serde(deserialize_with = "scale_factor_deserializer")]
     pub scale_factor: f64,
+    /// Scale factors replacing `scale_factor` on the outputs whose name contains the key
+    #[serde(deserialize_with = "output_scale_factors_deserializer")]
+    pub output_scale_factors: HashMap<String, f64>,
     pub style: AppearanceStyle,
     #[serde(deserialize_with = "opacity_deserializer")]
     pub opacity: f32,
     pub menu: MenuAppearance,
+    pub margin: AppearanceMargin,
+    pub exclusive_zone: ExclusiveZone,
+    /// Corner radii as top left, top right, bottom right, bottom left
+    pub bar_radius: [f32; 4],
+    pub auto_hide: bool,
+    pub auto_hide_reveal_delay_ms: u64,
+    pub sections: SectionsAppearance,
+    pub palette_source: PaletteSource,
     pub background_color: AppearanceColor,
     pub primary_color: AppearanceColor,
     pub secondary_color: AppearanceColor,
@@ -541,31 +1013,88 @@ pub struct Appearance {
     pub text_color: AppearanceColor,
     pub workspace_colors: Vec<AppearanceColor>,
     pub special_workspace_colors: Option<Vec<AppearanceColor>>,
+    pub module_colors: HashMap<ModuleName, HexColor>,
+    pub icons: IconsAppearance,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct IconsAppearance {
+    pub font: Option<String>,
+    pub mono_font: Option<String>,
+    /// Keyed by the snake_case icon name, unknown names are ignored with a warning
+    pub overrides: HashMap<String, String>,
 }
 
 static PRIMARY: HexColor = HexColor::rgb(250, 179, 135);
 
-fn scale_factor_deserializer<'de, D>(deserializer: D) -> Result<f64, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    let v = f64::deserialize(deserializer)?;
+impl Appearance {
+    // Replace the configured colors with the ones from the palette source,
+    // keeping them when the palette can't be read
+    fn apply_palette_source(&mut self) {
+        let Some(path) = self.palette_source.path() else {
+            return;
+        };
+
+        let colors = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<PywalColors>(&content).map_err(|e| e.to_string())
+            });
+
+        match colors {
+            Ok(colors) => {
+                info!("Applying palette from {path:?}");
+
+                self.background_color = AppearanceColor::Simple(colors.special.background);
+                self.text_color = AppearanceColor::Simple(colors.special.foreground);
+                if let Some(accent) = colors.colors.get("color4") {
+                    self.primary_color = AppearanceColor::Complete {
+                        base: *accent,
+                        strong: None,
+                        weak: None,
+                        text: Some(colors.special.background),
+                    };
+                }
+            }
+            Err(e) => {
+                warn!("Failed to read palette file {path:?}: {e}");
+            }
+        }
+    }
+}
 
+fn validate_scale_factor<E: serde::de::Error>(v: f64) -> Result<f64, E> {
     if v <= 0.0 {
-        return Err(serde::de::Error::custom(
-            "Scale factor must be greater than 0.0",
-        ));
+        return Err(E::custom("Scale factor must be greater than 0.0"));
     }
 
     if v > 2.0 {
-        return Err(serde::de::Error::custom(
-            "Scale factor cannot be greater than 2.0",
-        ));
+        return Err(E::custom("Scale factor cannot be greater than 2.0"));
     }
 
     Ok(v)
 }
 
+fn scale_factor_deserializer<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    validate_scale_factor(f64::deserialize(deserializer)?)
+}
+
+fn output_scale_factors_deserializer<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    HashMap::<String, f64>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(output, v)| validate_scale_factor(v).map(|v| (output, v)))
+        .collect()
+}
+
 fn opacity_deserializer<'de, D>(deserializer: D) -> Result<f32, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -585,6 +1114,23 @@ where
     Ok(v)
 }
 
+fn optional_opacity_deserializer<'de, D>(deserializer: D) -> Result<Option<f32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    opacity_deserializer(deserializer).map(Some)
+}
+
+fn time_of_day_deserializer<'de, D>(deserializer: D) -> Result<NaiveTime, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let v = String::deserialize(deserializer)?;
+
+    NaiveTime::parse_from_str(&v, "%H:%M")
+        .map_err(|_| serde::de::Error::custom(format!("Invalid time \"{v}\", expected HH:MM")))
+}
+
 fn default_opacity() -> f32 {
     1.0
 }
@@ -593,10 +1139,19 @@ impl Default for Appearance {
     fn default() -> Self {
         Self {
             font_name: None,
+            monospace_font_name: None,
             scale_factor: 1.0,
+            output_scale_factors: HashMap::new(),
             style: AppearanceStyle::default(),
             opacity: default_opacity(),
             menu: MenuAppearance::default(),
+            margin: AppearanceMargin::default(),
+            exclusive_zone: ExclusiveZone::default(),
+            bar_radius: [0.0; 4],
+            auto_hide: false,
+            auto_hide_reveal_delay_ms: 200,
+            sections: SectionsAppearance::default(),
+            palette_source: PaletteSource::default(),
             background_color: AppearanceColor::Complete {
                 base: HexColor::rgb(30, 30, 46),
                 strong: Some(HexColor::rgb(69, 71, 90)),
@@ -629,6 +1184,19 @@ impl Default for Appearance {
                 AppearanceColor::Simple(HexColor::rgb(203, 166, 247)),
             ],
             special_workspace_colors: None,
+            module_colors: HashMap::new(),
+            icons: IconsAppearance::default(),
+        }
+    }
+}
+
+impl Appearance {
+    /// A hidden bar must not keep reserving space, so auto hide disables the exclusive zone.
+    pub fn bar_exclusive_zone(&self) -> ExclusiveZone {
+        if self.auto_hide {
+            ExclusiveZone::Enabled(false)
+        } else {
+            self.exclusive_zone
         }
     }
 }
@@ -638,16 +1206,49 @@ pub enum Position {
     #[default]
     Top,
     Bottom,
+    Left,
+    Right,
+}
+
+impl Position {
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Position::Left | Position::Right)
+    }
+
+    /// Side of a bar widget its tooltip opens on, facing away from the screen edge.
+    pub fn tooltip_position(self) -> tooltip::Position {
+        match self {
+            Position::Top => tooltip::Position::Bottom,
+            Position::Bottom => tooltip::Position::Top,
+            Position::Left => tooltip::Position::Right,
+            Position::Right => tooltip::Position::Left,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
 }
 
 #[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Layer {
     #[default]
     Bottom,
+    Top,
     Overlay,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyboardInteractivity {
+    #[default]
+    None,
+    OnDemand,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ModuleName {
     Updates,
     Workspaces,
@@ -655,6 +1256,7 @@ pub enum ModuleName {
     SystemInfo,
     KeyboardLayout,
     KeyboardSubmap,
+    LockKeys,
     Tray,
     Clock,
     Tempo,
@@ -687,6 +1289,7 @@ impl<'de> Deserialize<'de> for ModuleName {
                     "SystemInfo" => ModuleName::SystemInfo,
                     "KeyboardLayout" => ModuleName::KeyboardLayout,
                     "KeyboardSubmap" => ModuleName::KeyboardSubmap,
+                    "LockKeys" => ModuleName::LockKeys,
                     "Tray" => ModuleName::Tray,
                     "Clock" => ModuleName::Clock,
                     "Tempo" => ModuleName::Tempo,
@@ -740,6 +1343,8 @@ pub enum Outputs {
     Active,
     #[serde(deserialize_with = "non_empty")]
     Targets(Vec<String>),
+    #[serde(deserialize_with = "non_empty")]
+    Exclude(Vec<String>),
 }
 
 fn non_empty<'de, D, T>(d: D) -> Result<Vec<T>, D::Error>
@@ -796,13 +1401,18 @@ pub enum CustomModuleType {
 #[derive(Deserialize, Clone, Debug)]
 pub struct CustomModuleDef {
     pub name: String,
-    #[serde(default)]
+    /// command launched when the module is clicked
+    #[serde(default, alias = "on_click")]
     pub command: Option<String>,
     #[serde(default)]
     pub icon: Option<String>,
 
     /// yields json lines containing text, alt, (pot tooltip)
     pub listen_cmd: Option<String>,
+    /// polled every `interval_ms`, the first line of stdout is used as text
+    pub exec: Option<String>,
+    #[serde(default = "CustomModuleDef::default_interval_ms")]
+    pub interval_ms: u64,
     /// map of regex -> icon
     pub icons: Option<HashMap<RegexCfg, String>>,
     /// regex to show alert
@@ -813,7 +1423,16 @@ pub struct CustomModuleDef {
     // .. appearance etc
 }
 
-pub fn get_config(path: Option<PathBuf>) -> Result<(Config, PathBuf), Box<dyn Error + Send>> {
+impl CustomModuleDef {
+    const fn default_interval_ms() -> u64 {
+        1000
+    }
+}
+
+pub fn get_config(
+    path: Option<PathBuf>,
+    profile: Option<&str>,
+) -> Result<(Config, PathBuf), Box<dyn Error + Send>> {
     match path {
         Some(p) => {
             info!("Config path provided {p:?}");
@@ -824,7 +1443,10 @@ pub fn get_config(path: Option<PathBuf>) -> Result<(Config, PathBuf), Box<dyn Er
                         format!("Config file does not exist: {}", expanded.display()),
                     )))
                 } else {
-                    Ok((read_config(&expanded).unwrap_or_default(), expanded))
+                    Ok((
+                        read_config(&expanded, profile).unwrap_or_default(),
+                        expanded,
+                    ))
                 }
             })
         }
@@ -841,7 +1463,10 @@ pub fn get_config(path: Option<PathBuf>) -> Result<(Config, PathBuf), Box<dyn Er
                     .map_err(|e| Box::new(e) as Box<dyn Error + Send>)?;
             }
 
-            Ok((read_config(&expanded).unwrap_or_default(), expanded))
+            Ok((
+                read_config(&expanded, profile).unwrap_or_default(),
+                expanded,
+            ))
         }),
     }
 }
@@ -854,17 +1479,16 @@ fn expand_path(path: PathBuf) -> Result<PathBuf, Box<dyn Error + Send>> {
     Ok(PathBuf::from(expanded.to_string()))
 }
 
-fn read_config(path: &Path) -> Result<Config, Box<dyn Error + Send>> {
+pub fn read_config(path: &Path, profile: Option<&str>) -> Result<Config, Box<dyn Error + Send>> {
     let content =
         std::fs::read_to_string(path).map_err(|e| Box::new(e) as Box<dyn Error + Send>)?;
 
     info!("Decoding config file {path:?}");
 
-    let res = toml::from_str(&content);
-
-    match res {
-        Ok(config) => {
+    match parse_config(&content, profile) {
+        Ok(mut config) => {
             info!("Config file loaded successfully");
+            config.appearance.apply_palette_source();
             Ok(config)
         }
         Err(e) => {
@@ -874,31 +1498,89 @@ fn read_config(path: &Path) -> Result<Config, Box<dyn Error + Send>> {
     }
 }
 
+fn parse_config(content: &str, profile: Option<&str>) -> Result<Config, toml::de::Error> {
+    let mut table = toml::from_str::<toml::Table>(content)?;
+
+    if let Some(name) = profile {
+        let overlay = table
+            .get("profiles")
+            .and_then(|profiles| profiles.get(name))
+            .and_then(toml::Value::as_table)
+            .cloned();
+
+        match overlay {
+            Some(overlay) => {
+                info!("Applying config profile {name}");
+                merge_table(&mut table, overlay);
+            }
+            None => warn!("Config profile {name} not found, using the base config"),
+        }
+    }
+
+    toml::Value::Table(table).try_into::<Config>()
+}
+
+/// Merges a profile overlay into the config: tables are merged key by key,
+/// any other value (arrays included) replaces the base one.
+fn merge_table(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => {
+                merge_table(base, overlay);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 enum Event {
     Changed,
     Removed,
 }
 
-pub fn subscription(path: &Path) -> Subscription<Message> {
+pub fn subscription(
+    path: &Path,
+    palette_path: Option<PathBuf>,
+    profile: Option<String>,
+) -> Subscription<Message> {
     let id = TypeId::of::<Config>();
     let path = path.to_path_buf();
 
     Subscription::run_with_id(
-        id,
+        (id, palette_path.clone(), profile.clone()),
         channel(100, async move |mut output| {
             match (path.parent(), path.file_name(), Inotify::init()) {
                 (Some(folder), Some(file_name), Ok(inotify)) => {
                     debug!("Watching config file at {path:?}");
 
-                    let res = inotify.watches().add(
-                        folder,
-                        WatchMask::CREATE | WatchMask::DELETE | WatchMask::MOVE | WatchMask::MODIFY,
-                    );
+                    let watch_mask =
+                        WatchMask::CREATE | WatchMask::DELETE | WatchMask::MOVE | WatchMask::MODIFY;
 
-                    if let Err(e) = res {
-                        error!("Failed to add watch for {folder:?}: {e}");
-                        return;
-                    }
+                    let config_wd = match inotify.watches().add(folder, watch_mask) {
+                        Ok(wd) => wd,
+                        Err(e) => {
+                            error!("Failed to add watch for {folder:?}: {e}");
+                            return;
+                        }
+                    };
+
+                    // Palette changes re-read the config, which applies the new colors
+                    let palette_watch = palette_path.as_ref().and_then(|palette_path| {
+                        let palette_folder = palette_path.parent()?;
+                        let palette_file_name = palette_path.file_name()?.to_owned();
+
+                        debug!("Watching palette file at {palette_path:?}");
+
+                        match inotify.watches().add(palette_folder, watch_mask) {
+                            Ok(wd) => Some((wd, palette_file_name)),
+                            Err(e) => {
+                                warn!("Failed to add watch for {palette_folder:?}: {e}");
+                                None
+                            }
+                        }
+                    });
 
                     let buffer = [0; 1024];
                     let stream = inotify.into_event_stream(buffer);
@@ -922,10 +1604,11 @@ pub fn subscription(path: &Path) -> Subscription<Message> {
                                 debug!("Event: {event:?}");
                                 match event {
                                     Ok(inotify::Event {
+                                        wd,
                                         name: Some(name),
                                         mask,
                                         ..
-                                    }) if file_name == name => {
+                                    }) if wd == config_wd && file_name == name => {
                                         if mask.contains(EventMask::DELETE)
                                             || mask.contains(EventMask::MOVED_FROM)
                                         {
@@ -939,6 +1622,19 @@ pub fn subscription(path: &Path) -> Subscription<Message> {
                                             file_event = Some(Event::Changed);
                                         }
                                     }
+                                    Ok(inotify::Event {
+                                        wd,
+                                        name: Some(name),
+                                        ..
+                                    }) if palette_watch.as_ref().is_some_and(
+                                        |(palette_wd, palette_file_name)| {
+                                            wd == *palette_wd && *palette_file_name == name
+                                        },
+                                    ) =>
+                                    {
+                                        debug!("Palette file changed");
+                                        file_event.get_or_insert(Event::Changed);
+                                    }
                                     _ => {
                                         debug!("Ignoring event");
                                     }
@@ -949,11 +1645,15 @@ pub fn subscription(path: &Path) -> Subscription<Message> {
                                 Some(Event::Changed) => {
                                     info!("Reload config file");
 
-                                    let new_config = read_config(&path).unwrap_or_default();
+                                    // Keep the running config when the new one is invalid
+                                    let message = match read_config(&path, profile.as_deref()) {
+                                        Ok(new_config) => {
+                                            Message::ConfigChanged(Box::new(new_config))
+                                        }
+                                        Err(e) => Message::ConfigError(e.to_string()),
+                                    };
 
-                                    let _ = output
-                                        .send(Message::ConfigChanged(Box::new(new_config)))
-                                        .await;
+                                    let _ = output.send(message).await;
                                 }
                                 Some(Event::Removed) => {
                                     // wait and double check if the file is really gone
@@ -990,3 +1690,55 @@ pub fn subscription(path: &Path) -> Subscription<Message> {
         }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+position = "Top"
+
+[notifications]
+max_notifications = 20
+popup_max_visible = 2
+always_popup = ["Calendar", "Alarm"]
+
+[profiles.focus]
+position = "Bottom"
+
+[profiles.focus.notifications]
+popup_max_visible = 0
+always_popup = ["Alarm"]
+"#;
+
+    #[test]
+    fn nested_tables_are_merged_key_by_key() {
+        let mut base: toml::Table = toml::from_str("[a]\nx = 1\ny = 2\n[a.b]\nz = 3").unwrap();
+        let overlay: toml::Table = toml::from_str("[a]\ny = 20\n[a.b]\nw = 4").unwrap();
+
+        merge_table(&mut base, overlay);
+
+        let expected: toml::Table =
+            toml::from_str("[a]\nx = 1\ny = 20\n[a.b]\nz = 3\nw = 4").unwrap();
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn profile_overrides_values_and_replaces_arrays() {
+        let config = parse_config(CONFIG, Some("focus")).unwrap();
+
+        assert_eq!(config.position, Position::Bottom);
+        assert_eq!(config.notifications.max_notifications, 20);
+        assert_eq!(config.notifications.popup_max_visible, 0);
+        assert_eq!(config.notifications.always_popup, vec!["Alarm"]);
+    }
+
+    #[test]
+    fn unknown_profile_keeps_the_base_config() {
+        let config = parse_config(CONFIG, Some("missing")).unwrap();
+
+        assert_eq!(config.position, Position::Top);
+        assert_eq!(config.notifications.popup_max_visible, 2);
+        assert_eq!(config.notifications.always_popup, vec!["Calendar", "Alarm"]);
+    }
+}
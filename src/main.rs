@@ -1,14 +1,19 @@
-use crate::config::get_config;
+use crate::{
+    config::{LogFormat, get_config},
+    theme::intern_font_name,
+};
 use app::App;
 use clap::Parser;
 use flexi_logger::{
-    Age, Cleanup, Criterion, FileSpec, LogSpecBuilder, LogSpecification, Logger, Naming,
+    Age, Cleanup, Criterion, DeferredNow, FileSpec, LogSpecBuilder, LogSpecification, Logger,
+    Naming,
 };
 use iced::Font;
-use log::{debug, error, warn};
+use log::{Record, debug, error, warn};
 use std::panic;
 use std::path::PathBuf;
-use std::{backtrace::Backtrace, borrow::Cow};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{backtrace::Backtrace, borrow::Cow, io::Write};
 
 mod app;
 mod components;
@@ -37,6 +42,14 @@ const HEIGHT: f64 = 34.;
 struct Args {
     #[arg(short, long, value_parser = clap::value_parser!(PathBuf))]
     config_path: Option<PathBuf>,
+
+    /// Start with the given config profile applied
+    #[arg(short, long)]
+    profile: Option<String>,
+
+    /// Send a test notification to the running notification daemon and exit
+    #[arg(long)]
+    notify_test: bool,
 }
 
 fn get_log_spec(log_level: &str) -> LogSpecification {
@@ -52,16 +65,60 @@ fn get_log_spec(log_level: &str) -> LogSpecification {
     }
 }
 
+const LOG_FORMAT_ENV: &str = "ASHELL_LOG_FORMAT";
+
+static JSON_LOGS: AtomicBool = AtomicBool::new(false);
+
+/// Select the log line format, `ASHELL_LOG_FORMAT` takes precedence over the config.
+fn set_log_format(log_format: LogFormat) {
+    let json = match std::env::var(LOG_FORMAT_ENV) {
+        Ok(value) => value.eq_ignore_ascii_case("json"),
+        Err(_) => log_format == LogFormat::Json,
+    };
+
+    JSON_LOGS.store(json, Ordering::Relaxed);
+}
+
+fn log_format(w: &mut dyn Write, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+    if JSON_LOGS.load(Ordering::Relaxed) {
+        let line = serde_json::json!({
+            "timestamp": now.now().to_rfc3339(),
+            "level": record.level().as_str(),
+            "module": record.module_path().unwrap_or("<unnamed>"),
+            "message": record.args().to_string(),
+        });
+
+        write!(w, "{line}")
+    } else {
+        flexi_logger::default_format(w, now, record)
+    }
+}
+
 #[tokio::main]
 async fn main() -> iced::Result {
     let args = Args::parse();
+    set_log_format(LogFormat::default());
     debug!("args: {args:?}");
 
+    if args.notify_test {
+        match services::notifications::send_test_notification().await {
+            Ok(id) => {
+                println!("Sent test notification with id {id}");
+                std::process::exit(0);
+            }
+            Err(err) => {
+                eprintln!("Failed to send test notification: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     let logger = Logger::with(
         LogSpecBuilder::new()
             .default(log::LevelFilter::Info)
             .build(),
     )
+    .format(log_format)
     .log_to_file(FileSpec::default().directory("/tmp/ashell"))
     .duplicate_to_stdout(flexi_logger::Duplicate::All)
     .rotate(
@@ -80,16 +137,18 @@ async fn main() -> iced::Result {
         error!("Panic: {info} \n {b}");
     }));
 
-    let (config, config_path) = get_config(args.config_path).unwrap_or_else(|err| {
-        error!("Failed to read config: {err}");
+    let (config, config_path) = get_config(args.config_path, args.profile.as_deref())
+        .unwrap_or_else(|err| {
+            error!("Failed to read config: {err}");
 
-        std::process::exit(1);
-    });
+            std::process::exit(1);
+        });
 
     logger.set_new_spec(get_log_spec(&config.log_level));
+    set_log_format(config.log_format);
 
     let font = if let Some(font_name) = &config.appearance.font_name {
-        Font::with_name(Box::leak(font_name.clone().into_boxed_str()))
+        Font::with_name(intern_font_name(font_name))
     } else {
         Font::DEFAULT
     };
@@ -103,5 +162,5 @@ async fn main() -> iced::Result {
         .font(Cow::from(NERD_FONT_MONO))
         .font(Cow::from(CUSTOM_FONT))
         .default_font(font)
-        .run_with(App::new((logger, config, config_path)))
+        .run_with(App::new((logger, config, config_path, args.profile)))
 }
@@ -1,8 +1,9 @@
 use crate::app::{self, App};
 use crate::config::{AppearanceStyle, Position};
+use crate::popup::{ease_in_cubic, ease_out_cubic};
 use crate::theme::backdrop_color;
 use crate::widgets::{self, ButtonUIRef};
-use iced::alignment::Vertical;
+use iced::alignment::{Horizontal, Vertical};
 use iced::platform_specific::shell::commands::layer_surface::{
     KeyboardInteractivity, Layer, set_keyboard_interactivity, set_layer,
 };
@@ -10,6 +11,7 @@ use iced::widget::container::Style;
 use iced::window::Id;
 use iced::{self, Element, Task, Theme, widget::container};
 use iced::{Border, Length, Padding, Pixels};
+use std::time::{Duration, Instant};
 
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub enum MenuType {
@@ -22,10 +24,19 @@ pub enum MenuType {
     Tempo,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuPhase {
+    Opening,
+    Open,
+    Closing,
+}
+
 #[derive(Clone, Debug)]
 pub struct Menu {
     pub id: Id,
     pub menu_info: Option<(MenuType, ButtonUIRef)>,
+    phase: MenuPhase,
+    phase_started: Instant,
 }
 
 impl Menu {
@@ -33,6 +44,56 @@ impl Menu {
         Self {
             id,
             menu_info: None,
+            phase: MenuPhase::Open,
+            phase_started: Instant::now(),
+        }
+    }
+
+    /// A closing menu is still rendered until its animation ends,
+    /// but it's no longer considered open.
+    pub fn is_open(&self) -> bool {
+        self.menu_info.is_some() && self.phase != MenuPhase::Closing
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.menu_info.is_some() && self.phase != MenuPhase::Open
+    }
+
+    /// Visibility progress (0.0-1.0) of the open/close animation.
+    pub fn progress(&self, animation_duration: Duration) -> f32 {
+        let t = if animation_duration.is_zero() {
+            1.0
+        } else {
+            (self.phase_started.elapsed().as_secs_f32() / animation_duration.as_secs_f32()).min(1.0)
+        };
+
+        match self.phase {
+            MenuPhase::Opening => ease_out_cubic(t),
+            MenuPhase::Open => 1.0,
+            MenuPhase::Closing => 1.0 - ease_in_cubic(t),
+        }
+    }
+
+    /// Advance the animation, moving the surface back to the background
+    /// once the close animation ends.
+    pub fn tick<Message: 'static>(&mut self, animation_duration: Duration) -> Task<Message> {
+        if self.phase_started.elapsed() < animation_duration {
+            return Task::none();
+        }
+
+        match self.phase {
+            MenuPhase::Opening => {
+                self.phase = MenuPhase::Open;
+
+                Task::none()
+            }
+            MenuPhase::Closing => {
+                self.phase = MenuPhase::Open;
+                self.menu_info.take();
+
+                set_layer(self.id, Layer::Background)
+            }
+            MenuPhase::Open => Task::none(),
         }
     }
 
@@ -43,6 +104,8 @@ impl Menu {
         request_keyboard: bool,
     ) -> Task<Message> {
         self.menu_info.replace((menu_type, button_ui_ref));
+        self.phase = MenuPhase::Opening;
+        self.phase_started = Instant::now();
 
         let mut tasks = vec![set_layer(self.id, Layer::Overlay)];
 
@@ -57,17 +120,11 @@ impl Menu {
     }
 
     pub fn close<Message: 'static>(&mut self) -> Task<Message> {
-        if self.menu_info.is_some() {
-            self.menu_info.take();
-
-            let mut tasks = vec![set_layer(self.id, Layer::Background)];
-
-            tasks.push(set_keyboard_interactivity(
-                self.id,
-                KeyboardInteractivity::None,
-            ));
+        if self.is_open() {
+            self.phase = MenuPhase::Closing;
+            self.phase_started = Instant::now();
 
-            Task::batch(tasks)
+            set_keyboard_interactivity(self.id, KeyboardInteractivity::None)
         } else {
             Task::none()
         }
@@ -79,6 +136,10 @@ impl Menu {
         button_ui_ref: ButtonUIRef,
         request_keyboard: bool,
     ) -> Task<Message> {
+        if !self.is_open() {
+            return self.open(menu_type, button_ui_ref, request_keyboard);
+        }
+
         match self.menu_info.as_mut() {
             None => self.open(menu_type, button_ui_ref, request_keyboard),
             Some((current_type, _)) if *current_type == menu_type => self.close(),
@@ -91,6 +152,10 @@ impl Menu {
     }
 
     pub fn close_if<Message: 'static>(&mut self, menu_type: MenuType) -> Task<Message> {
+        if !self.is_open() {
+            return Task::none();
+        }
+
         if let Some((current_type, _)) = self.menu_info.as_ref() {
             if *current_type == menu_type {
                 self.close()
@@ -150,27 +215,34 @@ impl App {
         content: Element<'a, app::Message>,
         button_ui_ref: ButtonUIRef,
     ) -> Element<'a, app::Message> {
-        widgets::MenuWrapper::new(
-            button_ui_ref.position.x,
+        const SLIDE_DISTANCE: f32 = 16.;
+
+        let progress = self
+            .outputs
+            .menu_progress(id, Duration::from_millis(self.theme.menu.animation_ms));
+        let opacity = self.theme.menu.opacity * progress;
+        let slide = (1. - progress) * SLIDE_DISTANCE;
+
+        let wrapper = widgets::MenuWrapper::new(
+            button_ui_ref.position.x + self.theme.bar_margin.left as f32,
             container(content)
                 .padding(self.theme.space.md)
                 .style(move |theme: &Theme| Style {
-                    background: Some(
-                        theme
-                            .palette()
-                            .background
-                            .scale_alpha(self.theme.menu.opacity)
-                            .into(),
-                    ),
+                    background: Some(theme.palette().background.scale_alpha(opacity).into()),
                     border: Border {
                         color: theme
                             .extended_palette()
                             .secondary
                             .base
                             .color
-                            .scale_alpha(self.theme.menu.opacity),
+                            .scale_alpha(opacity),
                         width: 1.,
-                        radius: self.theme.radius.lg.into(),
+                        radius: self
+                            .theme
+                            .menu
+                            .radius
+                            .unwrap_or([self.theme.radius.lg as f32; 4])
+                            .into(),
                     },
                     ..Default::default()
                 })
@@ -178,28 +250,33 @@ impl App {
                 .into(),
         )
         .padding({
-            let v_padding = match self.theme.bar_style {
+            let padding = match self.theme.bar_style {
                 AppearanceStyle::Solid | AppearanceStyle::Gradient => 2,
                 AppearanceStyle::Islands => 0,
             };
 
-            Padding::new(0.)
-                .top(if self.theme.bar_position == Position::Top {
-                    v_padding
-                } else {
-                    0
-                })
-                .bottom(if self.theme.bar_position == Position::Bottom {
-                    v_padding
-                } else {
-                    0
-                })
-        })
-        .align_y(match self.theme.bar_position {
-            Position::Top => Vertical::Top,
-            Position::Bottom => Vertical::Bottom,
-        })
-        .backdrop(backdrop_color(self.theme.menu.backdrop))
+            match self.theme.bar_position {
+                Position::Top => Padding::new(0.).top(padding),
+                Position::Bottom => Padding::new(0.).bottom(padding),
+                Position::Left => Padding::new(0.).left(padding),
+                Position::Right => Padding::new(0.).right(padding),
+            }
+        });
+
+        // Slide the menu in from the bar edge while it fades
+        match self.theme.bar_position {
+            Position::Top => wrapper.align_y(Vertical::Top).offset_y(-slide),
+            Position::Bottom => wrapper.align_y(Vertical::Bottom).offset_y(slide),
+            Position::Left => wrapper
+                .y(button_ui_ref.position.y + self.theme.bar_margin.top as f32)
+                .align_x(Horizontal::Left)
+                .offset_x(-slide),
+            Position::Right => wrapper
+                .y(button_ui_ref.position.y + self.theme.bar_margin.top as f32)
+                .align_x(Horizontal::Right)
+                .offset_x(slide),
+        }
+        .backdrop(backdrop_color(self.theme.menu.backdrop * progress))
         .on_click_outside(app::Message::CloseMenu(id))
         .into()
     }
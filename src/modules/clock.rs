@@ -1,12 +1,17 @@
-use crate::{config::ClockModuleConfig, theme::AshellTheme};
+use crate::{
+    config::ClockModuleConfig,
+    theme::AshellTheme,
+    utils::{every_aligned, format_has_seconds},
+};
 use chrono::{DateTime, Local};
-use iced::{Element, Subscription, time::every, widget::text};
+use iced::{Element, Subscription, widget::text};
 use log::warn;
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Update,
+    ConfigReloaded(ClockModuleConfig),
 }
 
 pub struct Clock {
@@ -30,6 +35,9 @@ impl Clock {
             Message::Update => {
                 self.date = Local::now();
             }
+            Message::ConfigReloaded(config) => {
+                self.config = config;
+            }
         }
     }
 
@@ -38,23 +46,12 @@ impl Clock {
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        let second_specifiers = [
-            "%S",  // Seconds (00-60)
-            "%T",  // Hour:Minute:Second
-            "%X",  // Locale time representation with seconds
-            "%r",  // 12-hour clock time with seconds
-            "%:z", // UTC offset with seconds
-            "%s",  // Unix timestamp (seconds since epoch)
-        ];
-        let interval = if second_specifiers
-            .iter()
-            .any(|&spec| self.config.format.contains(spec))
-        {
+        let interval = if format_has_seconds(&self.config.format) {
             Duration::from_secs(1)
         } else {
-            Duration::from_secs(5)
+            Duration::from_secs(60)
         };
 
-        every(interval).map(|_| Message::Update)
+        every_aligned(interval).map(|_| Message::Update)
     }
 }
@@ -2,13 +2,16 @@ use crate::{
     components::icons::{DynamicIcon, StaticIcon, icon},
     config::CustomModuleDef,
     theme::AshellTheme,
-    utils::launcher::execute_command,
+    utils::{
+        IndicatorState,
+        launcher::{command_output, execute_command},
+    },
 };
 use iced::widget::canvas;
 use iced::{
     Element, Length, Subscription, Theme,
     stream::channel,
-    widget::{Stack, row, text},
+    widget::{Stack, row, text, tooltip},
 };
 use iced::{
     mouse::Cursor,
@@ -17,12 +20,13 @@ use iced::{
         container,
     },
 };
-use log::{error, info};
+use log::{error, info, warn};
 use serde::Deserialize;
-use std::{any::TypeId, process::Stdio};
+use std::{any::TypeId, process::Stdio, time::Duration};
 use tokio::{
     io::{AsyncBufReadExt, BufReader},
     process::Command,
+    time::sleep,
 };
 
 #[derive(Debug, Clone)]
@@ -31,16 +35,72 @@ pub struct Custom {
     data: CustomListenData,
 }
 
+/// Waybar compatible module output.
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct CustomListenData {
+    #[serde(default)]
     pub alt: String,
     pub text: Option<String>,
+    pub tooltip: Option<String>,
+    pub class: Option<CustomClass>,
+    pub percentage: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum CustomClass {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl CustomListenData {
+    /// Parse a line of output as JSON, falling back to using it verbatim as text.
+    pub fn parse(line: &str) -> Self {
+        serde_json::from_str(line).unwrap_or_else(|e| {
+            if line.trim_start().starts_with('{') {
+                warn!("Invalid JSON custom module output {line:?}: {e}");
+            }
+
+            Self {
+                alt: line.to_string(),
+                text: Some(line.to_string()),
+                ..Default::default()
+            }
+        })
+    }
+
+    fn label(&self) -> Option<String> {
+        match (&self.text, self.percentage) {
+            (Some(text), _) if !text.is_empty() => Some(text.clone()),
+            (_, Some(percentage)) => Some(format!("{percentage:.0}%")),
+            _ => None,
+        }
+    }
+
+    fn indicator_state(&self) -> IndicatorState {
+        let classes = match &self.class {
+            Some(CustomClass::Single(class)) => std::slice::from_ref(class),
+            Some(CustomClass::Multiple(classes)) => classes.as_slice(),
+            None => &[],
+        };
+
+        classes
+            .iter()
+            .find_map(|class| match class.as_str() {
+                "critical" | "danger" | "urgent" => Some(IndicatorState::Danger),
+                "warning" => Some(IndicatorState::Warning),
+                "good" | "success" => Some(IndicatorState::Success),
+                _ => None,
+            })
+            .unwrap_or(IndicatorState::Normal)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     LaunchCommand,
     Update(CustomListenData),
+    ConfigReloaded(CustomModuleDef),
 }
 
 // Define a struct for the canvas program
@@ -82,6 +142,10 @@ impl Custom {
         self.config.r#type
     }
 
+    pub fn has_command(&self) -> bool {
+        self.config.command.is_some()
+    }
+
     pub fn update(&mut self, msg: Message) {
         match msg {
             Message::LaunchCommand => {
@@ -92,33 +156,57 @@ impl Custom {
             Message::Update(data) => {
                 self.data = data;
             }
+            Message::ConfigReloaded(config) => {
+                // The listen output belongs to the old command
+                if config.listen_cmd != self.config.listen_cmd {
+                    self.data = CustomListenData::default();
+                }
+                self.config = config;
+            }
         }
     }
 
     pub fn view(&'_ self, theme: &AshellTheme) -> Element<'_, Message> {
+        let state = self.data.indicator_state();
+        let content =
+            container(self.content_view(theme)).style(move |theme: &Theme| container::Style {
+                text_color: match state {
+                    IndicatorState::Success => Some(theme.palette().success),
+                    IndicatorState::Warning => Some(theme.extended_palette().danger.weak.color),
+                    IndicatorState::Danger => Some(theme.palette().danger),
+                    IndicatorState::Normal => None,
+                },
+                ..Default::default()
+            });
+
+        match self.data.tooltip.as_ref().filter(|tip| !tip.is_empty()) {
+            Some(tip) => tooltip(
+                content,
+                container(text(tip.clone()).size(theme.font_size.sm))
+                    .padding([theme.space.xxs, theme.space.xs])
+                    .style(container::rounded_box),
+                theme.bar_position.tooltip_position(),
+            )
+            .into(),
+            None => content.into(),
+        }
+    }
+
+    fn content_view(&'_ self, theme: &AshellTheme) -> Element<'_, Message> {
         match self.config.r#type {
-            crate::config::CustomModuleType::Text => self
-                .data
-                .text
-                .as_ref()
-                .and_then(|text_content| {
-                    if !text_content.is_empty() {
-                        Some(text(text_content.clone()).into())
-                    } else {
-                        None
-                    }
-                })
-                .unwrap_or_else(|| text("").into()),
+            crate::config::CustomModuleType::Text => {
+                text(self.data.label().unwrap_or_default()).into()
+            }
             crate::config::CustomModuleType::Button => {
                 let mut icon_element = self.config.icon.as_ref().map_or_else(
-                    || icon(StaticIcon::None),
-                    |text| icon(DynamicIcon(text.clone())),
+                    || icon(theme, StaticIcon::None),
+                    |text| icon(theme, DynamicIcon(text.clone())),
                 );
 
                 if let Some(icons_map) = &self.config.icons {
                     for (re, icon_str) in icons_map {
                         if re.is_match(&self.data.alt) {
-                            icon_element = icon(DynamicIcon(icon_str.clone()));
+                            icon_element = icon(theme, DynamicIcon(icon_str.clone()));
                             break; // Use the first match
                         }
                     }
@@ -155,16 +243,8 @@ impl Custom {
                     padded_icon_container.into() // No alert, just the padded icon
                 };
 
-                let maybe_text_element = self.data.text.as_ref().and_then(|text_content| {
-                    if !text_content.is_empty() {
-                        Some(text(text_content.clone()))
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(text_element) = maybe_text_element {
-                    row![icon_with_alert, text_element]
+                if let Some(label) = self.data.label() {
+                    row![icon_with_alert, text(label)]
                         .spacing(theme.space.xs)
                         .into()
                 } else {
@@ -175,6 +255,37 @@ impl Custom {
     }
 
     pub fn subscription(&self) -> Subscription<(String, Message)> {
+        Subscription::batch([self.listen_subscription(), self.exec_subscription()])
+    }
+
+    fn exec_subscription(&self) -> Subscription<(String, Message)> {
+        let id = TypeId::of::<Self>();
+        let name = self.config.name.clone();
+        let interval = Duration::from_millis(self.config.interval_ms.max(100));
+        if let Some(exec) = self.config.exec.clone() {
+            Subscription::run_with_id(
+                (id, name.clone(), exec.clone(), interval),
+                channel(10, async move |mut output| {
+                    loop {
+                        if let Some(stdout) = command_output(&exec).await {
+                            let data =
+                                CustomListenData::parse(stdout.lines().next().unwrap_or_default());
+
+                            if let Err(e) = output.try_send((name.clone(), Message::Update(data))) {
+                                error!("Failed to send update for custom module '{name}': {e}");
+                            }
+                        }
+
+                        sleep(interval).await;
+                    }
+                }),
+            )
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn listen_subscription(&self) -> Subscription<(String, Message)> {
         let id = TypeId::of::<Self>();
         let name = self.config.name.clone();
         if let Some(listen_cmd) = self.config.listen_cmd.clone() {
@@ -202,21 +313,13 @@ impl Custom {
                                 });
 
                                 while let Some(line) = reader.next_line().await.ok().flatten() {
-                                    match serde_json::from_str(&line) {
-                                        Ok(event) => {
-                                            if let Err(e) = output
-                                                .try_send((name.clone(), Message::Update(event)))
-                                            {
-                                                error!(
-                                                    "Failed to send update for custom module '{name}': {e}"
-                                                );
-                                            }
-                                        }
-                                        Err(e) => {
-                                            error!(
-                                                "Failed to parse JSON for custom module '{name}': {e} (payload: {line})"
-                                            );
-                                        }
+                                    let event = CustomListenData::parse(&line);
+                                    if let Err(e) =
+                                        output.try_send((name.clone(), Message::Update(event)))
+                                    {
+                                        error!(
+                                            "Failed to send update for custom module '{name}': {e}"
+                                        );
                                     }
                                 }
                             } else {
@@ -234,3 +337,49 @@ impl Custom {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json() {
+        let data = CustomListenData::parse(
+            r#"{"text": "5 updates", "alt": "updates", "class": ["a", "warning"]}"#,
+        );
+
+        assert_eq!(data.text.as_deref(), Some("5 updates"));
+        assert_eq!(data.alt, "updates");
+        assert_eq!(data.label().as_deref(), Some("5 updates"));
+        assert!(matches!(data.indicator_state(), IndicatorState::Warning));
+    }
+
+    #[test]
+    fn parse_plain_text() {
+        let data = CustomListenData::parse("hello");
+
+        assert_eq!(data.text.as_deref(), Some("hello"));
+        assert_eq!(data.alt, "hello");
+        assert!(matches!(data.indicator_state(), IndicatorState::Normal));
+    }
+
+    #[test]
+    fn parse_invalid_json_as_text() {
+        let data = CustomListenData::parse(r#"{"text": "#);
+
+        assert_eq!(data.text.as_deref(), Some(r#"{"text": "#));
+    }
+
+    #[test]
+    fn percentage_label() {
+        let label = |line| CustomListenData::parse(line).label();
+
+        assert_eq!(label(r#"{"percentage": 42}"#).as_deref(), Some("42%"));
+        assert_eq!(label(r#"{"percentage": 42.6}"#).as_deref(), Some("43%"));
+        assert_eq!(
+            label(r#"{"text": "", "percentage": 7}"#).as_deref(),
+            Some("7%")
+        );
+        assert_eq!(label("{}"), None);
+    }
+}
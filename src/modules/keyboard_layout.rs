@@ -28,6 +28,10 @@ impl KeyboardLayout {
         }
     }
 
+    pub fn cycle_on_click(&self) -> bool {
+        self.config.cycle_on_click
+    }
+
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::ServiceEvent(event) => {
@@ -43,7 +47,9 @@ impl KeyboardLayout {
                 Task::none()
             }
             Message::ChangeLayout => {
-                if let Some(service) = &mut self.service {
+                if let Some(service) = &mut self.service
+                    && self.config.cycle_on_click
+                {
                     return service
                         .command(CompositorCommand::NextLayout)
                         .map(Message::ServiceEvent);
@@ -1,22 +1,31 @@
 use crate::{
+    config::KeyboardSubmapModuleConfig,
     services::{ReadOnlyService, ServiceEvent, compositor::CompositorService},
     theme::AshellTheme,
 };
 use iced::{Element, Subscription, widget::text};
 
+// Name Hyprland gives the submap active when no other one is
+const DEFAULT_SUBMAP: &str = "default";
+
 #[derive(Debug, Clone)]
 pub enum Message {
     ServiceEvent(ServiceEvent<CompositorService>),
+    ConfigReloaded(KeyboardSubmapModuleConfig),
 }
 
 #[derive(Debug, Clone)]
 pub struct KeyboardSubmap {
+    config: KeyboardSubmapModuleConfig,
     service: Option<CompositorService>,
 }
 
 impl KeyboardSubmap {
-    pub fn default() -> Self {
-        Self { service: None }
+    pub fn new(config: KeyboardSubmapModuleConfig) -> Self {
+        Self {
+            config,
+            service: None,
+        }
     }
 
     pub fn update(&mut self, message: Message) {
@@ -30,17 +39,30 @@ impl KeyboardSubmap {
                 }
                 _ => {}
             },
+            Message::ConfigReloaded(config) => self.config = config,
         }
     }
 
     pub fn view(&self, _: &AshellTheme) -> Option<Element<'_, Message>> {
-        let submap = self.service.as_ref()?.submap.as_ref()?;
+        let submap = self
+            .service
+            .as_ref()?
+            .submap
+            .as_deref()
+            .filter(|submap| !submap.is_empty())
+            .unwrap_or(DEFAULT_SUBMAP);
 
-        if !submap.is_empty() {
-            Some(text(submap).into())
-        } else {
-            None
+        if submap == DEFAULT_SUBMAP && self.config.hide_when_default {
+            return None;
         }
+
+        let label = self
+            .config
+            .labels
+            .get(submap)
+            .map_or(submap, String::as_str);
+
+        Some(text(label).into())
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
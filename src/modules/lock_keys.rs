@@ -0,0 +1,121 @@
+use crate::{config::LockKeysModuleConfig, theme::AshellTheme};
+use iced::{
+    Element, Subscription, Theme,
+    stream::channel,
+    widget::{Row, text},
+};
+use std::{any::TypeId, time::Duration};
+use tokio::{fs, time::sleep};
+
+const LEDS_PATH: &str = "/sys/class/leds";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LockState {
+    pub caps_lock: bool,
+    pub num_lock: bool,
+}
+
+impl LockState {
+    async fn read() -> Self {
+        let mut state = Self::default();
+
+        if let Ok(mut entries) = fs::read_dir(LEDS_PATH).await {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let is_on = async || {
+                    fs::read_to_string(entry.path().join("brightness"))
+                        .await
+                        .is_ok_and(|value| value.trim() != "0")
+                };
+
+                if name.ends_with("::capslock") {
+                    state.caps_lock |= is_on().await;
+                } else if name.ends_with("::numlock") {
+                    state.num_lock |= is_on().await;
+                }
+            }
+        }
+
+        state
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Update(LockState),
+    ConfigReloaded(LockKeysModuleConfig),
+}
+
+pub struct LockKeys {
+    config: LockKeysModuleConfig,
+    state: LockState,
+}
+
+impl LockKeys {
+    pub fn new(config: LockKeysModuleConfig) -> Self {
+        Self {
+            config,
+            // Filled in by the first poll of the subscription
+            state: LockState::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Update(state) => {
+                self.state = state;
+            }
+            Message::ConfigReloaded(config) => {
+                self.config = config;
+            }
+        }
+    }
+
+    pub fn view(&'_ self, theme: &AshellTheme) -> Option<Element<'_, Message>> {
+        let lock = |label: &'static str, enabled: bool, active: bool| {
+            (enabled && (active || self.config.always_show)).then(|| {
+                text(label).style(move |theme: &Theme| text::Style {
+                    color: (!active).then(|| theme.extended_palette().secondary.base.color),
+                })
+            })
+        };
+
+        let caps_lock = lock("CAPS", self.config.caps_lock, self.state.caps_lock);
+        let num_lock = lock("NUM", self.config.num_lock, self.state.num_lock);
+
+        if caps_lock.is_none() && num_lock.is_none() {
+            return None;
+        }
+
+        Some(
+            Row::new()
+                .push_maybe(caps_lock)
+                .push_maybe(num_lock)
+                .spacing(theme.space.xs)
+                .into(),
+        )
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let id = TypeId::of::<Self>();
+        let interval = Duration::from_millis(self.config.interval_ms.max(50));
+
+        Subscription::run_with_id(
+            (id, interval),
+            channel(10, async move |mut output| {
+                let mut last_state = None;
+
+                loop {
+                    let state = LockState::read().await;
+
+                    if last_state != Some(state) {
+                        last_state = Some(state);
+                        let _ = output.try_send(Message::Update(state));
+                    }
+
+                    sleep(interval).await;
+                }
+            }),
+        )
+    }
+}
@@ -14,27 +14,73 @@ use crate::{
 use iced::{
     Background, Border, Element, Length, Subscription, Task, Theme,
     alignment::Vertical,
-    widget::{Column, column, container, horizontal_rule, row, slider, text},
+    mouse::ScrollDelta,
+    time::every,
+    widget::{
+        Column, button, column, container, horizontal_rule, image, mouse_area, row, slider, text,
+    },
+};
+use log::warn;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
 };
 
+const ALBUM_ART_SIZE: f32 = 48.;
+// Separates the end of a scrolling title from its next repetition
+const MARQUEE_GAP: &str = "   ";
+// Scrolling faster than about one step per frame only burns CPU
+const MARQUEE_MIN_INTERVAL: Duration = Duration::from_millis(16);
+
 #[derive(Debug, Clone)]
 pub enum Message {
     Prev(String),
     PlayPause(String),
     Next(String),
     SetVolume(String, f64),
+    Seek(String, f64),
+    SeekReleased,
+    RefreshPosition,
+    AlbumArtLoaded(String, Option<image::Handle>),
     Event(ServiceEvent<MprisPlayerService>),
+    SelectPlayer(String),
+    Scroll(i32),
+    ScrollAccumulator(f32),
+    MarqueeTick,
     ConfigReloaded(MediaPlayerModuleConfig),
+    TrackSettled(String),
+}
+
+pub struct TrackChange {
+    pub player: String,
+    pub title: String,
+    pub body: String,
+    pub art: Option<image::Handle>,
 }
 
 pub enum Action {
     None,
     Command(Task<Message>),
+    TrackChanged(TrackChange),
 }
 
 pub struct MediaPlayer {
     config: MediaPlayerModuleConfig,
     service: Option<MprisPlayerService>,
+    seek: Option<(String, f64)>,
+    /// Player picked in the menu, shown in the bar while it's running
+    selected: Option<String>,
+    scroll_accumulator: f32,
+    /// Title scrolling in the bar and how many characters it has moved
+    marquee: (String, usize),
+    /// Album art by url, `None` while loading or when the art isn't available
+    album_arts: HashMap<String, Option<image::Handle>>,
+    /// Album art urls still being downloaded
+    loading_album_arts: HashSet<String>,
+    /// Last track shown in the bar that was announced, or current at startup
+    announced_track: Option<String>,
+    /// Settled track whose popup waits for its album art to download
+    awaiting_album_art: Option<String>,
 }
 
 impl MediaPlayer {
@@ -42,6 +88,14 @@ impl MediaPlayer {
         Self {
             config,
             service: None,
+            seek: None,
+            selected: None,
+            scroll_accumulator: 0.,
+            marquee: (String::new(), 0),
+            album_arts: HashMap::new(),
+            loading_album_arts: HashSet::new(),
+            announced_track: None,
+            awaiting_album_art: None,
         }
     }
 
@@ -55,98 +109,359 @@ impl MediaPlayer {
             Message::SetVolume(s, v) => {
                 Action::Command(self.handle_command(s, PlayerCommand::Volume(v)))
             }
+            Message::Seek(s, v) => {
+                self.seek = Some((s, v));
+                Action::None
+            }
+            Message::SeekReleased => match self.seek.take() {
+                Some((s, v)) => Action::Command(
+                    self.handle_command(s, PlayerCommand::SetPosition((v * 1_000_000.) as i64)),
+                ),
+                None => Action::None,
+            },
+            Message::RefreshPosition => match self.service.as_ref() {
+                Some(s) => Action::Command(s.refresh().map(Message::Event)),
+                None => Action::None,
+            },
+            Message::AlbumArtLoaded(url, handle) => {
+                self.loading_album_arts.remove(&url);
+                if let Some(art) = self.album_arts.get_mut(&url) {
+                    *art = handle;
+                }
+
+                match self.awaiting_album_art.take() {
+                    Some(title) => self.update(Message::TrackSettled(title)),
+                    None => Action::None,
+                }
+            }
             Message::Event(event) => match event {
                 ServiceEvent::Init(s) => {
                     self.service = Some(s);
-                    Action::None
+                    // Tracks already playing at startup aren't announced
+                    self.announced_track = self.full_title();
+                    Action::Command(self.load_album_arts())
                 }
                 ServiceEvent::Update(d) => {
                     if let Some(service) = self.service.as_mut() {
                         service.update(d);
                     }
-                    Action::None
+                    Action::Command(Task::batch([
+                        self.load_album_arts(),
+                        self.debounce_track_change(),
+                    ]))
                 }
                 ServiceEvent::Error(_) => Action::None,
             },
+            Message::SelectPlayer(service) => {
+                self.selected = Some(service);
+                Action::None
+            }
+            Message::Scroll(direction) => {
+                self.scroll_accumulator = 0.;
+
+                let direction = if self.config.invert_scroll {
+                    -direction
+                } else {
+                    direction
+                };
+                let Some(service) = self
+                    .service
+                    .as_ref()
+                    .and_then(|s| self.active_player(s))
+                    .map(|d| d.service.clone())
+                else {
+                    return Action::None;
+                };
+
+                // Scrolling up goes back, scrolling down skips ahead
+                let command = if direction > 0 {
+                    PlayerCommand::Prev
+                } else {
+                    PlayerCommand::Next
+                };
+                Action::Command(self.handle_command(service, command))
+            }
+            Message::ScrollAccumulator(value) => {
+                if value == 0. {
+                    self.scroll_accumulator = 0.;
+                } else {
+                    self.scroll_accumulator += value;
+                }
+                Action::None
+            }
+            Message::MarqueeTick => {
+                let title = self.full_title().unwrap_or_default();
+                if title == self.marquee.0 {
+                    self.marquee.1 = self.marquee.1.wrapping_add(1);
+                } else {
+                    // A new track starts scrolling from its beginning
+                    self.marquee = (title, 0);
+                }
+                Action::None
+            }
             Message::ConfigReloaded(c) => {
                 self.config = c;
                 Action::None
             }
+            Message::TrackSettled(title) => {
+                // Skipped through, or already announced by an earlier timer
+                if self.full_title().as_ref() != Some(&title)
+                    || self.announced_track.as_ref() == Some(&title)
+                {
+                    return Action::None;
+                }
+
+                // Show the popup with the art once it's downloaded, or failed to
+                let art_url = self
+                    .service
+                    .as_ref()
+                    .and_then(|s| self.active_player(s))
+                    .and_then(|d| d.metadata.as_ref())
+                    .and_then(|m| m.art_url.as_ref());
+                if art_url.is_some_and(|url| self.loading_album_arts.contains(url)) {
+                    self.awaiting_album_art = Some(title);
+                    return Action::None;
+                }
+                self.announced_track = Some(title);
+
+                self.service
+                    .as_ref()
+                    .and_then(|s| self.active_player(s))
+                    .and_then(|d| d.metadata.as_ref().map(|m| (d, m)))
+                    .map_or(Action::None, |(d, metadata)| {
+                        let artists = metadata.artists.as_ref().map(|a| a.join(", "));
+                        let body = [artists.as_deref(), metadata.album.as_deref()]
+                            .into_iter()
+                            .flatten()
+                            .filter(|s| !s.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(" — ");
+
+                        Action::TrackChanged(TrackChange {
+                            player: player_name(&d.service).to_string(),
+                            title: metadata.title.clone().unwrap_or_default(),
+                            body,
+                            art: metadata
+                                .art_url
+                                .as_ref()
+                                .and_then(|url| self.album_arts.get(url).cloned().flatten()),
+                        })
+                    })
+            }
+        }
+    }
+
+    /// Announce the track shown in the bar once it stayed current for the debounce
+    /// delay, so skipping through tracks or seeking doesn't stack popups.
+    fn debounce_track_change(&self) -> Task<Message> {
+        match self.full_title() {
+            Some(title)
+                if self.config.notify_on_track_change
+                    && !title.is_empty()
+                    && self.announced_track.as_ref() != Some(&title) =>
+            {
+                let delay = Duration::from_millis(self.config.track_change_debounce_ms);
+                Task::perform(tokio::time::sleep(delay), move |_| {
+                    Message::TrackSettled(title)
+                })
+            }
+            _ => Task::none(),
         }
     }
 
     pub fn menu_view<'a>(&'a self, theme: &'a AshellTheme) -> Element<'a, Message> {
         container(match &self.service {
             None => Into::<Element<'a, Message>>::into(text("Not connected to MPRIS service")),
-            Some(s) => column!(
-                text("Players").size(theme.font_size.lg),
-                horizontal_rule(1),
-                column(s.iter().map(|d| {
-                    let title = text(self.get_title(d))
-                        .wrapping(text::Wrapping::WordOrGlyph)
-                        .width(Length::Fill);
-
-                    let play_pause_icon = match d.state {
-                        PlaybackStatus::Playing => StaticIcon::Pause,
-                        PlaybackStatus::Paused | PlaybackStatus::Stopped => StaticIcon::Play,
-                    };
-
-                    let buttons = row![
-                        icon_button(theme, StaticIcon::SkipPrevious)
-                            .on_press(Message::Prev(d.service.clone()))
-                            .size(IconButtonSize::Large),
-                        icon_button(theme, play_pause_icon)
-                            .on_press(Message::PlayPause(d.service.clone()))
-                            .size(IconButtonSize::Large),
-                        icon_button(theme, StaticIcon::SkipNext)
-                            .on_press(Message::Next(d.service.clone()))
-                            .size(IconButtonSize::Large),
-                    ]
-                    .align_y(Vertical::Center)
-                    .spacing(theme.space.xs);
+            Some(s) => {
+                let active = self.active_player(s).map(|d| d.service.as_str());
+                let multiple = s.len() > 1;
 
-                    let volume_slider = d.volume.map(|v| {
-                        slider(0.0..=100.0, v, move |v| {
-                            Message::SetVolume(d.service.clone(), v)
-                        })
-                    });
+                column!(
+                    text("Players").size(theme.font_size.lg),
+                    horizontal_rule(1),
+                    column(s.iter().map(|d| {
+                        let title = text(self.get_title(d))
+                            .wrapping(text::Wrapping::WordOrGlyph)
+                            .width(Length::Fill);
+
+                        let play_pause_icon = play_pause_icon(d.state);
+
+                        let buttons = row![
+                            icon_button(theme, StaticIcon::SkipPrevious)
+                                .on_press(Message::Prev(d.service.clone()))
+                                .size(IconButtonSize::Large),
+                            icon_button(theme, play_pause_icon)
+                                .on_press(Message::PlayPause(d.service.clone()))
+                                .size(IconButtonSize::Large),
+                            icon_button(theme, StaticIcon::SkipNext)
+                                .on_press(Message::Next(d.service.clone()))
+                                .size(IconButtonSize::Large),
+                        ]
+                        .align_y(Vertical::Center)
+                        .spacing(theme.space.xs);
+
+                        let volume_slider = d.volume.map(|v| {
+                            slider(0.0..=100.0, v, move |v| {
+                                Message::SetVolume(d.service.clone(), v)
+                            })
+                        });
 
-                    container(
-                        Column::new()
-                            .push(
-                                row!(title, buttons)
-                                    .spacing(theme.space.xs)
-                                    .align_y(Vertical::Center),
+                        let seek_slider = self.seek_slider(d, theme);
+
+                        // Pick which player the bar shows when there is more than one
+                        let selector = multiple.then(|| {
+                            let is_active = active == Some(d.service.as_str());
+
+                            row!(
+                                text(player_name(&d.service))
+                                    .size(theme.font_size.sm)
+                                    .width(Length::Fill),
+                                button(text(if is_active { "In bar" } else { "Show in bar" }))
+                                    .style(theme.ghost_button_style())
+                                    .padding([2, theme.space.xs])
+                                    .on_press_maybe(
+                                        (!is_active)
+                                            .then(|| Message::SelectPlayer(d.service.clone())),
+                                    )
                             )
-                            .push_maybe(volume_slider)
-                            .spacing(theme.space.xs),
-                    )
-                    .style(move |app_theme: &Theme| container::Style {
-                        background: Background::Color(
-                            app_theme
-                                .extended_palette()
-                                .secondary
-                                .strong
-                                .color
-                                .scale_alpha(theme.opacity),
+                            .align_y(Vertical::Center)
+                        });
+
+                        container(
+                            Column::new()
+                                .push_maybe(selector)
+                                .push(
+                                    row!(self.album_art(d, theme), title, buttons)
+                                        .spacing(theme.space.xs)
+                                        .align_y(Vertical::Center),
+                                )
+                                .push_maybe(seek_slider)
+                                .push_maybe(volume_slider)
+                                .spacing(theme.space.xs),
                         )
-                        .into(),
-                        border: Border::default().rounded(theme.radius.lg),
-                        ..container::Style::default()
-                    })
-                    .padding(theme.space.md)
-                    .width(Length::Fill)
-                    .into()
-                }))
-                .spacing(theme.space.md)
-            )
-            .spacing(theme.space.xs)
-            .into(),
+                        .style(move |app_theme: &Theme| container::Style {
+                            background: Background::Color(
+                                app_theme
+                                    .extended_palette()
+                                    .secondary
+                                    .strong
+                                    .color
+                                    .scale_alpha(theme.opacity),
+                            )
+                            .into(),
+                            border: Border::default().rounded(theme.radius.lg),
+                            ..container::Style::default()
+                        })
+                        .padding(theme.space.md)
+                        .width(Length::Fill)
+                        .into()
+                    }))
+                    .spacing(theme.space.md)
+                )
+                .spacing(theme.space.xs)
+                .into()
+            }
         })
         .max_width(MenuSize::Large)
         .into()
     }
 
+    /// Load the art of the current tracks, keeping only the ones still in use.
+    fn load_album_arts(&mut self) -> Task<Message> {
+        let Some(service) = self.service.as_ref() else {
+            return Task::none();
+        };
+        let urls = service
+            .iter()
+            .filter_map(|d| d.metadata.as_ref().and_then(|m| m.art_url.clone()))
+            .collect::<Vec<_>>();
+
+        self.album_arts.retain(|url, _| urls.contains(url));
+        self.loading_album_arts.retain(|url| urls.contains(url));
+
+        let mut tasks = Vec::new();
+        for url in urls {
+            if self.album_arts.contains_key(&url) {
+                continue;
+            }
+
+            if url.starts_with("http://") || url.starts_with("https://") {
+                self.album_arts.insert(url.clone(), None);
+                self.loading_album_arts.insert(url.clone());
+                tasks.push(Task::perform(fetch_album_art(url.clone()), move |handle| {
+                    Message::AlbumArtLoaded(
+                        url.clone(),
+                        handle
+                            .inspect_err(|e| warn!("Failed to fetch album art {url}: {e}"))
+                            .ok(),
+                    )
+                }));
+            } else {
+                let handle = reqwest::Url::parse(&url)
+                    .ok()
+                    .and_then(|u| u.to_file_path().ok())
+                    .filter(|path| path.exists())
+                    .map(image::Handle::from_path);
+                self.album_arts.insert(url, handle);
+            }
+        }
+
+        Task::batch(tasks)
+    }
+
+    fn album_art<'a>(&self, d: &MprisPlayerData, theme: &'a AshellTheme) -> Element<'a, Message> {
+        match d
+            .metadata
+            .as_ref()
+            .and_then(|m| m.art_url.as_ref())
+            .and_then(|url| self.album_arts.get(url).cloned().flatten())
+        {
+            Some(handle) => image(handle)
+                .width(Length::Fixed(ALBUM_ART_SIZE))
+                .height(Length::Fixed(ALBUM_ART_SIZE))
+                .into(),
+            None => container(icon(theme, StaticIcon::MusicNote).size(theme.font_size.lg))
+                .center(Length::Fixed(ALBUM_ART_SIZE))
+                .into(),
+        }
+    }
+
+    fn seek_slider<'a>(
+        &'a self,
+        d: &'a MprisPlayerData,
+        theme: &'a AshellTheme,
+    ) -> Option<Element<'a, Message>> {
+        // SetPosition needs the track id, hide the slider when seeking isn't possible
+        if !d.can_seek {
+            return None;
+        }
+        let metadata = d.metadata.as_ref()?;
+        metadata.track_id.as_ref()?;
+        let length = metadata.length.filter(|l| *l > 0)?;
+        let position = d.position?;
+
+        let length = length as f64 / 1_000_000.;
+        let position = match &self.seek {
+            Some((service, v)) if *service == d.service => *v,
+            _ => position as f64 / 1_000_000.,
+        }
+        .clamp(0., length);
+
+        Some(
+            row!(
+                text(format_position(position)).size(theme.font_size.xs),
+                slider(0.0..=length, position, move |v| {
+                    Message::Seek(d.service.clone(), v)
+                })
+                .on_release(Message::SeekReleased),
+                text(format_position(length)).size(theme.font_size.xs),
+            )
+            .align_y(Vertical::Center)
+            .spacing(theme.space.xs)
+            .into(),
+        )
+    }
+
     fn handle_command(&mut self, service_name: String, command: PlayerCommand) -> Task<Message> {
         match self.service.as_mut() {
             Some(s) => s
@@ -159,6 +474,22 @@ impl MediaPlayer {
         }
     }
 
+    /// The player shown in the bar: the one selected in the menu if it's still
+    /// running, then the first running player in `preferred_players` order,
+    /// then any player.
+    fn active_player<'a>(&self, s: &'a MprisPlayerService) -> Option<&'a MprisPlayerData> {
+        self.selected
+            .as_ref()
+            .and_then(|selected| s.iter().find(|d| d.service == *selected))
+            .or_else(|| {
+                self.config.preferred_players.iter().find_map(|preferred| {
+                    s.iter()
+                        .find(|d| d.service.to_lowercase().contains(&preferred.to_lowercase()))
+                })
+            })
+            .or_else(|| s.first())
+    }
+
     fn get_title(&self, d: &MprisPlayerData) -> String {
         match &d.metadata {
             Some(m) => truncate_text(&m.to_string(), self.config.max_title_length),
@@ -166,29 +497,221 @@ impl MediaPlayer {
         }
     }
 
+    /// Untruncated title of the player shown in the bar.
+    fn full_title(&self) -> Option<String> {
+        self.service
+            .as_ref()
+            .and_then(|s| self.active_player(s))
+            .and_then(|d| d.metadata.as_ref())
+            .map(|m| m.to_string())
+    }
+
+    /// Whether the bar title is too long and should scroll instead of being truncated.
+    fn should_scroll_title(&self) -> bool {
+        self.config.scroll_title
+            && self
+                .full_title()
+                .is_some_and(|title| title.chars().count() > self.config.max_title_length as usize)
+    }
+
+    fn bar_title(&self, d: &MprisPlayerData) -> String {
+        match &d.metadata {
+            Some(m) if self.should_scroll_title() => {
+                let title = m.to_string();
+                let offset = if title == self.marquee.0 {
+                    self.marquee.1
+                } else {
+                    0
+                };
+                marquee_window(&title, offset, self.config.max_title_length as usize)
+            }
+            _ => self.get_title(d),
+        }
+    }
+
     pub fn view(&'_ self, theme: &AshellTheme) -> Option<Element<'_, Message>> {
         self.service.as_ref().and_then(|s| {
-            s.first().map(|player| {
+            // Stopped players have nothing to show
+            let idle = s.iter().all(|d| d.state == PlaybackStatus::Stopped);
+            if self.config.hide_when_empty && idle {
+                return None;
+            }
+
+            self.active_player(s).map(|player| {
                 let title =
                     (self.config.indicator_format == MediaPlayerFormat::IconAndTitle).then(|| {
                         container(
-                            text(self.get_title(player))
+                            text(self.bar_title(player))
                                 .wrapping(text::Wrapping::None)
                                 .size(theme.font_size.sm),
                         )
                         .clip(true)
                     });
 
-                row![icon(StaticIcon::MusicNote)]
+                // Pressing the glyph doesn't reach the module button, so it won't open the menu
+                let control = self.config.playback_control.then(|| {
+                    button(icon(theme, play_pause_icon(player.state)))
+                        .padding([0, theme.space.xxs])
+                        .style(theme.ghost_button_style())
+                        .on_press(Message::PlayPause(player.service.clone()))
+                });
+
+                let content = row![icon(theme, StaticIcon::MusicNote)]
                     .push_maybe(title)
+                    .push_maybe(control)
                     .align_y(Vertical::Center)
-                    .spacing(theme.space.xs)
+                    .spacing(theme.space.xs);
+
+                if !self.config.scroll_to_skip {
+                    return content.into();
+                }
+
+                mouse_area(content)
+                    .on_scroll(move |delta| scroll_message(delta, self.scroll_accumulator))
                     .into()
             })
         })
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        MprisPlayerService::subscribe().map(Message::Event)
+        let is_seekable_playing = self.service.as_ref().is_some_and(|s| {
+            s.iter()
+                .any(|d| d.can_seek && d.state == PlaybackStatus::Playing)
+        });
+        // The title stays still while the track is paused
+        let is_marquee_running = self.should_scroll_title()
+            && self
+                .service
+                .as_ref()
+                .and_then(|s| self.active_player(s))
+                .is_some_and(|d| d.state == PlaybackStatus::Playing);
+
+        Subscription::batch([
+            MprisPlayerService::subscribe().map(Message::Event),
+            if is_seekable_playing {
+                every(Duration::from_secs(1)).map(|_| Message::RefreshPosition)
+            } else {
+                Subscription::none()
+            },
+            if is_marquee_running {
+                every(marquee_interval(self.config.scroll_speed)).map(|_| Message::MarqueeTick)
+            } else {
+                Subscription::none()
+            },
+        ])
+    }
+}
+
+async fn fetch_album_art(url: String) -> anyhow::Result<image::Handle> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(20))
+        .build()?;
+
+    let bytes = client
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    Ok(image::Handle::from_bytes(bytes.to_vec()))
+}
+
+/// Glyph of the action the play/pause button performs in `state`.
+// Lines step right away while pixels add up to a step first; both scroll up for a positive `y`
+fn scroll_message(delta: ScrollDelta, accumulator: f32) -> Message {
+    let sensibility = 3.;
+
+    match delta {
+        ScrollDelta::Lines { y, .. } => Message::Scroll(if y < 0. { -1 } else { 1 }),
+        ScrollDelta::Pixels { y, .. } if accumulator.abs() < sensibility => {
+            Message::ScrollAccumulator(y)
+        }
+        ScrollDelta::Pixels { .. } => Message::Scroll(if accumulator < 0. { -1 } else { 1 }),
+    }
+}
+
+fn play_pause_icon(state: PlaybackStatus) -> StaticIcon {
+    match state {
+        PlaybackStatus::Playing => StaticIcon::Pause,
+        PlaybackStatus::Paused | PlaybackStatus::Stopped => StaticIcon::Play,
+    }
+}
+
+fn format_position(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+/// Delay between two marquee steps for a speed in characters per second.
+fn marquee_interval(scroll_speed: u32) -> Duration {
+    Duration::from_millis(1000 / u64::from(scroll_speed.max(1))).max(MARQUEE_MIN_INTERVAL)
+}
+
+/// `width` characters of `title` starting at `offset`, wrapping around through
+/// a gap so the end of the title is followed by its beginning.
+fn marquee_window(title: &str, offset: usize, width: usize) -> String {
+    let chars = title.chars().chain(MARQUEE_GAP.chars()).collect::<Vec<_>>();
+
+    (0..width)
+        .map(|i| chars[offset.wrapping_add(i) % chars.len()])
+        .collect()
+}
+
+/// Short name of an MPRIS bus name, e.g. `spotify` for `org.mpris.MediaPlayer2.spotify`.
+fn player_name(service: &str) -> &str {
+    service
+        .strip_prefix("org.mpris.MediaPlayer2.")
+        .unwrap_or(service)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scroll_step(delta: ScrollDelta, accumulator: f32) -> Option<i32> {
+        match scroll_message(delta, accumulator) {
+            Message::Scroll(direction) => Some(direction),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn lines_and_pixels_scroll_the_same_way() {
+        let lines = |y| ScrollDelta::Lines { x: 0., y };
+        let pixels = |y| ScrollDelta::Pixels { x: 0., y };
+
+        assert_eq!(scroll_step(lines(1.), 0.), Some(1));
+        assert_eq!(scroll_step(lines(-1.), 0.), Some(-1));
+        assert_eq!(scroll_step(pixels(2.), 0.), None);
+        assert_eq!(scroll_step(pixels(2.), 4.), Some(1));
+        assert_eq!(scroll_step(pixels(-2.), -4.), Some(-1));
+    }
+
+    #[test]
+    fn marquee_window_wraps_through_the_gap() {
+        assert_eq!(marquee_window("abcdef", 0, 4), "abcd");
+        assert_eq!(marquee_window("abcdef", 4, 4), "ef  ");
+        assert_eq!(marquee_window("abcdef", 8, 4), " abc");
+        assert_eq!(marquee_window("abcdef", 9, 4), "abcd");
+    }
+
+    #[test]
+    fn marquee_window_counts_characters() {
+        assert_eq!(marquee_window("héllo", 1, 3), "éll");
+    }
+
+    #[test]
+    fn marquee_interval_is_clamped() {
+        assert_eq!(marquee_interval(5), Duration::from_millis(200));
+        assert_eq!(marquee_interval(0), Duration::from_secs(1));
+        assert_eq!(marquee_interval(1000), MARQUEE_MIN_INTERVAL);
     }
 }
@@ -7,7 +7,7 @@ use crate::{
 };
 use iced::{
     Alignment, Border, Color, Element, Length, Subscription,
-    widget::{Row, container, row},
+    widget::{Column, Row, container},
     window::Id,
 };
 
@@ -15,6 +15,7 @@ pub mod clock;
 pub mod custom_module;
 pub mod keyboard_layout;
 pub mod keyboard_submap;
+pub mod lock_keys;
 pub mod media_player;
 pub mod notifications;
 pub mod privacy;
@@ -39,28 +40,55 @@ impl App {
         theme: &'a AshellTheme,
     ) -> [Element<'a, Message>; 3] {
         [
-            &self.general_config.modules.left,
-            &self.general_config.modules.center,
-            &self.general_config.modules.right,
+            (&self.general_config.modules.left, self.theme.sections.left),
+            (
+                &self.general_config.modules.center,
+                self.theme.sections.center,
+            ),
+            (
+                &self.general_config.modules.right,
+                self.theme.sections.right,
+            ),
         ]
-        .map(|modules_def| {
-            let mut row = row!()
-                .height(Length::Shrink)
-                .align_y(Alignment::Center)
-                .spacing(self.theme.space.xxs);
-
-            for module_def in modules_def {
-                row = row.push_maybe(match module_def {
+        .map(|(modules_def, section)| {
+            let spacing = section.spacing.unwrap_or(self.theme.space.xxs);
+            let padding = section.padding.unwrap_or_default();
+            let modules = modules_def
+                .iter()
+                .filter_map(|module_def| match module_def {
                     // life parsing of string to module
                     ModuleDef::Single(module) => self.single_module_wrapper(id, theme, module),
                     ModuleDef::Group(group) => self.group_module_wrapper(id, theme, group),
                 });
-            }
 
-            row.into()
+            if self.theme.bar_position.is_vertical() {
+                Column::with_children(modules)
+                    .width(Length::Fill)
+                    .align_x(Alignment::Center)
+                    .spacing(spacing)
+                    .padding([padding, 0])
+                    .into()
+            } else {
+                Row::with_children(modules)
+                    .height(Length::Shrink)
+                    .align_y(Alignment::Center)
+                    .spacing(spacing)
+                    .padding([0, padding])
+                    .into()
+            }
         })
     }
 
+    // Modules fill the bar thickness, which is the height of horizontal bars
+    // and the width of vertical ones
+    fn module_layout(&self) -> (Length, Length, [u16; 2]) {
+        if self.theme.bar_position.is_vertical() {
+            (Length::Fill, Length::Shrink, [self.theme.space.xs, 2])
+        } else {
+            (Length::Shrink, Length::Fill, [2, self.theme.space.xs])
+        }
+    }
+
     pub fn modules_subscriptions(&self, modules_def: &[ModuleDef]) -> Vec<Subscription<Message>> {
         modules_def
             .iter()
@@ -77,6 +105,18 @@ impl App {
             .collect()
     }
 
+    // Modules that only refresh on a timer can wait while another menu is open,
+    // event driven ones would lose their state if their subscription restarted
+    fn is_module_paused(&self, module_name: &ModuleName) -> bool {
+        self.general_config.pause_when_menu_open
+            && self.outputs.menu_is_open()
+            && match module_name {
+                ModuleName::SystemInfo => !self.outputs.menu_type_is_open(&MenuType::SystemInfo),
+                ModuleName::Clock => true,
+                _ => false,
+            }
+    }
+
     fn single_module_wrapper<'a>(
         &'a self,
         id: Id,
@@ -84,17 +124,21 @@ impl App {
         module_name: &'a ModuleName,
     ) -> Option<Element<'a, Message>> {
         let module = self.get_module_view(id, module_name);
+        let (width, height, padding) = self.module_layout();
 
         module.map(|(content, action)| match action {
             Some(action) => {
                 let button = position_button(
                     container(content)
+                        .align_x(Alignment::Center)
                         .align_y(Alignment::Center)
-                        .height(Length::Fill)
+                        .width(width)
+                        .height(height)
                         .clip(true),
                 )
-                .padding([2, self.theme.space.xs])
-                .height(Length::Fill)
+                .padding(padding)
+                .width(width)
+                .height(height)
                 .style(theme.module_button_style(false));
 
                 match action {
@@ -109,8 +153,10 @@ impl App {
             }
             _ => {
                 let container = container(content)
-                    .padding([2, self.theme.space.xs])
-                    .height(Length::Fill)
+                    .padding(padding)
+                    .width(width)
+                    .height(height)
+                    .align_x(Alignment::Center)
                     .align_y(Alignment::Center)
                     .clip(true);
 
@@ -148,48 +194,54 @@ impl App {
             .iter()
             .filter_map(|module| self.get_module_view(id, module))
             .collect::<Vec<_>>();
+        let (width, height, padding) = self.module_layout();
 
         if modules.is_empty() {
             None
         } else {
             Some({
-                let group = Row::with_children(
-                    modules
-                        .into_iter()
-                        .map(|(content, action)| match action {
-                            Some(action) => {
-                                let button = position_button(
-                                    container(content)
-                                        .align_y(Alignment::Center)
-                                        .height(Length::Fill)
-                                        .clip(true),
-                                )
-                                .padding([2, self.theme.space.xs])
-                                .height(Length::Fill)
-                                .style(theme.module_button_style(true));
+                let modules = modules
+                    .into_iter()
+                    .map(|(content, action)| match action {
+                        Some(action) => {
+                            let button = position_button(
+                                container(content)
+                                    .align_x(Alignment::Center)
+                                    .align_y(Alignment::Center)
+                                    .width(width)
+                                    .height(height)
+                                    .clip(true),
+                            )
+                            .padding(padding)
+                            .width(width)
+                            .height(height)
+                            .style(theme.module_button_style(true));
 
-                                match action {
-                                    OnModulePress::Action(action) => button.on_press(*action),
-                                    OnModulePress::ToggleMenu(menu_type) => button
-                                        .on_press_with_position(move |button_ui_ref| {
-                                            Message::ToggleMenu(
-                                                menu_type.clone(),
-                                                id,
-                                                button_ui_ref,
-                                            )
-                                        }),
-                                }
-                                .into()
+                            match action {
+                                OnModulePress::Action(action) => button.on_press(*action),
+                                OnModulePress::ToggleMenu(menu_type) => button
+                                    .on_press_with_position(move |button_ui_ref| {
+                                        Message::ToggleMenu(menu_type.clone(), id, button_ui_ref)
+                                    }),
                             }
-                            _ => container(content)
-                                .padding([2, self.theme.space.xs])
-                                .height(Length::Fill)
-                                .align_y(Alignment::Center)
-                                .clip(true)
-                                .into(),
-                        })
-                        .collect::<Vec<_>>(),
-                );
+                            .into()
+                        }
+                        _ => container(content)
+                            .padding(padding)
+                            .width(width)
+                            .height(height)
+                            .align_x(Alignment::Center)
+                            .align_y(Alignment::Center)
+                            .clip(true)
+                            .into(),
+                    })
+                    .collect::<Vec<Element<'a, Message>>>();
+
+                let group: Element<'a, Message> = if self.theme.bar_position.is_vertical() {
+                    Column::with_children(modules).width(Length::Fill).into()
+                } else {
+                    Row::with_children(modules).into()
+                };
 
                 match self.theme.bar_style {
                     AppearanceStyle::Solid | AppearanceStyle::Gradient => group.into(),
@@ -220,16 +272,19 @@ impl App {
         id: Id,
         module_name: &'a ModuleName,
     ) -> Option<(Element<'a, Message>, Option<OnModulePress>)> {
-        match module_name {
+        let view = match module_name {
             ModuleName::Custom(name) => self.custom.get(name).map(|custom| {
+                let launch_command = || {
+                    OnModulePress::Action(Box::new(Message::Custom(
+                        name.clone(),
+                        custom_module::Message::LaunchCommand,
+                    )))
+                };
                 let action = match custom.module_type() {
-                    crate::config::CustomModuleType::Text => None,
-                    crate::config::CustomModuleType::Button => {
-                        Some(OnModulePress::Action(Box::new(Message::Custom(
-                            name.clone(),
-                            custom_module::Message::LaunchCommand,
-                        ))))
+                    crate::config::CustomModuleType::Text => {
+                        custom.has_command().then(launch_command)
                     }
+                    crate::config::CustomModuleType::Button => Some(launch_command()),
                 };
                 (
                     custom
@@ -255,7 +310,11 @@ impl App {
                     self.window_title
                         .view(&self.theme, title)
                         .map(Message::WindowTitle),
-                    None,
+                    self.window_title.has_click_cmd().then(|| {
+                        OnModulePress::Action(Box::new(Message::WindowTitle(
+                            window_title::Message::Click,
+                        )))
+                    }),
                 )
             }),
             ModuleName::SystemInfo => Some((
@@ -265,15 +324,21 @@ impl App {
             ModuleName::KeyboardLayout => self.keyboard_layout.view(&self.theme).map(|view| {
                 (
                     view.map(Message::KeyboardLayout),
-                    Some(OnModulePress::Action(Box::new(Message::KeyboardLayout(
-                        keyboard_layout::Message::ChangeLayout,
-                    )))),
+                    self.keyboard_layout.cycle_on_click().then(|| {
+                        OnModulePress::Action(Box::new(Message::KeyboardLayout(
+                            keyboard_layout::Message::ChangeLayout,
+                        )))
+                    }),
                 )
             }),
             ModuleName::KeyboardSubmap => self
                 .keyboard_submap
                 .view(&self.theme)
                 .map(|view| (view.map(Message::KeyboardSubmap), None)),
+            ModuleName::LockKeys => self
+                .lock_keys
+                .view(&self.theme)
+                .map(|view| (view.map(Message::LockKeys), None)),
             ModuleName::Tray => self
                 .tray
                 .view(id, &self.theme)
@@ -303,10 +368,34 @@ impl App {
                 self.settings.view(&self.theme).map(Message::Settings),
                 Some(OnModulePress::ToggleMenu(MenuType::Settings)),
             )),
+        };
+
+        // The override becomes the default text color, so it also applies to icons
+        match self.theme.module_colors.get(module_name) {
+            Some(color) => {
+                let color = Color::from_rgb8(color.r, color.g, color.b);
+
+                view.map(|(content, action)| {
+                    (
+                        container(content)
+                            .style(move |_| container::Style {
+                                text_color: Some(color),
+                                ..container::Style::default()
+                            })
+                            .into(),
+                        action,
+                    )
+                })
+            }
+            None => view,
         }
     }
 
     fn get_module_subscription(&self, module_name: &ModuleName) -> Option<Subscription<Message>> {
+        if self.is_module_paused(module_name) {
+            return None;
+        }
+
         match module_name {
             ModuleName::Custom(name) => self.custom.get(name).map(|custom| {
                 custom
@@ -334,6 +423,7 @@ impl App {
                     .subscription()
                     .map(Message::KeyboardSubmap),
             ),
+            ModuleName::LockKeys => Some(self.lock_keys.subscription().map(Message::LockKeys)),
             ModuleName::Tray => Some(self.tray.subscription().map(Message::Tray)),
             ModuleName::Clock => Some(self.clock.subscription().map(Message::Clock)),
             ModuleName::Tempo => Some(self.tempo.subscription().map(Message::Tempo)),
@@ -1,24 +1,74 @@
 use crate::{
     components::icons::{StaticIcon, icon, icon_button},
-    config::NotificationsModuleConfig,
+    config::{NotificationSortOrder, NotificationsModuleConfig},
     menu::MenuSize,
     services::{
         ReadOnlyService, ServiceEvent,
         notifications::{
             CloseReason, Notification, NotificationEvent, NotificationIcon, NotificationService,
+            NotificationSound, Urgency,
         },
     },
     theme::AshellTheme,
-    utils::truncate_chars,
+    utils::{IndicatorState, truncate_chars},
 };
+use chrono::{DateTime, Local};
 use iced::{
-    Alignment, Element, Length, Subscription, Task,
+    Alignment, Border, Element, Length, Subscription, Task,
+    time::every,
     widget::{
-        Image, Row, Svg, button, column, container, horizontal_rule, mouse_area, row, scrollable,
-        text, Column,
+        Column, Image, Row, Svg, button, column, container, horizontal_rule, horizontal_space,
+        mouse_area, row, scrollable, text, text_input, tooltip,
     },
     window::Id,
 };
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{collections::HashSet, time::Duration};
+
+// Bodies longer than this are truncated until the entry is expanded
+const BODY_PREVIEW_CHARS: usize = 200;
+
+// One-time codes are usually a standalone group of 6 digits
+static OTP_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{6}\b").unwrap());
+static OTP_KEYWORD_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(code|otp|passcode|pin|2fa|verify|verification)\b").unwrap());
+// How many characters may separate a code from its keyword
+const OTP_KEYWORD_DISTANCE: usize = 30;
+
+/// First code next to a word like "code" or "verification", so other numbers
+/// (order ids, amounts, dates) aren't offered for copying.
+fn find_otp(body: &str) -> Option<&str> {
+    let keywords: Vec<_> = OTP_KEYWORD_RE.find_iter(body).collect();
+    OTP_RE
+        .find_iter(body)
+        .find(|code| {
+            keywords.iter().any(|keyword| {
+                let distance = if keyword.end() <= code.start() {
+                    code.start() - keyword.end()
+                } else {
+                    keyword.start().saturating_sub(code.end())
+                };
+                distance <= OTP_KEYWORD_DISTANCE
+            })
+        })
+        .map(|m| m.as_str())
+}
+
+static URL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"https?://[^\s<>"'`]+"#).unwrap());
+
+/// First link in the body, without the punctuation that usually follows it in a sentence.
+fn find_url(body: &str) -> Option<&str> {
+    let mut url = URL_RE.find(body)?.as_str();
+    loop {
+        let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        // A closing parenthesis is part of the link only when it has an opening one
+        url = match trimmed.strip_suffix(')') {
+            Some(rest) if trimmed.matches('(').count() < trimmed.matches(')').count() => rest,
+            _ => return Some(trimmed),
+        };
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -30,35 +80,164 @@ pub enum Message {
     ClearAll,
     ClearAllSignalsSent,
     MenuOpened,
+    ToggleExpand(u32),
+    CopyBody(u32),
+    CopyCode(u32),
+    OpenLink(u32),
+    QuietHoursTick,
+    TimestampTick,
+    RestoreLast,
+    ReplayPopups,
+    OpenApp(u32),
+    SearchChanged(String),
+    TogglePin(u32),
 }
 
 pub enum Action {
     None,
     EmitSignal(Task<Message>),
     ShowPopup(Notification),
+    ReplayPopups(Vec<Notification>),
+    CopyToClipboard(String),
+}
+
+/// Key of the action button to highlight: the one labeled like the default action
+/// (which has no button of its own), otherwise the first one.
+fn primary_action(actions: &[(String, String)]) -> Option<&str> {
+    let default_label = actions
+        .iter()
+        .find(|(key, _)| key == "default")
+        .map(|(_, label)| label)
+        .filter(|label| !label.is_empty());
+    let mut visible = actions.iter().filter(|(key, _)| key != "default");
+
+    default_label
+        .and_then(|default_label| visible.clone().find(|(_, label)| label == default_label))
+        .or_else(|| visible.next())
+        .map(|(key, _)| key.as_str())
+}
+
+/// Case-insensitive substring match against the app name, summary and body.
+/// `query` is expected to be lowercase already.
+fn matches_query(notification: &Notification, query: &str) -> bool {
+    [
+        &notification.app_name,
+        &notification.summary,
+        &notification.body,
+    ]
+    .iter()
+    .any(|field| field.to_lowercase().contains(query))
+}
+
+/// Format a notification timestamp for the menu list, including the date
+/// when the notification was not received today.
+/// With `relative`, notifications from the last day are shown as "now", "5m ago" or "3h ago".
+/// Timestamps in the future, e.g. after the clock was adjusted, count as "now".
+fn format_timestamp(timestamp: &DateTime<Local>, now: DateTime<Local>, relative: bool) -> String {
+    let elapsed = now
+        .signed_duration_since(timestamp)
+        .max(chrono::TimeDelta::zero());
+    if relative && elapsed < chrono::TimeDelta::days(1) {
+        return match (elapsed.num_hours(), elapsed.num_minutes()) {
+            (0, 0) => "now".to_string(),
+            (0, minutes) => format!("{minutes}m ago"),
+            (hours, _) => format!("{hours}h ago"),
+        };
+    }
+
+    if timestamp.date_naive() == now.date_naive() {
+        timestamp.format("%H:%M").to_string()
+    } else {
+        timestamp.format("%d %b %H:%M").to_string()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Notifications {
     pub(crate) config: NotificationsModuleConfig,
     service: Option<NotificationService>,
-    unread_count: usize,
+    /// Ids of the notifications received since the menu was last opened
+    unread: HashSet<u32>,
+    unread_urgency: Option<Urgency>,
+    expanded: HashSet<u32>,
+    quiet: bool,
+    search: String,
+    /// Notifications that were unread when the menu was last opened, newest first
+    missed: Vec<u32>,
 }
 
-
 impl Notifications {
     pub fn new(config: NotificationsModuleConfig) -> Self {
+        let quiet = Self::in_quiet_hours(&config);
+
         Self {
             config,
             service: None,
-            unread_count: 0,
+            unread: HashSet::new(),
+            unread_urgency: None,
+            expanded: HashSet::new(),
+            quiet,
+            search: String::new(),
+            missed: Vec::new(),
         }
     }
 
+    fn in_quiet_hours(config: &NotificationsModuleConfig) -> bool {
+        config
+            .quiet_hours
+            .is_some_and(|quiet_hours| quiet_hours.contains(Local::now().time()))
+    }
+
+    /// Critical and allowlisted notifications still pop up during quiet hours.
+    fn can_popup(
+        config: &NotificationsModuleConfig,
+        quiet: bool,
+        notification: &Notification,
+    ) -> bool {
+        !quiet
+            || notification.urgency == Urgency::Critical
+            || config.is_always_popup(&notification.app_name)
+    }
+
+    /// Whether a popup raised outside of the notification service may show right now,
+    /// following the same quiet hours rules as received notifications.
+    pub fn can_popup_now(&self, notification: &Notification) -> bool {
+        Self::can_popup(
+            &self.config,
+            Self::in_quiet_hours(&self.config),
+            notification,
+        )
+    }
+
+    /// The per-app override wins over the sender's hint, which wins over `default_sound`.
+    fn resolve_sound(
+        config: &NotificationsModuleConfig,
+        notification: &Notification,
+    ) -> Option<NotificationSound> {
+        if !config.sound {
+            return None;
+        }
+        if let Some((_, sound)) = config
+            .app_sounds
+            .iter()
+            .find(|(app, _)| app.eq_ignore_ascii_case(&notification.app_name))
+        {
+            return NotificationSound::from_config(sound);
+        }
+        if notification.suppress_sound {
+            return None;
+        }
+        notification
+            .sound
+            .clone()
+            .or_else(|| NotificationSound::from_config(&config.default_sound))
+    }
+
     pub fn update(&mut self, message: Message) -> Action {
         match message {
             Message::Event(event) => match event {
-                ServiceEvent::Init(service) => {
+                ServiceEvent::Init(mut service) => {
+                    service.update_config(&self.config);
                     self.service = Some(service);
                     Action::None
                 }
@@ -66,20 +245,31 @@ impl Notifications {
                     if let Some(service) = self.service.as_mut() {
                         let popup_notification = match &notification_event {
                             NotificationEvent::Notify(n) => {
+                                self.quiet = Self::in_quiet_hours(&self.config);
+
                                 // Only increment unread for genuinely new notifications,
                                 // not replacements of existing ones
                                 let is_replacement = service
                                     .notifications
                                     .iter()
-                                    .any(|existing| existing.id == n.id);
+                                    .any(|existing| existing.is_replaced_by(n));
+                                let can_popup = Self::can_popup(&self.config, self.quiet, n);
                                 if !is_replacement {
-                                    self.unread_count += 1;
+                                    self.unread.insert(n.id);
+                                    self.unread_urgency = self.unread_urgency.max(Some(n.urgency));
+                                    if can_popup
+                                        && let Some(sound) = Self::resolve_sound(&self.config, n)
+                                    {
+                                        sound.play();
+                                    }
                                 }
-                                Some(n.clone())
+                                can_popup.then(|| n.clone())
                             }
                             NotificationEvent::Closed(_, _) => None,
                         };
                         service.update(notification_event);
+                        self.expanded
+                            .retain(|id| service.notifications.iter().any(|n| n.id == *id));
                         if let Some(n) = popup_notification {
                             return Action::ShowPopup(n);
                         }
@@ -88,9 +278,12 @@ impl Notifications {
                 }
                 ServiceEvent::Error(_) => Action::None,
             },
+            // Pinned notifications have to be unpinned first
+            Message::Dismiss(id) if self.is_pinned(id) => Action::None,
             Message::Dismiss(id) => {
+                self.expanded.remove(&id);
                 if let Some(service) = self.service.as_mut() {
-                    service.notifications.retain(|n| n.id != id);
+                    service.dismiss(id);
 
                     // Emit NotificationClosed D-Bus signal (reason: dismissed by user)
                     let service_clone = service.clone();
@@ -105,7 +298,18 @@ impl Notifications {
                 }
                 Action::None
             }
+            // Pinned notifications stay in the list after their action ran
+            Message::InvokeAction(id, action_key) if self.is_pinned(id) => {
+                match self.service.clone() {
+                    Some(service) => Action::EmitSignal(Task::perform(
+                        async move { service.emit_action_invoked_signal(id, &action_key).await },
+                        |_| Message::ActionSignalSent,
+                    )),
+                    None => Action::None,
+                }
+            }
             Message::InvokeAction(id, action_key) => {
+                self.expanded.remove(&id);
                 if let Some(service) = self.service.as_mut() {
                     service.notifications.retain(|n| n.id != id);
 
@@ -129,9 +333,16 @@ impl Notifications {
             | Message::ClearAllSignalsSent => Action::None,
             Message::ClearAll => {
                 if let Some(service) = self.service.as_mut() {
-                    let ids: Vec<u32> = service.notifications.iter().map(|n| n.id).collect();
-                    service.notifications.clear();
-                    self.unread_count = 0;
+                    self.expanded.retain(|id| service.is_pinned(*id));
+                    let ids: Vec<u32> = service
+                        .notifications
+                        .iter()
+                        .map(|n| n.id)
+                        .filter(|id| !service.is_pinned(*id))
+                        .collect();
+                    service.dismiss_all();
+                    self.unread.clear();
+                    self.unread_urgency = None;
 
                     // Emit NotificationClosed D-Bus signal for each dismissed notification
                     let service_clone = service.clone();
@@ -146,32 +357,190 @@ impl Notifications {
                         |_| Message::ClearAllSignalsSent,
                     ));
                 }
-                self.unread_count = 0;
+                self.unread.clear();
+                self.unread_urgency = None;
                 Action::None
             }
             Message::MenuOpened => {
-                self.unread_count = 0;
+                // Transient notifications are counted as unread but never stored
+                self.missed = self.service.as_ref().map_or_else(Vec::new, |service| {
+                    service
+                        .notifications
+                        .iter()
+                        .map(|n| n.id)
+                        .filter(|id| self.unread.contains(id))
+                        .collect()
+                });
+                self.unread.clear();
+                self.unread_urgency = None;
+                self.search.clear();
+                Action::None
+            }
+            Message::TogglePin(id) => {
+                if let Some(service) = self.service.as_mut() {
+                    service.toggle_pin(id);
+                }
+                Action::None
+            }
+            Message::ToggleExpand(id) => {
+                if !self.expanded.remove(&id) {
+                    self.expanded.insert(id);
+                }
+                Action::None
+            }
+            Message::CopyBody(id) => self.notification_body(id).map_or(Action::None, |body| {
+                Action::CopyToClipboard(body.to_owned())
+            }),
+            Message::CopyCode(id) => self
+                .notification_body(id)
+                .and_then(find_otp)
+                .map_or(Action::None, |code| {
+                    Action::CopyToClipboard(code.to_owned())
+                }),
+            Message::OpenLink(id) => {
+                let url = self
+                    .notification_body(id)
+                    .and_then(find_url)
+                    .map(str::to_owned);
+                match url {
+                    Some(url) => {
+                        crate::utils::launcher::open_url(url);
+                        self.update(Message::Dismiss(id))
+                    }
+                    None => Action::None,
+                }
+            }
+            Message::QuietHoursTick => {
+                self.quiet = Self::in_quiet_hours(&self.config);
+                Action::None
+            }
+            // Nothing to update, the redraw refreshes relative timestamps
+            Message::TimestampTick => Action::None,
+            Message::RestoreLast => {
+                if let Some(service) = self.service.as_mut() {
+                    service.restore_last();
+                }
+                Action::None
+            }
+            Message::ReplayPopups => {
+                self.quiet = Self::in_quiet_hours(&self.config);
+                let missed = std::mem::take(&mut self.missed);
+                let Some(service) = self.service.as_ref() else {
+                    return Action::None;
+                };
+                // Oldest first so they pop up in the order they arrived
+                Action::ReplayPopups(
+                    missed
+                        .iter()
+                        .rev()
+                        .filter_map(|id| service.notifications.iter().find(|n| n.id == *id))
+                        .filter(|n| Self::can_popup(&self.config, self.quiet, n))
+                        .cloned()
+                        .collect(),
+                )
+            }
+            Message::OpenApp(id) => {
+                let exec = self.service.as_ref().and_then(|service| {
+                    service
+                        .notifications
+                        .iter()
+                        .find(|n| n.id == id)
+                        .and_then(|n| n.desktop_exec.clone())
+                });
+                match exec {
+                    Some(exec) => {
+                        crate::utils::launcher::execute_command(exec);
+                        self.update(Message::Dismiss(id))
+                    }
+                    None => Action::None,
+                }
+            }
+            Message::SearchChanged(search) => {
+                self.search = search;
                 Action::None
             }
         }
     }
 
+    pub fn update_config(&mut self, config: NotificationsModuleConfig) {
+        if let Some(service) = self.service.as_mut() {
+            service.update_config(&config);
+        }
+        self.quiet = Self::in_quiet_hours(&config);
+        self.config = config;
+    }
+
+    /// Pinned notifications are listed first and survive "Clear all"
+    fn is_pinned(&self, id: u32) -> bool {
+        self.service
+            .as_ref()
+            .is_some_and(|service| service.is_pinned(id))
+    }
+
+    fn notification_body(&self, id: u32) -> Option<&str> {
+        self.service
+            .as_ref()
+            .and_then(|service| service.notifications.iter().find(|n| n.id == id))
+            .map(|n| n.body.as_str())
+    }
+
     pub fn view(&self, theme: &AshellTheme) -> Element<'_, Message> {
         let has_notifications = self
             .service
             .as_ref()
             .is_some_and(|s| !s.notifications.is_empty());
 
-        let mut content = row!(container(icon(if has_notifications {
-            StaticIcon::BellAlert
-        } else {
-            StaticIcon::Bell
-        })))
+        let mut content = row!(container(icon(
+            theme,
+            if self.quiet {
+                StaticIcon::BellOff
+            } else if has_notifications {
+                StaticIcon::BellAlert
+            } else {
+                StaticIcon::Bell
+            }
+        )))
         .align_y(Alignment::Center)
         .spacing(theme.space.xxs);
 
-        if self.unread_count > 0 {
-            content = content.push(text(self.unread_count));
+        if self.config.badge_show_critical {
+            let critical_count = self.service.as_ref().map_or(0, |s| {
+                s.notifications
+                    .iter()
+                    .filter(|n| n.urgency == Urgency::Critical)
+                    .count()
+            });
+
+            if critical_count > 0 {
+                content = content.push(
+                    container(text(critical_count).size(theme.font_size.xs))
+                        .padding([0, theme.space.xxs])
+                        .style(move |t: &iced::Theme| container::Style {
+                            background: Some(t.palette().danger.into()),
+                            text_color: Some(t.palette().background),
+                            border: Border::default().rounded(theme.radius.xl),
+                            ..Default::default()
+                        }),
+                );
+            }
+        }
+
+        if !self.unread.is_empty() {
+            let state = match self.unread_urgency {
+                Some(Urgency::Critical) => IndicatorState::Danger,
+                Some(Urgency::Normal) => IndicatorState::Warning,
+                _ => IndicatorState::Normal,
+            };
+
+            let badge = text(self.unread.len()).style(move |t: &iced::Theme| text::Style {
+                color: match state {
+                    IndicatorState::Danger => Some(t.palette().danger),
+                    IndicatorState::Warning => Some(t.extended_palette().danger.weak.color),
+                    _ => None,
+                },
+            });
+
+            content = content.push(badge);
         }
 
         content.into()
@@ -183,161 +552,494 @@ impl Notifications {
             .as_ref()
             .map(|s| s.notifications.as_slice())
             .unwrap_or(&[]);
+        let can_restore = self
+            .service
+            .as_ref()
+            .is_some_and(|s| !s.recently_closed.is_empty());
+        let can_replay = !self.missed.is_empty();
+        let icon_size = f32::from(self.config.icon_size);
+        let query = self.search.trim().to_lowercase();
+        let mut filtered = notifications
+            .iter()
+            .filter(|n| query.is_empty() || matches_query(n, &query))
+            .collect::<Vec<_>>();
+        // The service stores the newest notification first
+        match self.config.sort_order {
+            NotificationSortOrder::NewestFirst => {}
+            NotificationSortOrder::OldestFirst => filtered.reverse(),
+            NotificationSortOrder::UrgencyThenTime => filtered.sort_by(|a, b| {
+                b.urgency
+                    .cmp(&a.urgency)
+                    .then_with(|| b.timestamp.cmp(&a.timestamp))
+            }),
+        }
+        filtered.sort_by_key(|n| !self.is_pinned(n.id));
 
-        column!(
-            if notifications.is_empty() {
-                std::convert::Into::<Element<'_, _, _>>::into(
-                    container(text("No notifications")).padding(theme.space.xs),
+        column!(if notifications.is_empty() {
+            std::convert::Into::<Element<'_, _, _>>::into(
+                container(text("No notifications")).padding(theme.space.xs),
+            )
+        } else {
+            column!(
+                row!(
+                    text(format!("{} Notifications", notifications.len())).width(Length::Fill),
+                    button("Clear all")
+                        .style(theme.ghost_button_style())
+                        .padding([2, theme.space.xs])
+                        .on_press(Message::ClearAll)
                 )
-            } else {
-                column!(
-                    row!(
-                        text(format!("{} Notifications", notifications.len()))
-                            .width(Length::Fill),
-                        button("Clear all")
-                            .style(theme.ghost_button_style())
-                            .padding([2, theme.space.xs])
-                            .on_press(Message::ClearAll)
-                    )
-                    .align_y(Alignment::Center)
-                    .padding(theme.space.xs),
-                    horizontal_rule(1),
-                    container(scrollable(
-                        Column::with_children(
-                            notifications
-                                .iter()
-                                .map(|n| {
-                                    let time = n.timestamp.format("%H:%M").to_string();
-                                    let summary = n.summary.clone();
-                                    let body = n.body.clone();
-                                    let app = n.app_name.clone();
-                                    let id = n.id;
-
-                                    // Partition actions: default vs visible
-                                    let has_default_action =
-                                        n.actions.iter().any(|(k, _)| k == "default");
-                                    let visible_actions: Vec<_> = n
-                                        .actions
+                .align_y(Alignment::Center)
+                .padding(theme.space.xs),
+                text_input("Search notifications", &self.search)
+                    .size(theme.font_size.sm)
+                    .padding([theme.space.xxs, theme.space.xs])
+                    .style(theme.text_input_style())
+                    .on_input(Message::SearchChanged),
+                horizontal_rule(1),
+            )
+            .push_maybe(
+                filtered
+                    .is_empty()
+                    .then(|| container(text("No matching notifications")).padding(theme.space.xs)),
+            )
+            .push(
+                container(scrollable(
+                    Column::with_children(
+                        filtered
+                            .into_iter()
+                            .map(|n| {
+                                let time = format_timestamp(
+                                    &n.timestamp,
+                                    Local::now(),
+                                    self.config.relative_timestamps,
+                                );
+                                let full_time = n.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
+                                let summary = n.summary.clone();
+                                let body = n.body.clone();
+                                let app = n.display_app_name(&self.config.fallback_app_name);
+                                let app = match (&n.sender_process, n.sender_pid) {
+                                    (Some(process), Some(pid)) if self.config.show_sender => {
+                                        format!("{app} · {process} ({pid})")
+                                    }
+                                    _ => app.to_string(),
+                                };
+                                let id = n.id;
+                                let pinned = self.is_pinned(id);
+
+                                // Partition actions: default vs visible
+                                let has_default_action =
+                                    n.actions.iter().any(|(k, _)| k == "default");
+                                let can_open_app =
+                                    self.config.open_app_on_click && n.desktop_exec.is_some();
+                                let visible_actions: Vec<_> =
+                                    n.actions.iter().filter(|(k, _)| k != "default").collect();
+                                let primary_action = primary_action(&n.actions);
+
+                                // Icon element
+                                let icon_element: Option<Element<'_, _, _>> =
+                                    n.icon.as_ref().map(|icon| match icon {
+                                        NotificationIcon::Image(handle) => {
+                                            Image::new(handle.clone())
+                                                .height(Length::Fixed(icon_size))
+                                                .into()
+                                        }
+                                        NotificationIcon::Svg(handle) => Svg::new(handle.clone())
+                                            .height(Length::Fixed(icon_size))
+                                            .width(Length::Fixed(icon_size))
+                                            .into(),
+                                    });
+
+                                // Text content column
+                                let mut text_col = column!(
+                                    row!(
+                                        text(app).size(theme.font_size.xs),
+                                        tooltip(
+                                            text(time).size(theme.font_size.xs).color(
+                                                theme
+                                                    .get_theme()
+                                                    .extended_palette()
+                                                    .secondary
+                                                    .base
+                                                    .text
+                                            ),
+                                            container(text(full_time).size(theme.font_size.xs))
+                                                .padding([theme.space.xxs, theme.space.xs])
+                                                .style(container::rounded_box),
+                                            tooltip::Position::Bottom,
+                                        ),
+                                    )
+                                    .spacing(theme.space.xs),
+                                    text(summary).size(theme.font_size.sm),
+                                )
+                                .spacing(2)
+                                .width(Length::Fill);
+
+                                let otp = find_otp(&body).map(str::to_owned);
+                                let has_link =
+                                    self.config.detect_links && find_url(&body).is_some();
+
+                                if !body.is_empty() {
+                                    let expandable = body.chars().count() > BODY_PREVIEW_CHARS;
+                                    let expanded = self.expanded.contains(&id);
+                                    // Hovering a truncated body previews the full text
+                                    let preview = (expandable && !expanded).then(|| body.clone());
+                                    let body = if expanded {
+                                        body
+                                    } else {
+                                        truncate_chars(&body, BODY_PREVIEW_CHARS).to_owned()
+                                    };
+                                    let body = text(body).size(theme.font_size.xs);
+                                    let body: Element<'_, _, _> = match preview {
+                                        Some(preview) => tooltip(
+                                            body,
+                                            container(text(preview).size(theme.font_size.xs))
+                                                .max_width(MenuSize::Medium)
+                                                .padding([theme.space.xxs, theme.space.xs])
+                                                .style(container::rounded_box),
+                                            tooltip::Position::Bottom,
+                                        )
+                                        .into(),
+                                        None => body.into(),
+                                    };
+
+                                    text_col = text_col.push(
+                                        row!(container(body).width(Length::Fill))
+                                            .push_maybe(expandable.then(|| {
+                                                icon_button::<Message>(
+                                                    theme,
+                                                    if expanded {
+                                                        StaticIcon::UpChevron
+                                                    } else {
+                                                        StaticIcon::DownChevron
+                                                    },
+                                                )
+                                                .on_press(Message::ToggleExpand(id))
+                                            }))
+                                            .spacing(theme.space.xxs)
+                                            .align_y(Alignment::Start),
+                                    );
+                                }
+
+                                // Action buttons row
+                                if !visible_actions.is_empty() || otp.is_some() || has_link {
+                                    let action_buttons: Vec<Element<'_, _, _>> = visible_actions
                                         .iter()
-                                        .filter(|(k, _)| k != "default")
+                                        .map(|(key, label)| {
+                                            button(text(label.clone()).size(theme.font_size.xs))
+                                                .style({
+                                                    let primary =
+                                                        primary_action == Some(key.as_str());
+                                                    move |t, status| {
+                                                        if primary {
+                                                            theme.primary_button_style()(t, status)
+                                                        } else {
+                                                            theme.ghost_button_style()(t, status)
+                                                        }
+                                                    }
+                                                })
+                                                .padding([2, theme.space.xs])
+                                                .on_press(Message::InvokeAction(id, key.clone()))
+                                                .into()
+                                        })
+                                        .chain(otp.map(|code| {
+                                            button(
+                                                text(format!("Copy code {code}"))
+                                                    .size(theme.font_size.xs),
+                                            )
+                                            .style(theme.ghost_button_style())
+                                            .padding([2, theme.space.xs])
+                                            .on_press(Message::CopyCode(id))
+                                            .into()
+                                        }))
+                                        .chain(has_link.then(|| {
+                                            button(text("Open link").size(theme.font_size.xs))
+                                                .style(theme.ghost_button_style())
+                                                .padding([2, theme.space.xs])
+                                                .on_press(Message::OpenLink(id))
+                                                .into()
+                                        }))
                                         .collect();
+                                    text_col = text_col.push(
+                                        Row::with_children(action_buttons).spacing(theme.space.xxs),
+                                    );
+                                }
 
-                                    // Icon element
-                                    let icon_element: Option<Element<'_, _, _>> =
-                                        n.icon.as_ref().map(|icon| match icon {
-                                            NotificationIcon::Image(handle) => {
-                                                Image::new(handle.clone())
-                                                    .height(Length::Fixed(24.))
-                                                    .into()
-                                            }
-                                            NotificationIcon::Svg(handle) => Svg::new(handle.clone())
-                                                .height(Length::Fixed(24.))
-                                                .width(Length::Fixed(24.))
-                                                .into(),
-                                        });
-
-                                    // Text content column
-                                    let mut text_col = column!(
-                                        row!(
-                                            text(app).size(theme.font_size.xs),
-                                            text(time)
-                                                .size(theme.font_size.xs)
-                                                .color(
-                                                    theme
-                                                        .get_theme()
-                                                        .extended_palette()
-                                                        .secondary
-                                                        .base
-                                                        .text
-                                                ),
+                                // Build the main row with optional icon
+                                let mut content_row =
+                                    row!().spacing(theme.space.xs).align_y(Alignment::Center);
+                                if let Some(icon_el) = icon_element {
+                                    content_row = content_row.push(icon_el);
+                                }
+                                content_row = content_row
+                                    .push(text_col)
+                                    .push_maybe((!n.body.is_empty()).then(|| {
+                                        icon_button::<Message>(theme, StaticIcon::Copy)
+                                            .on_press(Message::CopyBody(id))
+                                    }))
+                                    .push(
+                                        icon_button::<Message>(
+                                            theme,
+                                            if pinned {
+                                                StaticIcon::Pin
+                                            } else {
+                                                StaticIcon::PinOutline
+                                            },
                                         )
-                                        .spacing(theme.space.xs),
-                                        text(summary).size(theme.font_size.sm),
+                                        .on_press(Message::TogglePin(id)),
                                     )
-                                    .spacing(2)
-                                    .width(Length::Fill);
-
-                                    if !body.is_empty() {
-                                        text_col = text_col.push(
-                                            text(truncate_chars(&body, 200).to_owned())
-                                                .size(theme.font_size.xs),
-                                        );
-                                    }
+                                    .push_maybe((!pinned).then(|| {
+                                        icon_button::<Message>(theme, StaticIcon::Close)
+                                            .on_press(Message::Dismiss(id))
+                                    }));
 
-                                    // Action buttons row
-                                    if !visible_actions.is_empty() {
-                                        let action_buttons: Vec<Element<'_, _, _>> =
-                                            visible_actions
-                                                .iter()
-                                                .map(|(key, label)| {
-                                                    button(
-                                                        text(label.clone())
-                                                            .size(theme.font_size.xs),
-                                                    )
-                                                    .style(theme.ghost_button_style())
-                                                    .padding([2, theme.space.xs])
-                                                    .on_press(Message::InvokeAction(
-                                                        id,
-                                                        key.clone(),
-                                                    ))
-                                                    .into()
-                                                })
-                                                .collect();
-                                        text_col = text_col.push(
-                                            Row::with_children(action_buttons)
-                                                .spacing(theme.space.xxs),
-                                        );
-                                    }
+                                let notification_content: Element<'_, _, _> =
+                                    container(content_row).padding([theme.space.xs, 0]).into();
 
-                                    // Build the main row with optional icon
-                                    let mut content_row = row!().spacing(theme.space.xs).align_y(Alignment::Center);
-                                    if let Some(icon_el) = icon_element {
-                                        content_row = content_row.push(icon_el);
-                                    }
-                                    content_row = content_row
-                                        .push(text_col)
-                                        .push(
-                                            icon_button::<Message>(theme, StaticIcon::Close)
-                                                .on_press(Message::Dismiss(id)),
-                                        );
-
-                                    let notification_content: Element<'_, _, _> =
-                                        container(content_row)
-                                            .padding([theme.space.xs, 0])
-                                            .into();
-
-                                    // Wrap with mouse_area for default action click
-                                    if has_default_action {
-                                        mouse_area(notification_content)
-                                            .on_press(Message::InvokeAction(
-                                                id,
-                                                "default".to_string(),
-                                            ))
-                                            .into()
-                                    } else {
-                                        notification_content
-                                    }
-                                })
-                                .collect::<Vec<Element<'_, _, _>>>(),
-                        )
-                        .spacing(2)
-                        .padding([0, theme.space.xs]),
-                    ))
-                    .max_height(400),
-                )
-                .into()
-            },
-        )
+                                // Wrap with mouse_area for default action click, or to open
+                                // the sending app when there is none
+                                if has_default_action {
+                                    mouse_area(notification_content)
+                                        .on_press(Message::InvokeAction(id, "default".to_string()))
+                                        .into()
+                                } else if can_open_app {
+                                    mouse_area(notification_content)
+                                        .on_press(Message::OpenApp(id))
+                                        .into()
+                                } else {
+                                    notification_content
+                                }
+                            })
+                            .collect::<Vec<Element<'_, _, _>>>(),
+                    )
+                    .spacing(2)
+                    .padding([0, theme.space.xs]),
+                ))
+                .max_height(400),
+            )
+            .into()
+        },)
+        .push_maybe((can_replay || can_restore).then(|| {
+            row!(horizontal_space())
+                .push_maybe(can_replay.then(|| {
+                    button("Replay popups")
+                        .style(theme.ghost_button_style())
+                        .padding([2, theme.space.xs])
+                        .on_press(Message::ReplayPopups)
+                }))
+                .push_maybe(can_restore.then(|| {
+                    button("Undo dismiss")
+                        .style(theme.ghost_button_style())
+                        .padding([2, theme.space.xs])
+                        .on_press(Message::RestoreLast)
+                }))
+                .spacing(theme.space.xs)
+                .padding([0, theme.space.xs])
+        }))
         .spacing(theme.space.xs)
         .max_width(MenuSize::Medium)
         .into()
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        NotificationService::subscribe_with_config(
+        let service = NotificationService::subscribe_with_config(
             self.config.max_notifications,
             self.config.default_timeout,
         )
-        .map(Message::Event)
+        .map(Message::Event);
+
+        if self.config.quiet_hours.is_some() {
+            Subscription::batch(vec![
+                service,
+                every(Duration::from_secs(30)).map(|_| Message::QuietHoursTick),
+            ])
+        } else {
+            service
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn otp_needs_a_keyword_nearby() {
+        assert_eq!(find_otp("Your verification code is 123456"), Some("123456"));
+        assert_eq!(find_otp("123456 is your OTP"), Some("123456"));
+        assert_eq!(find_otp("Order 123456 has shipped"), None);
+        assert_eq!(
+            find_otp("Use code 654321 to pay 100000 EUR"),
+            Some("654321")
+        );
+        assert_eq!(find_otp("Your 12345678 PIN"), None);
+    }
+
+    #[test]
+    fn otp_keyword_must_be_close() {
+        let far = format!("Your code {} 123456", "x".repeat(40));
+        assert_eq!(find_otp(&far), None);
+        assert_eq!(find_otp("Ticket 123456 needs a passcode"), Some("123456"));
+    }
+
+    #[test]
+    fn first_url_is_found() {
+        assert_eq!(
+            find_url("Build failed: https://ci.example.com/jobs/42 and http://other.example"),
+            Some("https://ci.example.com/jobs/42")
+        );
+        assert_eq!(find_url("no links here, just ftp://example.com"), None);
+    }
+
+    #[test]
+    fn trailing_punctuation_is_not_part_of_the_url() {
+        assert_eq!(
+            find_url("See https://example.com/page."),
+            Some("https://example.com/page")
+        );
+        assert_eq!(
+            find_url("Really? https://example.com/a?b=1!?"),
+            Some("https://example.com/a?b=1")
+        );
+        assert_eq!(
+            find_url("<a href=\"https://example.com/x\">link</a>"),
+            Some("https://example.com/x")
+        );
+    }
+
+    #[test]
+    fn closing_parenthesis_is_kept_only_when_balanced() {
+        assert_eq!(
+            find_url("(see https://example.com/docs)."),
+            Some("https://example.com/docs")
+        );
+        assert_eq!(
+            find_url("https://en.wikipedia.org/wiki/Rust_(programming_language)"),
+            Some("https://en.wikipedia.org/wiki/Rust_(programming_language)")
+        );
+        assert_eq!(
+            find_url("(https://en.wikipedia.org/wiki/Rust_(programming_language))."),
+            Some("https://en.wikipedia.org/wiki/Rust_(programming_language)")
+        );
+    }
+
+    fn sound_config(default_sound: &str, app_sounds: &[(&str, &str)]) -> NotificationsModuleConfig {
+        NotificationsModuleConfig {
+            sound: true,
+            default_sound: default_sound.to_string(),
+            app_sounds: app_sounds
+                .iter()
+                .map(|(app, sound)| (app.to_string(), sound.to_string()))
+                .collect(),
+            ..NotificationsModuleConfig::default()
+        }
+    }
+
+    fn with_hint(app_name: &str, hint: Option<&str>) -> Notification {
+        Notification {
+            app_name: app_name.to_string(),
+            sound: hint.map(|name| NotificationSound::Name(name.to_string())),
+            ..Notification::test(1)
+        }
+    }
+
+    fn named(name: &str) -> Option<NotificationSound> {
+        Some(NotificationSound::Name(name.to_string()))
+    }
+
+    #[test]
+    fn sounds_are_off_unless_enabled() {
+        let config = NotificationsModuleConfig {
+            sound: false,
+            ..sound_config("bell", &[("Mail", "mail")])
+        };
+        assert_eq!(
+            Notifications::resolve_sound(&config, &with_hint("Mail", Some("hint"))),
+            None
+        );
+    }
+
+    #[test]
+    fn app_override_wins_over_hint_and_default() {
+        let config = sound_config("bell", &[("mail", "/usr/share/sounds/mail.oga")]);
+        assert_eq!(
+            Notifications::resolve_sound(&config, &with_hint("Mail", Some("hint"))),
+            Some(NotificationSound::File(
+                "/usr/share/sounds/mail.oga".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn empty_app_override_mutes() {
+        let config = sound_config("bell", &[("Mail", "")]);
+        assert_eq!(
+            Notifications::resolve_sound(&config, &with_hint("Mail", Some("hint"))),
+            None
+        );
+    }
+
+    #[test]
+    fn hint_wins_over_default() {
+        let config = sound_config("bell", &[("Mail", "mail")]);
+        assert_eq!(
+            Notifications::resolve_sound(&config, &with_hint("Chat", Some("hint"))),
+            named("hint")
+        );
+        assert_eq!(
+            Notifications::resolve_sound(&config, &with_hint("Chat", None)),
+            named("bell")
+        );
+    }
+
+    #[test]
+    fn suppress_sound_mutes_hint_and_default() {
+        let config = sound_config("bell", &[]);
+        let notification = Notification {
+            suppress_sound: true,
+            ..with_hint("Chat", Some("hint"))
+        };
+        assert_eq!(Notifications::resolve_sound(&config, &notification), None);
+    }
+
+    #[test]
+    fn empty_default_sound_is_silent() {
+        let config = sound_config("", &[]);
+        assert_eq!(
+            Notifications::resolve_sound(&config, &with_hint("Chat", None)),
+            None
+        );
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2025, 3, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn absolute_timestamps_show_the_date_unless_today() {
+        let now = at(10, 12, 0);
+
+        assert_eq!(format_timestamp(&at(10, 9, 5), now, false), "09:05");
+        assert_eq!(format_timestamp(&at(9, 23, 30), now, false), "09 Mar 23:30");
+    }
+
+    #[test]
+    fn relative_timestamps_cover_the_last_day() {
+        let now = at(10, 12, 0);
+
+        assert_eq!(format_timestamp(&at(10, 12, 0), now, true), "now");
+        assert_eq!(format_timestamp(&at(10, 11, 55), now, true), "5m ago");
+        assert_eq!(format_timestamp(&at(10, 9, 0), now, true), "3h ago");
+        assert_eq!(format_timestamp(&at(9, 11, 0), now, true), "09 Mar 11:00");
+    }
+
+    #[test]
+    fn future_timestamps_are_now() {
+        let now = at(10, 12, 0);
+
+        assert_eq!(format_timestamp(&at(10, 12, 5), now, true), "now");
+        assert_eq!(format_timestamp(&at(10, 15, 0), now, true), "now");
     }
 }
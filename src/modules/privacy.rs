@@ -45,10 +45,18 @@ impl Privacy {
                         .push_maybe(
                             service
                                 .screenshare_access()
-                                .then(|| icon(StaticIcon::ScreenShare)),
+                                .then(|| icon(theme, StaticIcon::ScreenShare)),
+                        )
+                        .push_maybe(
+                            service
+                                .webcam_access()
+                                .then(|| icon(theme, StaticIcon::Webcam)),
+                        )
+                        .push_maybe(
+                            service
+                                .microphone_access()
+                                .then(|| icon(theme, StaticIcon::Mic1)),
                         )
-                        .push_maybe(service.webcam_access().then(|| icon(StaticIcon::Webcam)))
-                        .push_maybe(service.microphone_access().then(|| icon(StaticIcon::Mic1)))
                         .align_y(Alignment::Center)
                         .spacing(theme.space.xs),
                 )
@@ -4,7 +4,7 @@ use crate::{
     config::SettingsFormat,
     services::{
         ReadOnlyService, Service, ServiceEvent,
-        audio::{AudioCommand, AudioService, DeviceType, Sinks, Sources},
+        audio::{AudioCommand, AudioService, Device, DeviceType, Sinks, Sources},
     },
     theme::AshellTheme,
 };
@@ -48,6 +48,8 @@ pub struct AudioSettingsConfig {
     pub sources_more_cmd: Option<String>,
     pub indicator_format: SettingsFormat,
     pub microphone_indicator_format: SettingsFormat,
+    pub scroll_step: u8,
+    pub scroll_max: u8,
 }
 
 impl AudioSettingsConfig {
@@ -56,12 +58,16 @@ impl AudioSettingsConfig {
         sources_more_cmd: Option<String>,
         indicator_format: SettingsFormat,
         microphone_indicator_format: SettingsFormat,
+        scroll_step: u8,
+        scroll_max: u8,
     ) -> Self {
         Self {
             sinks_more_cmd,
             sources_more_cmd,
             indicator_format,
             microphone_indicator_format,
+            scroll_step,
+            scroll_max,
         }
     }
 }
@@ -104,11 +110,24 @@ impl AudioSettings {
                     if let Some(service) = self.service.as_mut() {
                         service.update(data);
 
-                        if service.sinks.len() < 2 {
+                        // Close the device list when an unplugged device leaves nothing to choose
+                        if service
+                            .sinks
+                            .iter()
+                            .map(Device::entries_count)
+                            .sum::<usize>()
+                            < 2
+                        {
                             return Action::CloseSubMenu;
                         }
 
-                        if service.sources.len() < 2 {
+                        if service
+                            .sources
+                            .iter()
+                            .map(Device::entries_count)
+                            .sum::<usize>()
+                            < 2
+                        {
                             return Action::CloseSubMenu;
                         }
                     }
@@ -197,6 +216,8 @@ impl AudioSettings {
                 let icon_type = Sinks::get_icon(&service.sinks, &service.server_info.default_sink);
                 let volume = service.cur_sink_volume;
 
+                let step = i32::from(self.config.scroll_step);
+                let max = i32::from(self.config.scroll_max);
                 let make_scroll_handler = |cur_volume: i32| {
                     move |delta| {
                         let delta = match delta {
@@ -204,9 +225,9 @@ impl AudioSettings {
                             iced::mouse::ScrollDelta::Pixels { y, .. } => y,
                         };
                         let new_volume = if delta > 0.0 {
-                            (cur_volume + 5).min(100)
+                            (cur_volume + step).min(max.max(cur_volume))
                         } else {
-                            (cur_volume - 5).max(0)
+                            (cur_volume - step).max(0)
                         };
                         Message::SinkVolumeChanged(new_volume)
                     }
@@ -214,7 +235,7 @@ impl AudioSettings {
 
                 match self.config.indicator_format {
                     SettingsFormat::Icon => {
-                        let icon = icon(icon_type);
+                        let icon = icon(theme, icon_type);
                         MouseArea::new(icon)
                             .on_right_press(Message::OpenMore)
                             .on_scroll(make_scroll_handler(volume))
@@ -227,7 +248,7 @@ impl AudioSettings {
                             .into()
                     }
                     SettingsFormat::IconAndPercentage | SettingsFormat::IconAndTime => {
-                        let icon = icon(icon_type);
+                        let icon = icon(theme, icon_type);
                         MouseArea::new(
                             row!(icon, text(format!("{}%", volume)))
                                 .spacing(theme.space.xxs)
@@ -250,6 +271,8 @@ impl AudioSettings {
                     Sources::get_icon(&service.sources, &service.server_info.default_source);
                 let volume = service.cur_source_volume;
 
+                let step = i32::from(self.config.scroll_step);
+                let max = i32::from(self.config.scroll_max);
                 let make_scroll_handler = |cur_volume: i32| {
                     move |delta| {
                         let delta = match delta {
@@ -257,9 +280,9 @@ impl AudioSettings {
                             iced::mouse::ScrollDelta::Pixels { y, .. } => y,
                         };
                         let new_volume = if delta > 0.0 {
-                            (cur_volume + 5).min(100)
+                            (cur_volume + step).min(max.max(cur_volume))
                         } else {
-                            (cur_volume - 5).max(0)
+                            (cur_volume - step).max(0)
                         };
                         Message::SourceVolumeChanged(new_volume)
                     }
@@ -267,7 +290,7 @@ impl AudioSettings {
 
                 match self.config.microphone_indicator_format {
                     SettingsFormat::Icon => {
-                        let icon = icon(icon_type);
+                        let icon = icon(theme, icon_type);
                         MouseArea::new(icon)
                             .on_right_press(Message::OpenSourceMore)
                             .on_scroll(make_scroll_handler(volume))
@@ -280,7 +303,7 @@ impl AudioSettings {
                             .into()
                     }
                     SettingsFormat::IconAndPercentage | SettingsFormat::IconAndTime => {
-                        let icon = icon(icon_type);
+                        let icon = icon(theme, icon_type);
                         MouseArea::new(
                             row!(icon, text(format!("{}%", volume)))
                                 .spacing(theme.space.xxs)
@@ -313,7 +336,13 @@ impl AudioSettings {
                     Message::ToggleSinkMute,
                     service.cur_sink_volume,
                     &Message::SinkVolumeChanged,
-                    if service.sinks.iter().map(|s| s.ports.len()).sum::<usize>() > 1 {
+                    if service
+                        .sinks
+                        .iter()
+                        .map(Device::entries_count)
+                        .sum::<usize>()
+                        > 1
+                    {
                         Some((sub_menu, Message::ToggleSinksMenu))
                     } else {
                         None
@@ -335,7 +364,13 @@ impl AudioSettings {
                         Message::ToggleSourceMute,
                         service.cur_source_volume,
                         &Message::SourceVolumeChanged,
-                        if service.sources.iter().map(|s| s.ports.len()).sum::<usize>() > 1 {
+                        if service
+                            .sources
+                            .iter()
+                            .map(Device::entries_count)
+                            .sum::<usize>()
+                            > 1
+                        {
                             Some((sub_menu, Message::ToggleSourcesMenu))
                         } else {
                             None
@@ -364,12 +399,27 @@ impl AudioSettings {
                     .sinks
                     .iter()
                     .flat_map(|s| {
-                        s.ports.iter().map(|p| SubmenuEntry {
-                            name: format!("{}: {}", p.description, s.description),
-                            device: p.device_type,
-                            active: p.active && s.name == service.server_info.default_sink,
-                            msg: Message::DefaultSinkChanged(s.name.clone(), p.name.clone()),
-                        })
+                        if s.ports.is_empty() {
+                            vec![SubmenuEntry {
+                                name: s.description.clone(),
+                                device: DeviceType::Speaker,
+                                active: s.is_active(&service.server_info.default_sink),
+                                msg: Message::DefaultSinkChanged(s.name.clone(), String::new()),
+                            }]
+                        } else {
+                            s.ports
+                                .iter()
+                                .map(|p| SubmenuEntry {
+                                    name: format!("{}: {}", p.description, s.description),
+                                    device: p.device_type,
+                                    active: p.active && s.name == service.server_info.default_sink,
+                                    msg: Message::DefaultSinkChanged(
+                                        s.name.clone(),
+                                        p.name.clone(),
+                                    ),
+                                })
+                                .collect()
+                        }
                     })
                     .collect(),
                 if self.config.sinks_more_cmd.is_some() {
@@ -393,12 +443,28 @@ impl AudioSettings {
                     .sources
                     .iter()
                     .flat_map(|s| {
-                        s.ports.iter().map(|p| SubmenuEntry {
-                            name: format!("{}: {}", p.description, s.description),
-                            device: p.device_type,
-                            active: p.active && s.name == service.server_info.default_source,
-                            msg: Message::DefaultSourceChanged(s.name.clone(), p.name.clone()),
-                        })
+                        if s.ports.is_empty() {
+                            vec![SubmenuEntry {
+                                name: s.description.clone(),
+                                device: DeviceType::Speaker,
+                                active: s.is_active(&service.server_info.default_source),
+                                msg: Message::DefaultSourceChanged(s.name.clone(), String::new()),
+                            }]
+                        } else {
+                            s.ports
+                                .iter()
+                                .map(|p| SubmenuEntry {
+                                    name: format!("{}: {}", p.description, s.description),
+                                    device: p.device_type,
+                                    active: p.active
+                                        && s.name == service.server_info.default_source,
+                                    msg: Message::DefaultSourceChanged(
+                                        s.name.clone(),
+                                        p.name.clone(),
+                                    ),
+                                })
+                                .collect()
+                        }
                     })
                     .collect(),
                 if self.config.sources_more_cmd.is_some() {
@@ -487,7 +553,7 @@ impl AudioSettings {
                 .map(|e| {
                     if e.active {
                         container(
-                            row!(icon(e.device.get_icon()), text(e.name))
+                            row!(icon(theme, e.device.get_icon()), text(e.name))
                                 .align_y(Alignment::Center)
                                 .spacing(theme.space.md)
                                 .padding([theme.space.xxs, theme.space.sm]),
@@ -499,7 +565,7 @@ impl AudioSettings {
                         .into()
                     } else {
                         button(
-                            row!(icon(e.device.get_icon()), text(e.name))
+                            row!(icon(theme, e.device.get_icon()), text(e.name))
                                 .spacing(theme.space.md)
                                 .align_y(Alignment::Center),
                         )
@@ -29,6 +29,7 @@ pub enum Message {
     PairDevice(OwnedObjectPath),
     ConnectDevice(OwnedObjectPath),
     DisconnectDevice(OwnedObjectPath),
+    DeviceCommandCompleted(ServiceEvent<BluetoothService>),
     RemoveDevice(OwnedObjectPath),
     OpenMore,
     More(Id),
@@ -61,6 +62,8 @@ impl BluetoothSettingsConfig {
 pub struct BluetoothSettings {
     config: BluetoothSettingsConfig,
     service: Option<BluetoothService>,
+    // Device with a connect or disconnect request in flight
+    pending_device: Option<OwnedObjectPath>,
 }
 
 impl BluetoothSettings {
@@ -68,6 +71,7 @@ impl BluetoothSettings {
         Self {
             config,
             service: None,
+            pending_device: None,
         }
     }
 
@@ -120,21 +124,34 @@ impl BluetoothSettings {
                 _ => Action::None,
             },
             Message::ConnectDevice(device_path) => match self.service.as_mut() {
-                Some(service) => Action::Command(
-                    service
-                        .command(BluetoothCommand::ConnectDevice(device_path))
-                        .map(Message::Event),
-                ),
+                Some(service) => {
+                    self.pending_device = Some(device_path.clone());
+
+                    Action::Command(
+                        service
+                            .command(BluetoothCommand::ConnectDevice(device_path))
+                            .map(Message::DeviceCommandCompleted),
+                    )
+                }
                 _ => Action::None,
             },
             Message::DisconnectDevice(device_path) => match self.service.as_mut() {
-                Some(service) => Action::Command(
-                    service
-                        .command(BluetoothCommand::DisconnectDevice(device_path))
-                        .map(Message::Event),
-                ),
+                Some(service) => {
+                    self.pending_device = Some(device_path.clone());
+
+                    Action::Command(
+                        service
+                            .command(BluetoothCommand::DisconnectDevice(device_path))
+                            .map(Message::DeviceCommandCompleted),
+                    )
+                }
                 _ => Action::None,
             },
+            Message::DeviceCommandCompleted(event) => {
+                self.pending_device = None;
+
+                self.update(Message::Event(event))
+            }
             Message::RemoveDevice(device_path) => match self.service.as_mut() {
                 Some(service) => Action::Command(
                     service
@@ -266,20 +283,34 @@ impl BluetoothSettings {
                 )
                 .push_maybe(if some_known {
                     let known_device_entry = |d: &BluetoothDevice| {
+                        let pending = self.pending_device.as_ref() == Some(&d.path);
+                        let status = match (pending, d.connected) {
+                            (true, true) => "Disconnecting...",
+                            (true, false) => "Connecting...",
+                            (false, true) => "Connected",
+                            (false, false) => "Not connected",
+                        };
+
                         button(
                             Row::new()
                                 .push(
-                                    text(d.name.clone())
-                                        .color_maybe(if d.connected {
+                                    column!(
+                                        text(d.name.clone()).color_maybe(if d.connected {
                                             Some(theme.get_theme().palette().success)
                                         } else {
                                             None
-                                        })
-                                        .width(Length::Fill),
+                                        }),
+                                        text(status).size(theme.font_size.xs),
+                                    )
+                                    .width(Length::Fill),
                                 )
                                 .push_maybe(
                                     d.battery.map(|battery| Self::battery_level(theme, battery)),
                                 )
+                                .push_maybe((!pending).then(|| {
+                                    text(if d.connected { "Disconnect" } else { "Connect" })
+                                        .size(theme.font_size.xs)
+                                }))
                                 .push(
                                     icon_button(theme, StaticIcon::Remove)
                                         .on_press(Message::RemoveDevice(d.path.clone()))
@@ -292,11 +323,13 @@ impl BluetoothSettings {
                         )
                         .style(theme.ghost_button_style())
                         .padding([theme.space.xs, theme.space.xs])
-                        .on_press(if d.connected {
-                            Message::DisconnectDevice(d.path.clone())
-                        } else {
-                            Message::ConnectDevice(d.path.clone())
-                        })
+                        .on_press_maybe((!pending).then(|| {
+                            if d.connected {
+                                Message::DisconnectDevice(d.path.clone())
+                            } else {
+                                Message::ConnectDevice(d.path.clone())
+                            }
+                        }))
                         .into()
                     };
 
@@ -387,13 +420,16 @@ impl BluetoothSettings {
     fn battery_level<'a>(theme: &AshellTheme, battery: u8) -> Element<'a, Message> {
         container(
             row!(
-                icon(match battery {
-                    0..=20 => StaticIcon::Battery0,
-                    21..=40 => StaticIcon::Battery1,
-                    41..=60 => StaticIcon::Battery2,
-                    61..=80 => StaticIcon::Battery3,
-                    _ => StaticIcon::Battery4,
-                }),
+                icon(
+                    theme,
+                    match battery {
+                        0..=20 => StaticIcon::Battery0,
+                        21..=40 => StaticIcon::Battery1,
+                        41..=60 => StaticIcon::Battery2,
+                        61..=80 => StaticIcon::Battery3,
+                        _ => StaticIcon::Battery4,
+                    }
+                ),
                 text(format!("{battery}%"))
             )
             .spacing(theme.space.xs)
@@ -426,26 +462,26 @@ impl BluetoothSettings {
                     } else {
                         StaticIcon::Bluetooth
                     };
-                    icon(icon_type).into()
+                    icon(theme, icon_type).into()
                 }
                 SettingsFormat::Percentage | SettingsFormat::Time => {
                     if connected_count > 0 {
                         text(format!("{}", connected_count)).into()
                     } else {
-                        icon(StaticIcon::Bluetooth).into()
+                        icon(theme, StaticIcon::Bluetooth).into()
                     }
                 }
                 SettingsFormat::IconAndPercentage | SettingsFormat::IconAndTime => {
                     if connected_count > 0 {
                         row!(
-                            icon(StaticIcon::BluetoothConnected),
+                            icon(theme, StaticIcon::BluetoothConnected),
                             text(format!("{}", connected_count))
                         )
                         .spacing(theme.space.xxs)
                         .align_y(Alignment::Center)
                         .into()
                     } else {
-                        icon(StaticIcon::Bluetooth).into()
+                        icon(theme, StaticIcon::Bluetooth).into()
                     }
                 }
             };
@@ -12,14 +12,19 @@ use iced::{
     futures::stream,
     widget::{MouseArea, container, row, slider, text},
 };
+use std::time::Duration;
+
+// Brightness writes are throttled to this interval while dragging the slider
+const WRITE_INTERVAL: Duration = Duration::from_millis(50);
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Event(ServiceEvent<BrightnessService>),
     Change(u32),
+    ApplyPending,
     MenuOpened,
     ResetUserAdjusting,
-    ConfigReloaded(SettingsFormat),
+    ConfigReloaded(BrightnessSettingsConfig),
 }
 
 pub enum Action {
@@ -27,20 +32,37 @@ pub enum Action {
     Command(Task<Message>),
 }
 
+#[derive(Debug, Clone)]
+pub struct BrightnessSettingsConfig {
+    pub indicator_format: SettingsFormat,
+    pub scroll_step: u8,
+}
+
+impl BrightnessSettingsConfig {
+    pub fn new(indicator_format: SettingsFormat, scroll_step: u8) -> Self {
+        Self {
+            indicator_format,
+            scroll_step,
+        }
+    }
+}
+
 pub struct BrightnessSettings {
-    config: SettingsFormat,
+    config: BrightnessSettingsConfig,
     service: Option<BrightnessService>,
     ui_percentage: u32,
+    pending_value: Option<u32>,
     is_user_adjusting: bool,
     reset_timer_active: bool,
 }
 
 impl BrightnessSettings {
-    pub fn new(config: SettingsFormat) -> Self {
+    pub fn new(config: BrightnessSettingsConfig) -> Self {
         Self {
             config,
             service: None,
             ui_percentage: 50,
+            pending_value: None,
             is_user_adjusting: false,
             reset_timer_active: false,
         }
@@ -49,6 +71,7 @@ impl BrightnessSettings {
     fn calculate_scroll_brightness(
         current_percentage: u32,
         max_value: u32,
+        step: u32,
         delta: iced::mouse::ScrollDelta,
     ) -> Message {
         let delta = match delta {
@@ -57,9 +80,9 @@ impl BrightnessSettings {
         };
         // brightness is always changed by one less than expected
         let new_percentage = if delta > 0.0 {
-            (current_percentage + 5 + 1).min(100)
+            (current_percentage + step + 1).min(100)
         } else {
-            current_percentage.saturating_sub(5 + 1)
+            current_percentage.saturating_sub(step + 1)
         };
         let new_brightness = new_percentage * max_value / 100;
         Message::Change(new_brightness)
@@ -97,15 +120,19 @@ impl BrightnessSettings {
                     } else {
                         100
                     };
-                match self.service.as_mut() {
-                    Some(service) => Action::Command(
-                        service
-                            .command(BrightnessCommand::Set(value))
-                            .map(Message::Event),
-                    ),
-                    _ => Action::None,
-                }
+                // The slider emits a value for every step while dragging,
+                // only the latest one is written on the next `ApplyPending` tick
+                self.pending_value = Some(value);
+                Action::None
             }
+            Message::ApplyPending => match (self.service.as_mut(), self.pending_value.take()) {
+                (Some(service), Some(value)) => Action::Command(
+                    service
+                        .command(BrightnessCommand::Set(value))
+                        .map(Message::Event),
+                ),
+                _ => Action::None,
+            },
             Message::MenuOpened => {
                 if let Some(service) = self.service.as_mut() {
                     Action::Command(
@@ -122,8 +149,8 @@ impl BrightnessSettings {
                 self.reset_timer_active = false;
                 Action::None
             }
-            Message::ConfigReloaded(format) => {
-                self.config = format;
+            Message::ConfigReloaded(config) => {
+                self.config = config;
                 Action::None
             }
         }
@@ -134,7 +161,7 @@ impl BrightnessSettings {
             let max = service.max;
             let current_percentage = self.ui_percentage;
             row!(
-                container(icon_mono(StaticIcon::Brightness))
+                container(icon_mono(theme, StaticIcon::Brightness))
                     .center_x(32.)
                     .center_y(32.)
                     .clip(true),
@@ -164,13 +191,14 @@ impl BrightnessSettings {
         self.service.as_ref().map(|service| {
             let percentage = self.ui_percentage;
             let max_value = service.max;
+            let step = u32::from(self.config.scroll_step);
 
             let scroll_handler =
-                move |delta| Self::calculate_scroll_brightness(percentage, max_value, delta);
+                move |delta| Self::calculate_scroll_brightness(percentage, max_value, step, delta);
 
-            match self.config {
+            match self.config.indicator_format {
                 SettingsFormat::Icon => {
-                    let icon = icon_mono(StaticIcon::Brightness);
+                    let icon = icon_mono(theme, StaticIcon::Brightness);
                     MouseArea::new(icon).on_scroll(scroll_handler).into()
                 }
                 SettingsFormat::Percentage | SettingsFormat::Time => {
@@ -179,7 +207,7 @@ impl BrightnessSettings {
                         .into()
                 }
                 SettingsFormat::IconAndPercentage | SettingsFormat::IconAndTime => {
-                    let icon = icon_mono(StaticIcon::Brightness);
+                    let icon = icon_mono(theme, StaticIcon::Brightness);
                     MouseArea::new(
                         row!(icon, text(format!("{}%", percentage)))
                             .spacing(theme.space.xxs)
@@ -195,11 +223,16 @@ impl BrightnessSettings {
     pub fn subscription(&self) -> Subscription<Message> {
         Subscription::batch([
             BrightnessService::subscribe().map(Message::Event),
+            if self.pending_value.is_some() {
+                iced::time::every(WRITE_INTERVAL).map(|_| Message::ApplyPending)
+            } else {
+                Subscription::none()
+            },
             if self.reset_timer_active {
                 Subscription::run_with_id(
                     0,
                     stream::once(async {
-                        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                        tokio::time::sleep(Duration::from_millis(500)).await;
                         Message::ResetUserAdjusting
                     }),
                 )
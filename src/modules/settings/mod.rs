@@ -13,13 +13,15 @@ use crate::{
     modules::settings::{
         audio::{AudioSettings, AudioSettingsConfig},
         bluetooth::{BluetoothSettings, BluetoothSettingsConfig},
-        brightness::BrightnessSettings,
+        brightness::{BrightnessSettings, BrightnessSettingsConfig},
         network::{NetworkSettings, NetworkSettingsConfig},
+        night_light::{NightLightSettings, NightLightSettingsConfig},
         power::{PowerSettings, PowerSettingsConfig},
     },
     password_dialog,
     services::idle_inhibitor::IdleInhibitorManager,
     theme::AshellTheme,
+    utils::state,
 };
 use iced::{
     Alignment, Background, Border, Element, Length, Padding, Subscription, Task, Theme,
@@ -33,6 +35,7 @@ mod audio;
 mod bluetooth;
 mod brightness;
 mod network;
+mod night_light;
 mod power;
 
 pub struct Settings {
@@ -42,12 +45,16 @@ pub struct Settings {
     brightness: BrightnessSettings,
     network: NetworkSettings,
     bluetooth: BluetoothSettings,
+    night_light: NightLightSettings,
     idle_inhibitor: Option<IdleInhibitorManager>,
     sub_menu: Option<SubMenu>,
+    persist_sub_menu: bool,
     password_dialog: Option<(String, String)>,
     indicators: Vec<SettingsIndicator>,
     custom_buttons: Vec<SettingsCustomButton>,
     custom_buttons_status: HashMap<String, Option<bool>>,
+    profiles: Vec<String>,
+    active_profile: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +63,7 @@ pub enum Message {
     Bluetooth(bluetooth::Message),
     Audio(audio::Message),
     Brightness(brightness::Message),
+    NightLight(night_light::Message),
     ToggleInhibitIdle,
     Lock,
     Power(power::Message),
@@ -65,6 +73,8 @@ pub enum Message {
     CustomButtonsStatus(Vec<(String, Option<bool>)>),
     MenuOpened,
     ConfigReloaded(SettingsModuleConfig),
+    ProfilesChanged(Vec<String>, Option<String>),
+    SelectProfile(String),
 }
 
 pub enum Action {
@@ -74,6 +84,9 @@ pub enum Action {
     RequestKeyboard(Id),
     ReleaseKeyboard(Id),
     ReleaseKeyboardWithCommand(Id, Task<Message>),
+    LowBattery(i64),
+    ApplyProfile(String),
+    ResetProfile,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -87,6 +100,46 @@ pub enum SubMenu {
     Bluetooth,
 }
 
+impl SubMenu {
+    const STATE_FILE: &str = "settings_sub_menu";
+
+    fn name(&self) -> &'static str {
+        match self {
+            SubMenu::PeripheralMenu => "PeripheralMenu",
+            SubMenu::Power => "Power",
+            SubMenu::Sinks => "Sinks",
+            SubMenu::Sources => "Sources",
+            SubMenu::Wifi => "Wifi",
+            SubMenu::Vpn => "Vpn",
+            SubMenu::Bluetooth => "Bluetooth",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "PeripheralMenu" => Some(SubMenu::PeripheralMenu),
+            "Power" => Some(SubMenu::Power),
+            "Sinks" => Some(SubMenu::Sinks),
+            "Sources" => Some(SubMenu::Sources),
+            "Wifi" => Some(SubMenu::Wifi),
+            "Vpn" => Some(SubMenu::Vpn),
+            "Bluetooth" => Some(SubMenu::Bluetooth),
+            _ => None,
+        }
+    }
+
+    fn load() -> Option<Self> {
+        state::read(Self::STATE_FILE).and_then(|name| Self::from_name(&name))
+    }
+
+    fn save(sub_menu: Option<Self>) {
+        state::write(
+            Self::STATE_FILE,
+            sub_menu.as_ref().map_or("", SubMenu::name),
+        );
+    }
+}
+
 impl Settings {
     pub fn new(config: SettingsModuleConfig) -> Self {
         Settings {
@@ -98,6 +151,9 @@ impl Settings {
                 config.shutdown_cmd,
                 config.logout_cmd,
                 config.battery_format,
+                config.battery_warn_threshold,
+                config.battery_alert_threshold,
+                config.battery_low_notification,
                 config.peripheral_indicators,
                 config.peripheral_battery_format,
             )),
@@ -106,8 +162,13 @@ impl Settings {
                 config.audio_sources_more_cmd,
                 config.audio_indicator_format,
                 config.microphone_indicator_format,
+                config.volume_scroll_step,
+                config.volume_scroll_max,
+            )),
+            brightness: BrightnessSettings::new(BrightnessSettingsConfig::new(
+                config.brightness_indicator_format,
+                config.brightness_scroll_step,
             )),
-            brightness: BrightnessSettings::new(config.brightness_indicator_format),
             network: NetworkSettings::new(NetworkSettingsConfig::new(
                 config.wifi_more_cmd,
                 config.vpn_more_cmd,
@@ -118,20 +179,43 @@ impl Settings {
                 config.bluetooth_more_cmd,
                 config.bluetooth_indicator_format,
             )),
+            night_light: NightLightSettings::new(NightLightSettingsConfig::new(
+                config.night_light_cmd,
+                config.night_light_warm_temperature,
+                config.night_light_cool_temperature,
+            )),
             idle_inhibitor: if config.remove_idle_btn {
                 None
             } else {
                 IdleInhibitorManager::new()
             },
-            sub_menu: None,
+            sub_menu: if config.persist_sub_menu {
+                SubMenu::load()
+            } else {
+                None
+            },
+            persist_sub_menu: config.persist_sub_menu,
             password_dialog: None,
             indicators: config.indicators,
             custom_buttons: config.custom_buttons,
             custom_buttons_status: HashMap::new(),
+            profiles: Vec::new(),
+            active_profile: None,
         }
     }
 
     pub fn update(&mut self, message: Message) -> Action {
+        let sub_menu = self.sub_menu;
+        let action = self.handle_message(message);
+
+        if self.persist_sub_menu && self.sub_menu != sub_menu {
+            SubMenu::save(self.sub_menu);
+        }
+
+        action
+    }
+
+    fn handle_message(&mut self, message: Message) -> Action {
         match message {
             Message::Power(msg) => match self.power.update(msg) {
                 power::Action::None => Action::None,
@@ -144,6 +228,7 @@ impl Settings {
                     Action::None
                 }
                 power::Action::Command(task) => Action::Command(task.map(Message::Power)),
+                power::Action::LowBattery(capacity) => Action::LowBattery(capacity),
             },
             Message::Audio(msg) => match self.audio.update(msg) {
                 audio::Action::None => Action::None,
@@ -233,6 +318,12 @@ impl Settings {
                 brightness::Action::None => Action::None,
                 brightness::Action::Command(task) => Action::Command(task.map(Message::Brightness)),
             },
+            Message::NightLight(msg) => match self.night_light.update(msg) {
+                night_light::Action::None => Action::None,
+                night_light::Action::Command(task) => {
+                    Action::Command(task.map(Message::NightLight))
+                }
+            },
             Message::ToggleSubMenu(menu_type) => {
                 if self.sub_menu == Some(menu_type) {
                     self.sub_menu.take();
@@ -314,7 +405,15 @@ impl Settings {
                 Action::None
             }
             Message::MenuOpened => {
-                self.sub_menu = None;
+                // The last opened sub menu is kept, refresh the wifi list if it's visible again
+                let wifi_task = if self.sub_menu == Some(SubMenu::Wifi) {
+                    match self.network.update(network::Message::WifiMenuOpened) {
+                        network::Action::Command(task) => task.map(Message::Network),
+                        _ => Task::none(),
+                    }
+                } else {
+                    Task::none()
+                };
 
                 let buttons = self.custom_buttons.clone();
 
@@ -374,7 +473,11 @@ impl Settings {
                     brightness::Action::Command(task) => task.map(Message::Brightness),
                 };
 
-                Action::Command(Task::batch([custom_buttons_task, brightness_task]))
+                Action::Command(Task::batch([
+                    custom_buttons_task,
+                    brightness_task,
+                    wifi_task,
+                ]))
             }
             Message::ConfigReloaded(config) => {
                 self.lock_cmd = config.lock_cmd;
@@ -386,6 +489,9 @@ impl Settings {
                         config.shutdown_cmd,
                         config.logout_cmd,
                         config.battery_format,
+                        config.battery_warn_threshold,
+                        config.battery_alert_threshold,
+                        config.battery_low_notification,
                         config.peripheral_indicators,
                         config.peripheral_battery_format,
                     )));
@@ -395,6 +501,8 @@ impl Settings {
                         config.audio_sources_more_cmd,
                         config.audio_indicator_format,
                         config.microphone_indicator_format,
+                        config.volume_scroll_step,
+                        config.volume_scroll_max,
                     )));
                 self.network.update(network::Message::ConfigReloaded(
                     NetworkSettingsConfig::new(
@@ -411,20 +519,79 @@ impl Settings {
                     ),
                 ));
                 self.brightness.update(brightness::Message::ConfigReloaded(
-                    config.brightness_indicator_format,
+                    BrightnessSettingsConfig::new(
+                        config.brightness_indicator_format,
+                        config.brightness_scroll_step,
+                    ),
                 ));
+                let night_light_action =
+                    self.night_light
+                        .update(night_light::Message::ConfigReloaded(
+                            NightLightSettingsConfig::new(
+                                config.night_light_cmd,
+                                config.night_light_warm_temperature,
+                                config.night_light_cool_temperature,
+                            ),
+                        ));
                 if config.remove_idle_btn {
                     self.idle_inhibitor = None;
                 } else if self.idle_inhibitor.is_none() {
                     self.idle_inhibitor = IdleInhibitorManager::new();
                 }
+                self.persist_sub_menu = config.persist_sub_menu;
                 self.indicators = config.indicators;
                 self.custom_buttons = config.custom_buttons;
+                match night_light_action {
+                    night_light::Action::None => Action::None,
+                    night_light::Action::Command(task) => {
+                        Action::Command(task.map(Message::NightLight))
+                    }
+                }
+            }
+            Message::ProfilesChanged(profiles, active_profile) => {
+                self.profiles = profiles;
+                self.active_profile = active_profile;
                 Action::None
             }
+            Message::SelectProfile(name) => {
+                // Selecting the active profile again goes back to the base config
+                if self.active_profile.as_ref() == Some(&name) {
+                    Action::ResetProfile
+                } else {
+                    Action::ApplyProfile(name)
+                }
+            }
         }
     }
 
+    fn profiles_section<'a>(&'a self, theme: &'a AshellTheme) -> Option<Element<'a, Message>> {
+        if self.profiles.is_empty() {
+            return None;
+        }
+
+        Some(
+            Row::with_children(self.profiles.iter().map(|name| {
+                let active = self.active_profile.as_ref() == Some(name);
+
+                button(text(name).width(Length::Fill).align_x(Alignment::Center))
+                    .style(move |t, status| {
+                        if active {
+                            theme.primary_button_style()(t, status)
+                        } else {
+                            theme.outline_button_style()(t, status)
+                        }
+                    })
+                    .padding([theme.space.xxs, theme.space.xs])
+                    .width(Length::Fill)
+                    .on_press(Message::SelectProfile(name.clone()))
+                    .into()
+            }))
+            .spacing(theme.space.xs)
+            .width(Length::Fill)
+            .into(),
+        )
+    }
+
     pub fn menu_view<'a>(
         &'a self,
         id: Id,
@@ -433,8 +600,14 @@ impl Settings {
     ) -> Element<'a, Message> {
         container(
             if let Some((ssid, current_password)) = &self.password_dialog {
-                password_dialog::view(id, theme, ssid, current_password)
-                    .map(Message::PasswordDialog)
+                password_dialog::view(
+                    id,
+                    theme,
+                    ssid,
+                    current_password,
+                    self.network.connection_error(ssid),
+                )
+                .map(Message::PasswordDialog)
             } else {
                 let battery_data = self
                     .power
@@ -500,6 +673,9 @@ impl Settings {
                         self.network
                             .airplane_mode_quick_setting_button(theme)
                             .map(|(button, _)| (button.map(Message::Network), None)),
+                        self.night_light
+                            .quick_setting_button(theme)
+                            .map(|(button, _)| (button.map(Message::NightLight), None)),
                         self.idle_inhibitor.as_ref().map(|idle_inhibitor| {
                             (
                                 quick_setting_button(
@@ -554,12 +730,16 @@ impl Settings {
                 );
 
                 let (top_sink_slider, bottom_sink_slider) = match position {
-                    Position::Top => (sink_slider.map(|e| e.map(Message::Audio)), None),
                     Position::Bottom => (None, sink_slider.map(|e| e.map(Message::Audio))),
+                    Position::Top | Position::Left | Position::Right => {
+                        (sink_slider.map(|e| e.map(Message::Audio)), None)
+                    }
                 };
                 let (top_source_slider, bottom_source_slider) = match position {
-                    Position::Top => (source_slider.map(|e| e.map(Message::Audio)), None),
                     Position::Bottom => (None, source_slider.map(|e| e.map(Message::Audio))),
+                    Position::Top | Position::Left | Position::Right => {
+                        (source_slider.map(|e| e.map(Message::Audio)), None)
+                    }
                 };
 
                 Column::new()
@@ -607,7 +787,13 @@ impl Settings {
                             .slider(theme)
                             .map(|e| e.map(Message::Brightness)),
                     )
+                    .push_maybe(
+                        self.night_light
+                            .slider(theme)
+                            .map(|e| e.map(Message::NightLight)),
+                    )
                     .push(quick_settings)
+                    .push_maybe(self.profiles_section(theme))
                     .spacing(theme.space.md)
                     .into()
             },
@@ -627,7 +813,7 @@ impl Settings {
                         .as_ref()
                         .filter(|i| i.is_inhibited())
                         .map(|_| {
-                            container(icon(StaticIcon::EyeOpened)).style(|theme: &Theme| {
+                            container(icon(theme, StaticIcon::EyeOpened)).style(|theme: &Theme| {
                                 container::Style {
                                     text_color: Some(theme.palette().danger),
                                     ..Default::default()
@@ -641,7 +827,7 @@ impl Settings {
                 SettingsIndicator::PowerProfile => {
                     if let Some(element) = self
                         .power
-                        .power_profile_indicator()
+                        .power_profile_indicator(theme)
                         .map(|e| e.map(Message::Power))
                     {
                         row = row.push(element);
@@ -732,6 +918,7 @@ impl Settings {
             self.brightness.subscription().map(Message::Brightness),
             self.network.subscription().map(Message::Network),
             self.bluetooth.subscription().map(Message::Bluetooth),
+            self.night_light.subscription().map(Message::NightLight),
         ])
     }
 }
@@ -817,7 +1004,7 @@ fn quick_setting_button<'a, Msg: Clone + 'static, I: Icon>(
     with_submenu: Option<(SubMenu, Option<SubMenu>, Msg)>,
 ) -> Element<'a, Msg> {
     let main_content = row!(
-        icon(icon_type).size(theme.font_size.lg),
+        icon(theme, icon_type).size(theme.font_size.lg),
         container(
             Column::new()
                 .push(text(title).size(theme.font_size.sm))
@@ -161,6 +161,9 @@ impl NetworkSettingsConfig {
 pub struct NetworkSettings {
     config: NetworkSettingsConfig,
     service: Option<NetworkService>,
+    // SSID of the last network a password was submitted for, used to detect a wrong password
+    password_sent_for: Option<String>,
+    connection_error: Option<(String, String)>,
 }
 
 impl NetworkSettings {
@@ -168,9 +171,18 @@ impl NetworkSettings {
         Self {
             config,
             service: None,
+            password_sent_for: None,
+            connection_error: None,
         }
     }
 
+    pub fn connection_error(&self, ssid: &str) -> Option<&str> {
+        self.connection_error
+            .as_ref()
+            .filter(|(error_ssid, _)| error_ssid == ssid)
+            .map(|(_, error)| error.as_str())
+    }
+
     pub fn update(&mut self, message: Message) -> Action {
         match message {
             Message::Event(event) => match event {
@@ -179,8 +191,29 @@ impl NetworkSettings {
                     Action::None
                 }
                 ServiceEvent::Update(NetworkEvent::RequestPasswordForSSID(ssid)) => {
+                    if self.password_sent_for.take().as_ref() == Some(&ssid) {
+                        self.connection_error = Some((
+                            ssid.clone(),
+                            "Authentication failed, check the password".to_string(),
+                        ));
+                    }
+
                     Action::RequestPasswordForSSID(ssid)
                 }
+                ServiceEvent::Update(NetworkEvent::ConnectionFailed(
+                    ssid,
+                    error,
+                    known_connections,
+                )) => {
+                    self.password_sent_for = None;
+                    self.connection_error = Some((ssid, error));
+
+                    if let Some(service) = self.service.as_mut() {
+                        service.update(NetworkEvent::KnownConnections(known_connections));
+                    }
+
+                    Action::None
+                }
                 ServiceEvent::Update(data) => {
                     if let Some(service) = self.service.as_mut() {
                         service.update(data);
@@ -206,11 +239,15 @@ impl NetworkSettings {
                 _ => Action::None,
             },
             Message::SelectAccessPoint(ac) => match self.service.as_mut() {
-                Some(service) => Action::Command(
-                    service
-                        .command(NetworkCommand::SelectAccessPoint((ac, None)))
-                        .map(Message::Event),
-                ),
+                Some(service) => {
+                    self.connection_error = None;
+
+                    Action::Command(
+                        service
+                            .command(NetworkCommand::SelectAccessPoint((ac, None)))
+                            .map(Message::Event),
+                    )
+                }
                 _ => Action::None,
             },
             Message::RequestWiFiPassword(id, ssid) => {
@@ -276,6 +313,9 @@ impl NetworkSettings {
                         .find(|ap| ap.ssid == ssid)
                         .cloned();
                     if let Some(ap) = ap {
+                        self.password_sent_for = Some(ssid);
+                        self.connection_error = None;
+
                         Action::Command(
                             service
                                 .command(NetworkCommand::SelectAccessPoint((ap, Some(password))))
@@ -311,10 +351,10 @@ impl NetworkSettings {
                     })
                     .map_or_else(
                         || match self.config.indicator_format {
-                            SettingsFormat::Icon => icon(StaticIcon::Wifi0).into(),
+                            SettingsFormat::Icon => icon(theme, StaticIcon::Wifi0).into(),
                             SettingsFormat::Percentage | SettingsFormat::Time => text("0%").into(),
                             SettingsFormat::IconAndPercentage | SettingsFormat::IconAndTime => {
-                                row!(icon(StaticIcon::Wifi0), text("0%"))
+                                row!(icon(theme, StaticIcon::Wifi0), text("0%"))
                                     .spacing(theme.space.xxs)
                                     .align_y(Alignment::Center)
                                     .into()
@@ -330,7 +370,7 @@ impl NetworkSettings {
 
                             match self.config.indicator_format {
                                 SettingsFormat::Icon => wrap_connectivity_style(
-                                    icon(icon_type).into(),
+                                    icon(theme, icon_type).into(),
                                     state.0,
                                     state.1,
                                 ),
@@ -347,7 +387,7 @@ impl NetworkSettings {
                                     let strength_text =
                                         strength.map_or("100%".to_string(), |s| format!("{}%", s));
                                     wrap_connectivity_style(
-                                        row!(icon(icon_type), text(strength_text))
+                                        row!(icon(theme, icon_type), text(strength_text))
                                             .spacing(theme.space.xxs)
                                             .align_y(Alignment::Center)
                                             .into(),
@@ -368,7 +408,7 @@ impl NetworkSettings {
         })
     }
 
-    pub fn vpn_indicator<'a>(&'a self, _: &AshellTheme) -> Option<Element<'a, Message>> {
+    pub fn vpn_indicator<'a>(&'a self, theme: &AshellTheme) -> Option<Element<'a, Message>> {
         self.service.as_ref().and_then(|service| {
             service
                 .active_connections
@@ -377,7 +417,7 @@ impl NetworkSettings {
                 .map(|a| {
                     let icon_type = a.get_icon();
 
-                    container(icon(icon_type))
+                    container(icon(theme, icon_type))
                         .style(|theme: &Theme| container::Style {
                             text_color: Some(theme.extended_palette().danger.weak.color),
                             ..Default::default()
@@ -421,6 +461,7 @@ impl NetworkSettings {
                                 service,
                                 id,
                                 theme,
+                                self.connection_error.as_ref(),
                                 active_connection
                                     .map(|(name, strength, _)| (name.as_str(), *strength)),
                                 self.config.wifi_more_cmd.is_some(),
@@ -538,6 +579,7 @@ impl NetworkSettings {
         service: &'a NetworkService,
         id: Id,
         theme: &'a AshellTheme,
+        connection_error: Option<&'a (String, String)>,
         active_connection: Option<(&str, u8)>,
         show_more_button: bool,
     ) -> Element<'a, Message> {
@@ -556,6 +598,15 @@ impl NetworkSettings {
             .width(Length::Fill)
             .align_y(Alignment::Center),
             horizontal_rule(1),
+        )
+        .push_maybe(connection_error.map(|(ssid, error)| {
+            text(format!("Failed to connect to {ssid}: {error}"))
+                .size(theme.font_size.sm)
+                .style(|theme: &Theme| text::Style {
+                    color: Some(theme.palette().danger),
+                })
+        }))
+        .push(
             container(scrollable(
                 Column::with_children({
                     let (active_networks, inactive_networks): (Vec<_>, Vec<_>) = service
@@ -578,7 +629,7 @@ impl NetworkSettings {
                             button(
                                 container(
                                     row!(
-                                        icon(if ac.public {
+                                        icon(theme, if ac.public {
                                             ActiveConnectionInfo::get_wifi_icon(ac.strength)
                                         } else {
                                             ActiveConnectionInfo::get_wifi_lock_icon(ac.strength)
@@ -603,7 +654,8 @@ impl NetworkSettings {
                             .style(theme.ghost_button_style())
                             .padding([8, 8])
                             .on_press_maybe(if !is_active {
-                                Some(if is_known {
+                                // Open networks can be joined without asking for a password
+                                Some(if is_known || ac.public {
                                     Message::SelectAccessPoint(ac.clone())
                                 } else {
                                     Message::RequestWiFiPassword(id, ac.ssid.to_string())
@@ -0,0 +1,246 @@
+use super::quick_setting_button;
+use crate::{
+    components::icons::{StaticIcon, icon_mono},
+    theme::AshellTheme,
+    utils::launcher,
+};
+use iced::{
+    Alignment, Element, Length, Subscription, Task,
+    time::every,
+    widget::{container, row, slider, text},
+};
+use log::{debug, warn};
+use std::time::Duration;
+use tokio::process::Child;
+
+// How often the running command is checked for an early exit
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Toggle,
+    Change(u32),
+    Apply,
+    CheckProcess,
+    Stopped,
+    ConfigReloaded(NightLightSettingsConfig),
+}
+
+pub enum Action {
+    None,
+    Command(Task<Message>),
+}
+
+#[derive(Debug, Clone)]
+pub struct NightLightSettingsConfig {
+    pub cmd: Option<String>,
+    pub warm_temperature: u32,
+    pub cool_temperature: u32,
+}
+
+impl NightLightSettingsConfig {
+    pub fn new(cmd: Option<String>, warm_temperature: u32, cool_temperature: u32) -> Self {
+        Self {
+            cmd,
+            warm_temperature: warm_temperature.min(cool_temperature),
+            cool_temperature: cool_temperature.max(warm_temperature),
+        }
+    }
+}
+
+pub struct NightLightSettings {
+    config: NightLightSettingsConfig,
+    temperature: u32,
+    // The command keeps running while the night light is on,
+    // the compositor restores the gamma ramps once it's killed
+    process: Option<Child>,
+    // The previous command is being stopped, the new one starts once it exited
+    restarting: bool,
+}
+
+impl NightLightSettings {
+    pub fn new(config: NightLightSettingsConfig) -> Self {
+        Self {
+            temperature: config.warm_temperature,
+            config,
+            process: None,
+            restarting: false,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.process.is_some() || self.restarting
+    }
+
+    // Two commands would race for the gamma control, so a running one
+    // has to exit before its replacement is spawned
+    fn start(&mut self) -> Task<Message> {
+        if self.process.is_some() {
+            let task = self.stop();
+            self.restarting = true;
+
+            task
+        } else {
+            // While restarting, the latest temperature is used once the old command exited
+            if !self.restarting {
+                self.spawn();
+            }
+
+            Task::none()
+        }
+    }
+
+    fn spawn(&mut self) {
+        if let Some(cmd) = &self.config.cmd {
+            let cmd = cmd.replace("{temperature}", &self.temperature.to_string());
+            debug!("Starting night light: {cmd}");
+
+            self.process = launcher::spawn_command(&cmd);
+        }
+    }
+
+    fn stop(&mut self) -> Task<Message> {
+        self.restarting = false;
+
+        match self.process.take() {
+            Some(mut process) => {
+                debug!("Stopping night light");
+
+                if let Err(e) = process.start_kill() {
+                    warn!("Failed to stop the night light command: {e}");
+                }
+
+                Task::perform(async move { process.wait().await }, |result| {
+                    if let Err(e) = result {
+                        warn!("Failed to wait for the night light command: {e}");
+                    }
+
+                    Message::Stopped
+                })
+            }
+            None => Task::none(),
+        }
+    }
+
+    // A command that exits on its own, or fails to start, turns the night light off
+    fn check_process(&mut self) {
+        if let Some(process) = self.process.as_mut() {
+            match process.try_wait() {
+                Ok(None) => {}
+                Ok(Some(status)) => {
+                    warn!("Night light command exited with {status}");
+                    self.process = None;
+                }
+                Err(e) => {
+                    warn!("Failed to check the night light command: {e}");
+                    self.process = None;
+                }
+            }
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Action {
+        match message {
+            Message::Toggle => {
+                if self.is_active() {
+                    Action::Command(self.stop())
+                } else {
+                    Action::Command(self.start())
+                }
+            }
+            Message::Change(temperature) => {
+                self.temperature = temperature;
+                Action::None
+            }
+            Message::Apply => {
+                if self.is_active() {
+                    Action::Command(self.start())
+                } else {
+                    Action::None
+                }
+            }
+            Message::CheckProcess => {
+                self.check_process();
+                Action::None
+            }
+            Message::Stopped => {
+                if self.restarting {
+                    self.restarting = false;
+                    self.spawn();
+                }
+                Action::None
+            }
+            Message::ConfigReloaded(config) => {
+                self.temperature = self
+                    .temperature
+                    .clamp(config.warm_temperature, config.cool_temperature);
+                self.config = config;
+
+                if self.config.cmd.is_none() {
+                    Action::Command(self.stop())
+                } else {
+                    Action::None
+                }
+            }
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        if self.process.is_some() {
+            every(POLL_INTERVAL).map(|_| Message::CheckProcess)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    pub fn quick_setting_button<'a>(
+        &'a self,
+        theme: &'a AshellTheme,
+    ) -> Option<(Element<'a, Message>, Option<Element<'a, Message>>)> {
+        self.config.cmd.as_ref().map(|_| {
+            (
+                quick_setting_button(
+                    theme,
+                    StaticIcon::NightLight,
+                    "Night Light".to_string(),
+                    self.is_active().then(|| format!("{}K", self.temperature)),
+                    self.is_active(),
+                    Message::Toggle,
+                    None,
+                    None,
+                ),
+                None,
+            )
+        })
+    }
+
+    pub fn slider(&'_ self, theme: &AshellTheme) -> Option<Element<'_, Message>> {
+        self.is_active().then(|| {
+            row!(
+                container(icon_mono(theme, StaticIcon::NightLight))
+                    .center_x(32.)
+                    .center_y(32.)
+                    .clip(true),
+                slider(
+                    self.config.warm_temperature..=self.config.cool_temperature,
+                    self.temperature,
+                    Message::Change,
+                )
+                .step(100_u32)
+                .on_release(Message::Apply)
+                .width(Length::Fill),
+                text(format!("{}K", self.temperature)).size(theme.font_size.sm),
+            )
+            .align_y(Alignment::Center)
+            .spacing(theme.space.xs)
+            .into()
+        })
+    }
+}
+
+impl Drop for NightLightSettings {
+    fn drop(&mut self) {
+        // Nothing is left to wait for the command, tokio reaps it in the background
+        let _ = self.stop();
+    }
+}
@@ -59,6 +59,7 @@ pub enum Action {
     None,
     TogglePeripheralMenu,
     Command(Task<Message>),
+    LowBattery(i64),
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +70,9 @@ pub struct PowerSettingsConfig {
     pub shutdown_cmd: String,
     pub logout_cmd: String,
     pub battery_format: SettingsFormat,
+    pub battery_warn_threshold: u8,
+    pub battery_alert_threshold: u8,
+    pub battery_low_notification: bool,
     pub peripheral_indicators: PeripheralIndicators,
     pub peripheral_battery_format: SettingsFormat,
 }
@@ -82,6 +86,9 @@ impl PowerSettingsConfig {
         shutdown_cmd: String,
         logout_cmd: String,
         battery_format: SettingsFormat,
+        battery_warn_threshold: u8,
+        battery_alert_threshold: u8,
+        battery_low_notification: bool,
         peripheral_indicators: PeripheralIndicators,
         peripheral_battery_format: SettingsFormat,
     ) -> Self {
@@ -92,6 +99,9 @@ impl PowerSettingsConfig {
             shutdown_cmd,
             logout_cmd,
             battery_format,
+            battery_warn_threshold,
+            battery_alert_threshold,
+            battery_low_notification,
             peripheral_indicators,
             peripheral_battery_format,
         }
@@ -101,6 +111,7 @@ impl PowerSettingsConfig {
 pub struct PowerSettings {
     config: PowerSettingsConfig,
     service: Option<UPowerService>,
+    low_battery_notified: bool,
 }
 
 impl PowerSettings {
@@ -108,6 +119,35 @@ impl PowerSettings {
         Self {
             config,
             service: None,
+            low_battery_notified: false,
+        }
+    }
+
+    fn battery_state(&self, battery: &BatteryData) -> IndicatorState {
+        battery.get_indicator_state(
+            self.config.battery_warn_threshold,
+            self.config.battery_alert_threshold,
+        )
+    }
+
+    // Notify once when the battery drops under the alert threshold,
+    // and re-arm after it is charged above it again.
+    fn check_low_battery(&mut self) -> Action {
+        let Some(battery) = self.service.as_ref().and_then(|s| s.system_battery) else {
+            return Action::None;
+        };
+
+        let is_low = matches!(battery.status, BatteryStatus::Discharging(_))
+            && battery.capacity < i64::from(self.config.battery_alert_threshold);
+
+        if !is_low {
+            self.low_battery_notified = false;
+            Action::None
+        } else if self.low_battery_notified || !self.config.battery_low_notification {
+            Action::None
+        } else {
+            self.low_battery_notified = true;
+            Action::LowBattery(battery.capacity)
         }
     }
 
@@ -116,13 +156,13 @@ impl PowerSettings {
             Message::Event(event) => match event {
                 ServiceEvent::Init(service) => {
                     self.service = Some(service);
-                    Action::None
+                    self.check_low_battery()
                 }
                 ServiceEvent::Update(data) => {
                     if let Some(service) = self.service.as_mut() {
                         service.update(data);
                     }
-                    Action::None
+                    self.check_low_battery()
                 }
                 ServiceEvent::Error(_) => Action::None,
             },
@@ -164,28 +204,30 @@ impl PowerSettings {
 
     pub fn menu<'a>(&'a self, theme: &'a AshellTheme) -> Element<'a, Message> {
         column!(
-            button(row!(icon(StaticIcon::Suspend), text("Suspend")).spacing(theme.space.md))
+            button(row!(icon(theme, StaticIcon::Suspend), text("Suspend")).spacing(theme.space.md))
                 .padding([theme.space.xxs, theme.space.sm])
                 .on_press(Message::Suspend)
                 .width(Length::Fill)
                 .style(theme.ghost_button_style()),
-            button(row!(icon(StaticIcon::Hibernate), text("Hibernate")).spacing(theme.space.md))
-                .padding([theme.space.xxs, theme.space.sm])
-                .on_press(Message::Hibernate)
-                .width(Length::Fill)
-                .style(theme.ghost_button_style()),
-            button(row!(icon(StaticIcon::Reboot), text("Reboot")).spacing(theme.space.md))
+            button(
+                row!(icon(theme, StaticIcon::Hibernate), text("Hibernate")).spacing(theme.space.md)
+            )
+            .padding([theme.space.xxs, theme.space.sm])
+            .on_press(Message::Hibernate)
+            .width(Length::Fill)
+            .style(theme.ghost_button_style()),
+            button(row!(icon(theme, StaticIcon::Reboot), text("Reboot")).spacing(theme.space.md))
                 .padding([theme.space.xxs, theme.space.sm])
                 .on_press(Message::Reboot)
                 .width(Length::Fill)
                 .style(theme.ghost_button_style()),
-            button(row!(icon(StaticIcon::Power), text("Shutdown")).spacing(theme.space.md))
+            button(row!(icon(theme, StaticIcon::Power), text("Shutdown")).spacing(theme.space.md))
                 .padding([theme.space.xxs, theme.space.sm])
                 .on_press(Message::Shutdown)
                 .width(Length::Fill)
                 .style(theme.ghost_button_style()),
             horizontal_rule(1),
-            button(row!(icon(StaticIcon::Logout), text("Logout")).spacing(theme.space.md))
+            button(row!(icon(theme, StaticIcon::Logout), text("Logout")).spacing(theme.space.md))
                 .padding([theme.space.xxs, theme.space.sm])
                 .on_press(Message::Logout)
                 .width(Length::Fill)
@@ -208,7 +250,7 @@ impl PowerSettings {
                         .iter()
                         .map(|p| {
                             Row::new()
-                                .push(icon(p.kind.get_icon()))
+                                .push(icon(theme, p.kind.get_icon()))
                                 .push(text(p.name.to_string()).width(Length::Fill))
                                 .push(self.menu_indicator(theme, p.data, None))
                                 .align_y(Vertical::Center)
@@ -243,24 +285,25 @@ impl PowerSettings {
                     for p in service.peripherals.iter() {
                         row = row.push_maybe({
                             if kinds.as_ref().is_none_or(|kinds| kinds.contains(&p.kind)) {
-                                let state = p.data.get_indicator_state();
+                                let state = self.battery_state(&p.data);
 
                                 Some(
                                     container(match self.config.peripheral_battery_format {
                                         SettingsFormat::Icon => {
                                             convert::Into::<Element<'a, Message>>::into(icon(
+                                                ashell_theme,
                                                 p.get_icon_state(),
                                             ))
                                         }
                                         SettingsFormat::Percentage => row!(
-                                            icon(p.kind.get_icon()),
+                                            icon(ashell_theme, p.kind.get_icon()),
                                             text(format!("{}%", p.data.capacity))
                                         )
                                         .spacing(ashell_theme.space.xxs)
                                         .align_y(Alignment::Center)
                                         .into(),
                                         SettingsFormat::IconAndPercentage => row!(
-                                            icon(p.get_icon_state()),
+                                            icon(ashell_theme, p.get_icon_state()),
                                             text(format!("{}%", p.data.capacity))
                                         )
                                         .spacing(ashell_theme.space.xxs)
@@ -270,7 +313,7 @@ impl PowerSettings {
                                             text(format_time_for_battery(&p.data)).into()
                                         }
                                         SettingsFormat::IconAndTime => row!(
-                                            icon(p.get_icon_state()),
+                                            icon(ashell_theme, p.get_icon_state()),
                                             text(format_time_for_battery(&p.data))
                                         )
                                         .spacing(ashell_theme.space.xxs)
@@ -282,6 +325,9 @@ impl PowerSettings {
                                             text_color: Some(match state {
                                                 IndicatorState::Success => theme.palette().success,
                                                 IndicatorState::Danger => theme.palette().danger,
+                                                IndicatorState::Warning => {
+                                                    theme.extended_palette().danger.weak.color
+                                                }
                                                 _ => theme.palette().text,
                                             }),
                                             ..Default::default()
@@ -311,15 +357,15 @@ impl PowerSettings {
     ) -> Option<Element<'a, Message>> {
         self.service.as_ref().and_then(|service| {
             service.system_battery.map(|battery| {
-                let state = battery.get_indicator_state();
+                let state = self.battery_state(&battery);
 
                 container(match self.config.battery_format {
-                    SettingsFormat::Icon => icon(battery.get_icon()).into(),
+                    SettingsFormat::Icon => icon(ashell_theme, battery.get_icon()).into(),
                     SettingsFormat::Percentage => convert::Into::<Element<'a, Message>>::into(
                         text(format!("{}%", battery.capacity)),
                     ),
                     SettingsFormat::IconAndPercentage => row!(
-                        icon(battery.get_icon()),
+                        icon(ashell_theme, battery.get_icon()),
                         text(format!("{}%", battery.capacity))
                     )
                     .spacing(ashell_theme.space.xxs)
@@ -327,7 +373,7 @@ impl PowerSettings {
                     .into(),
                     SettingsFormat::Time => text(format_time_for_battery(&battery)).into(),
                     SettingsFormat::IconAndTime => row!(
-                        icon(battery.get_icon()),
+                        icon(ashell_theme, battery.get_icon()),
                         text(format_time_for_battery(&battery))
                     )
                     .spacing(ashell_theme.space.xxs)
@@ -338,6 +384,7 @@ impl PowerSettings {
                     text_color: Some(match state {
                         IndicatorState::Success => theme.palette().success,
                         IndicatorState::Danger => theme.palette().danger,
+                        IndicatorState::Warning => theme.extended_palette().danger.weak.color,
                         _ => theme.palette().text,
                     }),
                     ..Default::default()
@@ -353,13 +400,15 @@ impl PowerSettings {
         battery: BatteryData,
         peripheral_icon: Option<StaticIcon>,
     ) -> Element<'a, Message> {
-        let state = battery.get_indicator_state();
+        let state = self.battery_state(&battery);
 
         container({
             let battery_info = container(
                 Row::new()
-                    .push_maybe(peripheral_icon.map(icon))
-                    .push(icon(battery.get_icon()))
+                    .push_maybe(
+                        peripheral_icon.map(|peripheral_icon| icon(ashell_theme, peripheral_icon)),
+                    )
+                    .push(icon(ashell_theme, battery.get_icon()))
                     .push(text(format!("{}%", battery.capacity)))
                     .spacing(ashell_theme.space.xxs),
             )
@@ -367,6 +416,7 @@ impl PowerSettings {
                 text_color: Some(match state {
                     IndicatorState::Success => theme.palette().success,
                     IndicatorState::Danger => theme.palette().danger,
+                    IndicatorState::Warning => theme.extended_palette().danger.weak.color,
                     _ => theme.palette().text,
                 }),
                 ..Default::default()
@@ -438,13 +488,16 @@ impl PowerSettings {
         })
     }
 
-    pub fn power_profile_indicator<'a>(&'a self) -> Option<Element<'a, Message>> {
+    pub fn power_profile_indicator<'a>(
+        &'a self,
+        theme: &AshellTheme,
+    ) -> Option<Element<'a, Message>> {
         self.service
             .as_ref()
             .and_then(|service| match service.power_profile {
                 PowerProfile::Balanced => None,
                 PowerProfile::Performance => Some(
-                    container(icon(StaticIcon::Performance))
+                    container(icon(theme, StaticIcon::Performance))
                         .style(|theme: &Theme| container::Style {
                             text_color: Some(theme.palette().danger),
                             ..Default::default()
@@ -452,7 +505,7 @@ impl PowerSettings {
                         .into(),
                 ),
                 PowerProfile::PowerSaver => Some(
-                    container(icon(StaticIcon::PowerSaver))
+                    container(icon(theme, StaticIcon::PowerSaver))
                         .style(|theme: &Theme| container::Style {
                             text_color: Some(theme.palette().success),
                             ..Default::default()
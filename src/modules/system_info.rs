@@ -1,31 +1,107 @@
 use crate::{
     components::icons::{StaticIcon, icon},
-    config::{SystemInfoIndicator, SystemInfoModuleConfig},
+    config::{
+        GpuSource, LoadAveragePeriod, SystemInfoDiskFormat, SystemInfoGraph, SystemInfoIndicator,
+        SystemInfoLoadAverage, SystemInfoModuleConfig,
+    },
     menu::MenuSize,
     theme::AshellTheme,
+    utils::{IndicatorState, format_bytes, format_rate},
 };
 use iced::{
-    Alignment, Element, Length, Subscription, Theme,
+    Alignment, Element, Length, Point, Rectangle, Renderer, Subscription, Theme,
+    mouse::Cursor,
+    stream::channel,
     time::every,
-    widget::{Column, Row, column, container, horizontal_rule, row, text},
+    widget::{
+        Column, Row, canvas,
+        canvas::{Frame, Geometry, Path, Program, Stroke},
+        column, container, horizontal_rule, row, text,
+    },
 };
 use itertools::Itertools;
-use std::time::{Duration, Instant};
+use log::warn;
+use std::{
+    any::TypeId,
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 use sysinfo::{Components, Disks, Networks, System};
+use tokio::{process::Command, time::sleep};
 
 struct NetworkData {
     ip: String,
-    download_speed: u32,
-    upload_speed: u32,
-    last_check: Instant,
+    /// Download rate in bytes per second.
+    download_speed: u64,
+    /// Upload rate in bytes per second.
+    upload_speed: u64,
+}
+
+struct DiskData {
+    mount_point: String,
+    usage: u32,
+    used_space: u64,
+    total_space: u64,
+}
+
+struct LoadAverageData {
+    one: f64,
+    five: f64,
+    fifteen: f64,
+    cores: usize,
+}
+
+impl LoadAverageData {
+    fn get(&self, period: LoadAveragePeriod) -> f64 {
+        match period {
+            LoadAveragePeriod::One => self.one,
+            LoadAveragePeriod::Five => self.five,
+            LoadAveragePeriod::Fifteen => self.fifteen,
+        }
+    }
+
+    /// The selected averages, colored by the busiest one relative to the core count.
+    fn format(
+        &self,
+        periods: &[LoadAveragePeriod],
+        config: &SystemInfoLoadAverage,
+    ) -> Option<(String, IndicatorState)> {
+        if periods.is_empty() {
+            return None;
+        }
+
+        let value = periods
+            .iter()
+            .map(|period| format!("{:.2}", self.get(*period)))
+            .join(" ");
+        let busiest = periods
+            .iter()
+            .map(|period| self.get(*period))
+            .fold(0., f64::max);
+        let state = threshold_state(
+            &(busiest / self.cores.max(1) as f64),
+            (config.warn_threshold, config.alert_threshold),
+        );
+
+        Some((value, state))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GpuData {
+    usage: u32,
+    temperature: Option<i32>,
 }
 
 struct SystemInfoData {
     pub cpu_usage: u32,
+    pub load_average: LoadAverageData,
     pub memory_usage: u32,
     pub memory_swap_usage: u32,
     pub temperature: Option<i32>,
-    pub disks: Vec<(String, u32)>,
+    pub disks: Vec<DiskData>,
     pub network: Option<NetworkData>,
 }
 
@@ -33,8 +109,9 @@ fn get_system_info(
     system: &mut System,
     components: &mut Components,
     disks: &mut Disks,
-    (networks, last_check): (&mut Networks, Option<Instant>),
+    (networks, last_refresh): (&mut Networks, &mut Instant),
     temperature_sensor: &str,
+    network_interfaces: &[String],
 ) -> SystemInfoData {
     system.refresh_memory();
     system.refresh_cpu_specifics(sysinfo::CpuRefreshKind::everything());
@@ -43,7 +120,20 @@ fn get_system_info(
     disks.refresh(true);
     networks.refresh(true);
 
+    // Rates are averaged over the time since the previous refresh, samples
+    // aren't evenly spaced when the config reloads or the system resumes
+    let now = Instant::now();
+    let elapsed = now.duration_since(*last_refresh).as_secs_f64();
+    *last_refresh = now;
+
     let cpu_usage = system.global_cpu_usage().floor() as u32;
+    let load_average = System::load_average();
+    let load_average = LoadAverageData {
+        one: load_average.one,
+        five: load_average.five,
+        fifteen: load_average.fifteen,
+        cores: system.cpus().len(),
+    };
     let memory_usage = ((system.total_memory() - system.available_memory()) as f32
         / system.total_memory() as f32
         * 100.) as u32;
@@ -64,24 +154,39 @@ fn get_system_info(
         .into_iter()
         .filter(|d| !d.is_removable() && d.total_space() != 0)
         .map(|d| {
-            (
-                d.mount_point().to_string_lossy().to_string(),
-                (((d.total_space() - d.available_space()) as f32) / d.total_space() as f32 * 100.)
-                    as u32,
-            )
+            let used_space = d.total_space().saturating_sub(d.available_space());
+
+            DiskData {
+                mount_point: d.mount_point().to_string_lossy().to_string(),
+                usage: (used_space as f32 / d.total_space() as f32 * 100.) as u32,
+                used_space,
+                total_space: d.total_space(),
+            }
         })
-        .sorted_by(|a, b| a.0.cmp(&b.0))
+        .sorted_by(|a, b| a.mount_point.cmp(&b.mount_point))
         .collect::<Vec<_>>();
 
-    let elapsed = last_check.map(|v| v.elapsed().as_secs());
+    // Without an explicit interface list prefer the interface holding the
+    // default route, falling back to every ethernet/wireless interface.
+    let default_interface = if network_interfaces.is_empty() {
+        default_route_interface().filter(|name| networks.contains_key(name))
+    } else {
+        None
+    };
 
     let network = networks
         .iter()
         .filter(|(name, _)| {
-            name.contains("en")
-                || name.contains("eth")
-                || name.contains("wl")
-                || name.contains("wlan")
+            if !network_interfaces.is_empty() {
+                network_interfaces.contains(name)
+            } else if let Some(default_interface) = &default_interface {
+                *name == default_interface
+            } else {
+                name.contains("en")
+                    || name.contains("eth")
+                    || name.contains("wl")
+                    || name.contains("wlan")
+            }
         })
         .sorted_by_key(|(name, _)| {
             if name.contains("en") {
@@ -125,14 +230,16 @@ fn get_system_info(
         );
 
     let network_speed = |value: u64| {
-        match elapsed {
-            None | Some(0) => 0, // avoid division by zero
-            Some(elapsed) => (value / 1000) as u32 / elapsed as u32,
+        if elapsed > 0. {
+            (value as f64 / elapsed) as u64
+        } else {
+            0 // avoid division by zero
         }
     };
 
     SystemInfoData {
         cpu_usage,
+        load_average,
         memory_usage,
         memory_swap_usage,
         temperature,
@@ -141,14 +248,180 @@ fn get_system_info(
             ip: ip.to_string(),
             download_speed: network_speed(network.1),
             upload_speed: network_speed(network.2),
-            last_check: Instant::now(),
         }),
     }
 }
 
+/// Recent readings of the graphed metrics, oldest first.
+#[derive(Default)]
+struct History {
+    cpu: VecDeque<f32>,
+    memory: VecDeque<f32>,
+    temperature: VecDeque<f32>,
+}
+
+impl History {
+    fn push(&mut self, data: &SystemInfoData, length: usize) {
+        let push = |samples: &mut VecDeque<f32>, value: f32| {
+            samples.push_back(value);
+            while samples.len() > length {
+                samples.pop_front();
+            }
+        };
+
+        push(&mut self.cpu, data.cpu_usage as f32);
+        push(&mut self.memory, data.memory_usage as f32);
+        if let Some(temperature) = data.temperature {
+            push(&mut self.temperature, temperature as f32);
+        }
+    }
+
+    fn samples(&self, graph: SystemInfoGraph) -> &VecDeque<f32> {
+        match graph {
+            SystemInfoGraph::Cpu => &self.cpu,
+            SystemInfoGraph::Memory => &self.memory,
+            SystemInfoGraph::Temperature => &self.temperature,
+        }
+    }
+}
+
+/// Line graph of percentages (or degrees) between 0 and 100, newest sample on the right.
+struct Sparkline<'a> {
+    samples: &'a VecDeque<f32>,
+    /// Samples fitting in the graph width, a short history only fills its right side
+    capacity: usize,
+}
+
+impl<Message> Program<Message> for Sparkline<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if self.samples.len() > 1 {
+            let step = bounds.width / (self.capacity.max(2) - 1) as f32;
+            let start = (self.capacity.saturating_sub(self.samples.len())) as f32 * step;
+            let point = |(i, value): (usize, &f32)| {
+                Point::new(
+                    start + i as f32 * step,
+                    bounds.height * (1. - value.clamp(0., 100.) / 100.),
+                )
+            };
+
+            let line = Path::new(|builder| {
+                let mut points = self.samples.iter().enumerate().map(point);
+                if let Some(first) = points.next() {
+                    builder.move_to(first);
+                }
+                for p in points {
+                    builder.line_to(p);
+                }
+            });
+
+            frame.stroke(
+                &line,
+                Stroke::default()
+                    .with_color(theme.palette().primary)
+                    .with_width(1.5),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// `Err` when nvidia-smi is missing or can't reach the driver, there is nothing to sample then.
+async fn nvidia_gpu_data() -> Result<Option<GpuData>, String> {
+    let output = Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=utilization.gpu,temperature.gpu",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+
+    // One line per GPU, the first one is shown
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut fields = stdout
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim);
+    let usage = fields.next().and_then(|usage| usage.parse().ok());
+
+    Ok(usage.map(|usage| GpuData {
+        usage,
+        temperature: fields.next().and_then(|t| t.parse().ok()),
+    }))
+}
+
+/// The first card exposing a busy percentage, integrated GPUs included.
+fn amd_gpu_device() -> Option<PathBuf> {
+    fs::read_dir("/sys/class/drm")
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path().join("device"))
+        .filter(|device| device.join("gpu_busy_percent").exists())
+        .sorted()
+        .next()
+}
+
+async fn amd_gpu_data(device: &Path) -> Option<GpuData> {
+    let usage = tokio::fs::read_to_string(device.join("gpu_busy_percent"))
+        .await
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+
+    let mut temperature = None;
+    if let Ok(mut hwmons) = tokio::fs::read_dir(device.join("hwmon")).await {
+        while let Ok(Some(hwmon)) = hwmons.next_entry().await {
+            if let Ok(millidegrees) =
+                tokio::fs::read_to_string(hwmon.path().join("temp1_input")).await
+            {
+                temperature = millidegrees
+                    .trim()
+                    .parse::<i32>()
+                    .ok()
+                    .map(|millidegrees| millidegrees / 1000);
+                break;
+            }
+        }
+    }
+
+    Some(GpuData { usage, temperature })
+}
+
+fn default_route_interface() -> Option<String> {
+    let routes = fs::read_to_string("/proc/net/route").ok()?;
+
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let name = fields.next()?;
+        let destination = fields.next()?;
+
+        (destination == "00000000").then(|| name.to_string())
+    })
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     Update,
+    GpuUpdate(Option<GpuData>),
+    ConfigReloaded(SystemInfoModuleConfig),
 }
 
 pub struct SystemInfo {
@@ -157,7 +430,30 @@ pub struct SystemInfo {
     components: Components,
     disks: Disks,
     networks: Networks,
+    /// When `networks` was last refreshed, its counters are relative to it
+    last_refresh: Instant,
     data: SystemInfoData,
+    /// Sampled by its own subscription, the driver tools can be slow to answer
+    gpu: Option<GpuData>,
+    history: History,
+}
+
+fn threshold_state<V: PartialOrd>(value: &V, (warn, alert): (V, V)) -> IndicatorState {
+    if *value >= alert {
+        IndicatorState::Danger
+    } else if *value >= warn {
+        IndicatorState::Warning
+    } else {
+        IndicatorState::Normal
+    }
+}
+
+fn state_color(state: IndicatorState, theme: &Theme) -> Option<iced::Color> {
+    match state {
+        IndicatorState::Warning => Some(theme.extended_palette().danger.weak.color),
+        IndicatorState::Danger => Some(theme.palette().danger),
+        _ => None,
+    }
 }
 
 impl SystemInfo {
@@ -166,21 +462,29 @@ impl SystemInfo {
         let mut components = Components::new_with_refreshed_list();
         let mut disks = Disks::new_with_refreshed_list();
         let mut networks = Networks::new_with_refreshed_list();
+        let mut last_refresh = Instant::now();
         let data = get_system_info(
             &mut system,
             &mut components,
             &mut disks,
-            (&mut networks, None),
+            (&mut networks, &mut last_refresh),
             &config.temperature.sensor,
+            &config.network.interfaces,
         );
 
+        let mut history = History::default();
+        history.push(&data, config.history.length);
+
         Self {
             config,
             system,
             components,
             disks,
             data,
+            gpu: None,
             networks,
+            last_refresh,
+            history,
         }
     }
 
@@ -191,12 +495,21 @@ impl SystemInfo {
                     &mut self.system,
                     &mut self.components,
                     &mut self.disks,
-                    (
-                        &mut self.networks,
-                        self.data.network.as_ref().map(|n| n.last_check),
-                    ),
+                    (&mut self.networks, &mut self.last_refresh),
                     &self.config.temperature.sensor,
+                    &self.config.network.interfaces,
                 );
+                self.history.push(&self.data, self.config.history.length);
+            }
+            Message::GpuUpdate(gpu) => {
+                self.gpu = gpu;
+            }
+            Message::ConfigReloaded(config) => {
+                if config.gpu.source.is_none() {
+                    self.gpu = None;
+                }
+                self.config = config;
+                self.update(Message::Update);
             }
         }
     }
@@ -206,12 +519,18 @@ impl SystemInfo {
         info_icon: StaticIcon,
         label: String,
         value: String,
+        state: IndicatorState,
     ) -> Element<'a, Message> {
         row!(
-            container(icon(info_icon).size(theme.font_size.xl))
+            container(icon(theme, info_icon).size(theme.font_size.xl))
                 .center_x(Length::Fixed(theme.space.xl as f32)),
             text(label).width(Length::Fill),
-            text(value)
+            container(text(value).font(theme.monospace_font)).style(move |theme: &Theme| {
+                container::Style {
+                    text_color: state_color(state, theme),
+                    ..Default::default()
+                }
+            })
         )
         .align_y(Alignment::Center)
         .spacing(theme.space.xs)
@@ -226,37 +545,83 @@ impl SystemInfo {
         threshold: Option<(V, V)>,
         prefix: Option<&str>,
     ) -> Element<'a, Message> {
-        let element = container(
+        let state = threshold.map_or(IndicatorState::Normal, |threshold| {
+            threshold_state(&value, threshold)
+        });
+
+        Self::indicator_element(theme, info_icon, format!("{value}{unit}"), state, prefix)
+    }
+
+    fn indicator_element<'a>(
+        theme: &AshellTheme,
+        info_icon: StaticIcon,
+        value: String,
+        state: IndicatorState,
+        prefix: Option<&str>,
+    ) -> Element<'a, Message> {
+        container(
             row!(
-                icon(info_icon),
+                icon(theme, info_icon),
                 if let Some(prefix) = prefix {
-                    text(format!("{prefix} {value}{unit}"))
+                    text(format!("{prefix} {value}"))
                 } else {
-                    text(format!("{value}{unit}"))
+                    text(value)
                 }
+                .font(theme.monospace_font)
             )
             .spacing(theme.space.xxs),
-        );
+        )
+        .style(move |theme: &Theme| container::Style {
+            text_color: state_color(state, theme),
+            ..Default::default()
+        })
+        .into()
+    }
 
-        if let Some((warn_threshold, alert_threshold)) = threshold {
-            element
-                .style(move |theme: &Theme| container::Style {
-                    text_color: if value > warn_threshold && value < alert_threshold {
-                        Some(theme.extended_palette().danger.weak.color)
-                    } else if value >= alert_threshold {
-                        Some(theme.palette().danger)
-                    } else {
-                        None
-                    },
-                    ..Default::default()
-                })
+    fn graphs_element(&'_ self, theme: &AshellTheme) -> Option<Element<'_, Message>> {
+        let length = self.config.history.length;
+        let graphs = self
+            .config
+            .history
+            .graphs
+            .iter()
+            .map(|graph| (*graph, self.history.samples(*graph)))
+            .filter(|(_, samples)| length > 0 && !samples.is_empty())
+            .map(|(graph, samples)| {
+                let (graph_icon, label) = match graph {
+                    SystemInfoGraph::Cpu => (StaticIcon::Cpu, "CPU"),
+                    SystemInfoGraph::Memory => (StaticIcon::Mem, "Memory"),
+                    SystemInfoGraph::Temperature => (StaticIcon::Temp, "Temperature"),
+                };
+
+                row!(
+                    container(icon(theme, graph_icon))
+                        .center_x(Length::Fixed(theme.space.xl as f32)),
+                    text(label).width(Length::FillPortion(1)),
+                    canvas(Sparkline {
+                        samples,
+                        capacity: length,
+                    })
+                    .width(Length::FillPortion(2))
+                    .height(Length::Fixed(theme.space.xl as f32)),
+                )
+                .align_y(Alignment::Center)
+                .spacing(theme.space.xs)
                 .into()
-        } else {
-            element.into()
-        }
+            })
+            .collect::<Vec<Element<_>>>();
+
+        (!graphs.is_empty()).then(|| {
+            Column::with_children(graphs)
+                .spacing(theme.space.xxs)
+                .padding([0, theme.space.xs])
+                .into()
+        })
     }
 
     pub fn menu_view(&'_ self, theme: &AshellTheme) -> Element<'_, Message> {
+        let gpu_temperature = self.gpu.as_ref().and_then(|gpu| gpu.temperature);
+
         container(
             column!(
                 text("System Info").size(theme.font_size.lg),
@@ -267,18 +632,83 @@ impl SystemInfo {
                         StaticIcon::Cpu,
                         "CPU Usage".to_string(),
                         format!("{}%", self.data.cpu_usage),
+                        threshold_state(
+                            &self.data.cpu_usage,
+                            (
+                                self.config.cpu.warn_threshold,
+                                self.config.cpu.alert_threshold,
+                            ),
+                        ),
                     ))
+                    .push_maybe(
+                        self.data
+                            .load_average
+                            .format(&self.config.load_average.menu, &self.config.load_average)
+                            .map(|(value, state)| {
+                                Self::info_element(
+                                    theme,
+                                    StaticIcon::Cpu,
+                                    "Load Average".to_string(),
+                                    value,
+                                    state,
+                                )
+                            }),
+                    )
+                    .push_maybe(self.gpu.as_ref().map(|gpu| {
+                        Self::info_element(
+                            theme,
+                            StaticIcon::Gpu,
+                            "GPU Usage".to_string(),
+                            format!("{}%", gpu.usage),
+                            threshold_state(
+                                &gpu.usage,
+                                (
+                                    self.config.gpu.warn_threshold,
+                                    self.config.gpu.alert_threshold,
+                                ),
+                            ),
+                        )
+                    }))
+                    .push_maybe(gpu_temperature.map(|temp| {
+                        Self::info_element(
+                            theme,
+                            StaticIcon::Temp,
+                            "GPU Temperature".to_string(),
+                            format!("{temp}°C"),
+                            threshold_state(
+                                &temp,
+                                (
+                                    self.config.temperature.warn_threshold,
+                                    self.config.temperature.alert_threshold,
+                                ),
+                            ),
+                        )
+                    }))
                     .push(Self::info_element(
                         theme,
                         StaticIcon::Mem,
                         "Memory Usage".to_string(),
                         format!("{}%", self.data.memory_usage),
+                        threshold_state(
+                            &self.data.memory_usage,
+                            (
+                                self.config.memory.warn_threshold,
+                                self.config.memory.alert_threshold,
+                            ),
+                        ),
                     ))
                     .push(Self::info_element(
                         theme,
                         StaticIcon::Mem,
                         "Swap memory Usage".to_string(),
                         format!("{}%", self.data.memory_swap_usage),
+                        threshold_state(
+                            &self.data.memory_swap_usage,
+                            (
+                                self.config.memory.warn_threshold,
+                                self.config.memory.alert_threshold,
+                            ),
+                        ),
                     ))
                     .push_maybe(self.data.temperature.map(|temp| {
                         Self::info_element(
@@ -286,6 +716,13 @@ impl SystemInfo {
                             StaticIcon::Temp,
                             "Temperature".to_string(),
                             format!("{temp}°C"),
+                            threshold_state(
+                                &temp,
+                                (
+                                    self.config.temperature.warn_threshold,
+                                    self.config.temperature.alert_threshold,
+                                ),
+                            ),
                         )
                     }))
                     .push(
@@ -293,12 +730,24 @@ impl SystemInfo {
                             self.data
                                 .disks
                                 .iter()
-                                .map(|(mount_point, usage)| {
+                                .map(|disk| {
                                     Self::info_element(
                                         theme,
                                         StaticIcon::Drive,
-                                        format!("Disk Usage {mount_point}"),
-                                        format!("{usage}%"),
+                                        format!("Disk Usage {}", disk.mount_point),
+                                        format!(
+                                            "{} / {} ({}%)",
+                                            format_bytes(disk.used_space),
+                                            format_bytes(disk.total_space),
+                                            disk.usage
+                                        ),
+                                        threshold_state(
+                                            &disk.usage,
+                                            (
+                                                self.config.disk.warn_threshold,
+                                                self.config.disk.alert_threshold,
+                                            ),
+                                        ),
                                     )
                                 })
                                 .collect::<Vec<Element<_>>>(),
@@ -312,32 +761,31 @@ impl SystemInfo {
                                 StaticIcon::IpAddress,
                                 "IP Address".to_string(),
                                 network.ip.clone(),
+                                IndicatorState::Normal,
                             ),
                             Self::info_element(
                                 theme,
                                 StaticIcon::DownloadSpeed,
                                 "Download Speed".to_string(),
-                                if network.download_speed > 1000 {
-                                    format!("{} MB/s", network.download_speed / 1000)
-                                } else {
-                                    format!("{} KB/s", network.download_speed)
-                                },
+                                format_rate(network.download_speed),
+                                IndicatorState::Normal,
                             ),
                             Self::info_element(
                                 theme,
                                 StaticIcon::UploadSpeed,
                                 "Upload Speed".to_string(),
-                                if network.upload_speed > 1000 {
-                                    format!("{} MB/s", network.upload_speed / 1000)
-                                } else {
-                                    format!("{} KB/s", network.upload_speed)
-                                },
+                                format_rate(network.upload_speed),
+                                IndicatorState::Normal,
                             ),
                         ])
                     }))
                     .spacing(theme.space.xxs)
                     .padding([0, theme.space.xs])
             )
+            .push_maybe(
+                self.graphs_element(theme)
+                    .map(|graphs| column!(horizontal_rule(1), graphs).spacing(theme.space.xs)),
+            )
             .spacing(theme.space.xs),
         )
         .max_width(MenuSize::Medium)
@@ -357,6 +805,43 @@ impl SystemInfo {
                 )),
                 None,
             )),
+            SystemInfoIndicator::LoadAverage => self
+                .data
+                .load_average
+                .format(&self.config.load_average.bar, &self.config.load_average)
+                .map(|(value, state)| {
+                    Self::indicator_element(theme, StaticIcon::Cpu, value, state, Some("load"))
+                }),
+            SystemInfoIndicator::GpuUsage => self.gpu.as_ref().map(|gpu| {
+                Self::indicator_info_element(
+                    theme,
+                    StaticIcon::Gpu,
+                    gpu.usage,
+                    "%",
+                    Some((
+                        self.config.gpu.warn_threshold,
+                        self.config.gpu.alert_threshold,
+                    )),
+                    None,
+                )
+            }),
+            SystemInfoIndicator::GpuTemperature => self
+                .gpu
+                .as_ref()
+                .and_then(|gpu| gpu.temperature)
+                .map(|temperature| {
+                    Self::indicator_info_element(
+                        theme,
+                        StaticIcon::Gpu,
+                        temperature,
+                        "°C",
+                        Some((
+                            self.config.temperature.warn_threshold,
+                            self.config.temperature.alert_threshold,
+                        )),
+                        None,
+                    )
+                }),
             SystemInfoIndicator::Memory => Some(Self::indicator_info_element(
                 theme,
                 StaticIcon::Mem,
@@ -392,25 +877,35 @@ impl SystemInfo {
                     None,
                 )
             }),
-            SystemInfoIndicator::Disk(config) => {
-                self.data.disks.iter().find_map(|(disk_mount, disk)| {
-                    if disk_mount == &config.path {
-                        Some(Self::indicator_info_element(
-                            theme,
-                            StaticIcon::Drive,
-                            *disk,
-                            "%",
-                            Some((
-                                self.config.disk.warn_threshold,
-                                self.config.disk.alert_threshold,
-                            )),
-                            Some(config.name.as_deref().unwrap_or(disk_mount)),
-                        ))
-                    } else {
-                        None
-                    }
-                })
-            }
+            SystemInfoIndicator::Disk(config) => self.data.disks.iter().find_map(|disk| {
+                if disk.mount_point == config.path {
+                    let state = threshold_state(
+                        &disk.usage,
+                        (
+                            self.config.disk.warn_threshold,
+                            self.config.disk.alert_threshold,
+                        ),
+                    );
+                    let value = match config.format {
+                        SystemInfoDiskFormat::Percentage => format!("{}%", disk.usage),
+                        SystemInfoDiskFormat::UsedTotal => format!(
+                            "{}/{}",
+                            format_bytes(disk.used_space),
+                            format_bytes(disk.total_space)
+                        ),
+                    };
+
+                    Some(Self::indicator_element(
+                        theme,
+                        StaticIcon::Drive,
+                        value,
+                        state,
+                        Some(config.name.as_deref().unwrap_or(&disk.mount_point)),
+                    ))
+                } else {
+                    None
+                }
+            }),
             SystemInfoIndicator::IpAddress => self.data.network.as_ref().map(|network| {
                 Self::indicator_info_element(
                     theme,
@@ -425,16 +920,8 @@ impl SystemInfo {
                 Self::indicator_info_element(
                     theme,
                     StaticIcon::DownloadSpeed,
-                    if network.download_speed > 1000 {
-                        network.download_speed / 1000
-                    } else {
-                        network.download_speed
-                    },
-                    if network.download_speed > 1000 {
-                        "MB/s"
-                    } else {
-                        "KB/s"
-                    },
+                    format_rate(network.download_speed),
+                    "",
                     None,
                     None,
                 )
@@ -443,20 +930,22 @@ impl SystemInfo {
                 Self::indicator_info_element(
                     theme,
                     StaticIcon::UploadSpeed,
-                    if network.upload_speed > 1000 {
-                        network.upload_speed / 1000
-                    } else {
-                        network.upload_speed
-                    },
-                    if network.upload_speed > 1000 {
-                        "MB/s"
-                    } else {
-                        "KB/s"
-                    },
+                    format_rate(network.upload_speed),
+                    "",
                     None,
                     None,
                 )
             }),
+            SystemInfoIndicator::NetworkThroughput => self.data.network.as_ref().map(|network| {
+                row!(
+                    icon(theme, StaticIcon::DownloadSpeed),
+                    text(format_rate(network.download_speed)).font(theme.monospace_font),
+                    icon(theme, StaticIcon::UploadSpeed),
+                    text(format_rate(network.upload_speed)).font(theme.monospace_font),
+                )
+                .spacing(theme.space.xxs)
+                .into()
+            }),
         });
 
         Row::with_children(indicators)
@@ -466,6 +955,56 @@ impl SystemInfo {
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        every(Duration::from_secs(5)).map(|_| Message::Update)
+        Subscription::batch(vec![
+            every(Duration::from_millis(self.config.interval_ms.max(100))).map(|_| Message::Update),
+            self.gpu_subscription(),
+        ])
+    }
+
+    /// Samples the GPU off the update loop. When there is no driver or card to
+    /// read, the stream ends and isn't retried until the source or the interval changes.
+    fn gpu_subscription(&self) -> Subscription<Message> {
+        let Some(source) = self.config.gpu.source else {
+            return Subscription::none();
+        };
+        let id = TypeId::of::<Self>();
+        let interval = Duration::from_millis(self.config.interval_ms.max(100));
+
+        Subscription::run_with_id(
+            (id, source, interval),
+            channel(1, async move |mut output| match source {
+                GpuSource::Nvidia => loop {
+                    match nvidia_gpu_data().await {
+                        Ok(data) => {
+                            let _ = output.try_send(Message::GpuUpdate(data));
+                        }
+                        Err(e) => {
+                            warn!("No NVIDIA GPU to sample, nvidia-smi failed: {e}");
+                            let _ = output.try_send(Message::GpuUpdate(None));
+                            break;
+                        }
+                    }
+
+                    sleep(interval).await;
+                },
+                GpuSource::Amd => {
+                    let Some(device) = tokio::task::spawn_blocking(amd_gpu_device)
+                        .await
+                        .ok()
+                        .flatten()
+                    else {
+                        warn!("No AMD GPU to sample, no card exposes gpu_busy_percent");
+                        let _ = output.try_send(Message::GpuUpdate(None));
+                        return;
+                    };
+
+                    loop {
+                        let _ = output.try_send(Message::GpuUpdate(amd_gpu_data(&device).await));
+
+                        sleep(interval).await;
+                    }
+                }
+            }),
+        )
     }
 }
@@ -3,20 +3,20 @@ use crate::{
     config::{TempoModuleConfig, WeatherLocation},
     menu::MenuSize,
     theme::AshellTheme,
+    utils::{every_aligned, format_has_seconds, launcher::command_output},
 };
-use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate, NaiveDateTime, Weekday};
+use chrono::{DateTime, Datelike, Days, Local, Locale, Months, NaiveDate, NaiveDateTime, Weekday};
 use iced::{
     Background, Border, Degrees, Element,
     Length::{self, FillPortion},
-    Rotation, Subscription, Theme,
+    Rotation, Subscription, Task, Theme,
     alignment::{Horizontal, Vertical},
     core::svg::Handle,
     futures::SinkExt,
     stream::channel,
-    time::every,
     widget::{
         Column, Row, Svg, button, column, container, row, scrollable, scrollable::Scrollbar, svg,
-        text,
+        text, tooltip,
     },
 };
 use itertools::izip;
@@ -30,28 +30,36 @@ pub enum Message {
     ChangeSelectDate(Option<NaiveDate>),
     UpdateWeather(Box<WeatherData>),
     UpdateLocation(Location),
+    MenuOpened,
+    EventsLoaded(NaiveDate, Vec<String>),
+    ConfigReloaded(TempoModuleConfig),
 }
 
 pub enum Action {
     None,
+    Command(Task<Message>),
 }
 
 pub struct Tempo {
     config: TempoModuleConfig,
+    locale: Locale,
     date: DateTime<Local>,
     selected_date: Option<NaiveDate>,
     weather_data: Option<WeatherData>,
     location: Option<Location>,
+    events: Vec<String>,
 }
 
 impl Tempo {
     pub fn new(config: TempoModuleConfig) -> Self {
         Self {
+            locale: resolve_locale(config.locale.as_deref()),
             config,
             date: Local::now(),
             selected_date: None,
             weather_data: None,
             location: None,
+            events: Vec::new(),
         }
     }
 
@@ -65,7 +73,7 @@ impl Tempo {
             Message::ChangeSelectDate(selected_date) => {
                 self.selected_date = selected_date;
 
-                Action::None
+                self.load_events()
             }
             Message::UpdateWeather(data) => {
                 self.weather_data = Some(*data);
@@ -75,20 +83,86 @@ impl Tempo {
             Message::UpdateLocation(location) => {
                 self.location = Some(location);
 
+                Action::None
+            }
+            Message::MenuOpened => self.load_events(),
+            Message::EventsLoaded(date, events) => {
+                // Ignore results for a day that is no longer selected
+                if date == self.events_date() {
+                    self.events = events;
+                }
+
+                Action::None
+            }
+            Message::ConfigReloaded(config) => {
+                // Weather data is only valid for the location it was fetched for
+                if config.weather_location != self.config.weather_location {
+                    self.weather_data = None;
+                    self.location = None;
+                }
+                if config.events_cmd != self.config.events_cmd {
+                    self.events.clear();
+                }
+                self.locale = resolve_locale(config.locale.as_deref());
+                self.config = config;
+
                 Action::None
             }
         }
     }
 
+    fn events_date(&self) -> NaiveDate {
+        self.selected_date.unwrap_or(self.date.date_naive())
+    }
+
+    fn load_events(&mut self) -> Action {
+        let Some(events_cmd) = &self.config.events_cmd else {
+            return Action::None;
+        };
+
+        let date = self.events_date();
+        let command = events_cmd.replace("{date}", &date.format("%Y-%m-%d").to_string());
+        self.events.clear();
+
+        Action::Command(Task::perform(
+            async move {
+                command_output(&command)
+                    .await
+                    .map(|output| {
+                        output
+                            .lines()
+                            .filter(|line| !line.trim().is_empty())
+                            .map(str::to_owned)
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            },
+            move |events| Message::EventsLoaded(date, events),
+        ))
+    }
+
+    fn format_date(&self, format: &str) -> String {
+        self.date.format_localized(format, self.locale).to_string()
+    }
+
     pub fn view(&'_ self, theme: &AshellTheme) -> Element<'_, Message> {
-        Row::new()
+        let content = Row::new()
             .push_maybe(self.weather_indicator(theme))
-            .push(text(
-                self.date.format(&self.config.clock_format).to_string(),
-            ))
+            .push(text(self.format_date(&self.config.clock_format)))
             .align_y(Vertical::Center)
-            .spacing(theme.space.sm)
-            .into()
+            .spacing(theme.space.sm);
+
+        match &self.config.tooltip_format {
+            Some(tooltip_format) => tooltip(
+                content,
+                container(text(self.format_date(tooltip_format)).size(theme.font_size.sm))
+                    .padding([theme.space.xxs, theme.space.xs])
+                    .style(container::rounded_box),
+                theme.bar_position.tooltip_position(),
+            )
+            .into(),
+            None => content.into(),
+        }
     }
 
     pub fn weather_indicator(&'_ self, theme: &AshellTheme) -> Option<Element<'_, Message>> {
@@ -144,17 +218,21 @@ impl Tempo {
         let calendar = Column::new()
             .push(
                 row!(
-                    button(icon(StaticIcon::LeftChevron))
+                    button(icon(theme, StaticIcon::LeftChevron))
                         .on_press(Message::ChangeSelectDate(
                             selected_date.checked_sub_months(Months::new(1)),
                         ))
                         .padding([theme.space.xs, theme.space.md])
                         .style(theme.settings_button_style()),
-                    text(selected_date.format("%B").to_string())
-                        .size(theme.font_size.md)
-                        .width(Length::Fill)
-                        .align_x(Horizontal::Center),
-                    button(icon(StaticIcon::RightChevron))
+                    text(
+                        selected_date
+                            .format_localized("%B", self.locale)
+                            .to_string()
+                    )
+                    .size(theme.font_size.md)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Center),
+                    button(icon(theme, StaticIcon::RightChevron))
                         .on_press(Message::ChangeSelectDate(
                             selected_date.checked_add_months(Months::new(1))
                         ))
@@ -240,8 +318,8 @@ impl Tempo {
         column!(
             button(
                 column!(
-                    text(self.date.format("%A").to_string()).size(theme.font_size.sm),
-                    text(self.date.format("%d %B %Y").to_string()).size(theme.font_size.md),
+                    text(self.format_date("%A")).size(theme.font_size.sm),
+                    text(self.format_date("%d %B %Y")).size(theme.font_size.md),
                 )
                 .spacing(theme.space.xs)
             )
@@ -254,10 +332,62 @@ impl Tempo {
             .style(theme.outline_button_style()),
             calendar
         )
+        .push_maybe(self.events(theme))
+        .push_maybe(self.timezones(theme))
         .spacing(theme.space.lg)
         .into()
     }
 
+    fn timezones<'a>(&'a self, theme: &'a AshellTheme) -> Option<Element<'a, Message>> {
+        (!self.config.timezones.is_empty()).then(|| {
+            Column::with_children(
+                self.config
+                    .timezones
+                    .iter()
+                    .map(|tz| {
+                        row!(
+                            text(tz.name()).size(theme.font_size.sm).width(Length::Fill),
+                            text(
+                                self.date
+                                    .with_timezone(tz)
+                                    .format_localized(&self.config.clock_format, self.locale)
+                                    .to_string()
+                            )
+                            .size(theme.font_size.sm)
+                        )
+                        .align_y(Vertical::Center)
+                        .spacing(theme.space.md)
+                        .into()
+                    })
+                    .collect::<Vec<Element<'a, Message>>>(),
+            )
+            .spacing(theme.space.xxs)
+            .width(Length::Fixed(225.))
+            .into()
+        })
+    }
+
+    fn events<'a>(&'a self, theme: &'a AshellTheme) -> Option<Element<'a, Message>> {
+        self.config.events_cmd.as_ref().map(|_| {
+            if self.events.is_empty() {
+                text("No events")
+                    .size(theme.font_size.sm)
+                    .color(theme.iced_theme.palette().text.scale_alpha(0.5))
+                    .into()
+            } else {
+                Column::with_children(
+                    self.events
+                        .iter()
+                        .map(|event| text(event).size(theme.font_size.sm).into())
+                        .collect::<Vec<Element<'a, Message>>>(),
+                )
+                .spacing(theme.space.xxs)
+                .width(Length::Fixed(225.))
+                .into()
+            }
+        })
+    }
+
     fn weather<'a>(&'a self, theme: &'a AshellTheme) -> Option<Element<'a, Message>> {
         self.weather_data
             .as_ref()
@@ -517,30 +647,21 @@ impl Tempo {
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        let second_specifiers = [
-            "%S",  // Seconds (00-60)
-            "%T",  // Hour:Minute:Second
-            "%X",  // Locale time representation with seconds
-            "%r",  // 12-hour clock time with seconds
-            "%:z", // UTC offset with seconds
-            "%s",  // Unix timestamp (seconds since epoch)
-        ];
-        let interval = if second_specifiers
-            .iter()
-            .any(|&spec| self.config.clock_format.contains(spec))
+        let interval = if format_has_seconds(&self.config.clock_format)
+            || self
+                .config
+                .tooltip_format
+                .as_deref()
+                .is_some_and(format_has_seconds)
         {
             Duration::from_secs(1)
         } else {
-            Duration::from_secs(5)
+            Duration::from_secs(60)
         };
 
         let weather_sub = self.config.weather_location.clone().map(|location| {
             Subscription::run_with_id(
-                (
-                    TypeId::of::<Self>(),
-                    format!("{:?}", self.config.weather_location),
-                    "weather",
-                ),
+                (TypeId::of::<Self>(), location.clone(), "weather"),
                 channel(100, async move |mut output| {
                     let mut failed_attempt = 0;
 
@@ -599,9 +720,12 @@ impl Tempo {
         });
 
         if let Some(weather_sub) = weather_sub {
-            Subscription::batch(vec![every(interval).map(|_| Message::Update), weather_sub])
+            Subscription::batch(vec![
+                every_aligned(interval).map(|_| Message::Update),
+                weather_sub,
+            ])
         } else {
-            every(interval).map(|_| Message::Update)
+            every_aligned(interval).map(|_| Message::Update)
         }
     }
 }
@@ -937,3 +1061,28 @@ pub const fn weather_description(code: u32) -> &'static str {
         _ => "Unknown weather condition",
     }
 }
+
+// Use the configured locale, falling back to the time locale of the environment
+fn resolve_locale(locale: Option<&str>) -> Locale {
+    locale
+        .map(str::to_owned)
+        .or_else(|| {
+            ["LC_ALL", "LC_TIME", "LANG"]
+                .into_iter()
+                .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+        })
+        .and_then(|name| {
+            // Drop the encoding and modifier, e.g. de_DE.UTF-8@euro
+            let name = name.split(['.', '@']).next().unwrap_or_default();
+
+            // The C locale is POSIX under another name, not an unknown one
+            if matches!(name, "C" | "POSIX") {
+                return Some(Locale::POSIX);
+            }
+
+            Locale::try_from(name)
+                .inspect_err(|_| warn!("Unknown locale {name}, using POSIX"))
+                .ok()
+        })
+        .unwrap_or(Locale::POSIX)
+}
@@ -1,10 +1,11 @@
 use crate::{
     components::icons::{StaticIcon, icon},
+    config::{TrayClickAction, TrayModuleConfig},
     menu::MenuSize,
     services::{
         ReadOnlyService, Service, ServiceEvent,
         tray::{
-            TrayCommand, TrayEvent, TrayIcon, TrayService,
+            ScrollOrientation, TrayCommand, TrayEvent, TrayIcon, TrayService,
             dbus::{Layout, LayoutProps},
         },
     },
@@ -13,7 +14,11 @@ use crate::{
 };
 use iced::{
     Alignment, Element, Length, Subscription, Task,
-    widget::{Column, Image, Row, Svg, button, container, horizontal_rule, row, text, toggler},
+    mouse::ScrollDelta,
+    widget::{
+        Column, Image, Row, Svg, button, container, horizontal_rule, mouse_area, row, text,
+        toggler, tooltip,
+    },
     window::Id,
 };
 use log::debug;
@@ -21,10 +26,12 @@ use log::debug;
 #[derive(Debug, Clone)]
 pub enum Message {
     Event(Box<ServiceEvent<TrayService>>),
-    ToggleMenu(String, Id, ButtonUIRef),
+    Click(String, TrayClickAction, Id, ButtonUIRef),
     ToggleSubmenu(i32),
     MenuSelected(String, i32),
     MenuOpened(String),
+    Scroll(String, ScrollDelta),
+    ConfigReloaded(TrayModuleConfig),
 }
 
 pub enum Action {
@@ -32,15 +39,56 @@ pub enum Action {
     ToggleMenu(String, Id, ButtonUIRef),
     TrayMenuCommand(Task<Message>),
     CloseTrayMenu(String),
+    Command(Task<Message>),
+}
+
+fn scroll_delta(delta: ScrollDelta) -> Option<(i32, ScrollOrientation)> {
+    let (x, y) = match delta {
+        ScrollDelta::Lines { x, y } => (x, y),
+        // Pixel deltas have the opposite sign of line deltas (see workspaces),
+        // scale them roughly to the size of a wheel step
+        ScrollDelta::Pixels { x, y } => (-x / 20., -y / 20.),
+    };
+    let (value, orientation) = if y != 0. {
+        (y, ScrollOrientation::Vertical)
+    } else {
+        (x, ScrollOrientation::Horizontal)
+    };
+    let delta = value.round() as i32;
+
+    (delta != 0).then_some((delta, orientation))
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct TrayModule {
+    config: TrayModuleConfig,
     service: Option<TrayService>,
     submenus: Vec<i32>,
+    // Where the menu opens if the item doesn't implement Activate
+    pending_activation: Option<(String, Id, ButtonUIRef)>,
 }
 
 impl TrayModule {
+    pub fn new(config: TrayModuleConfig) -> Self {
+        Self {
+            config,
+            service: None,
+            submenus: Vec::new(),
+            pending_activation: None,
+        }
+    }
+
+    fn command(&mut self, command: TrayCommand) -> Action {
+        match self.service.as_mut() {
+            Some(service) => Action::Command(
+                service
+                    .command(command)
+                    .map(|event| Message::Event(Box::new(event))),
+            ),
+            _ => Action::None,
+        }
+    }
+
     pub fn update(&mut self, message: Message) -> Action {
         match message {
             Message::Event(event) => match *event {
@@ -49,10 +97,29 @@ impl TrayModule {
                     Action::None
                 }
                 ServiceEvent::Update(data) => {
-                    let action = if let TrayEvent::Unregistered(name) = &data {
-                        Action::CloseTrayMenu(name.clone())
-                    } else {
-                        Action::None
+                    let action = match &data {
+                        TrayEvent::Unregistered(name) => {
+                            self.pending_activation
+                                .take_if(|(pending, ..)| pending == name);
+                            Action::CloseTrayMenu(name.clone())
+                        }
+                        TrayEvent::Activated(name) => {
+                            self.pending_activation
+                                .take_if(|(pending, ..)| pending == name);
+                            Action::None
+                        }
+                        TrayEvent::ActivateFailed(name) => {
+                            match self
+                                .pending_activation
+                                .take_if(|(pending, ..)| pending == name)
+                            {
+                                Some((pending, id, button_ui_ref)) => {
+                                    Action::ToggleMenu(pending, id, button_ui_ref)
+                                }
+                                None => Action::None,
+                            }
+                        }
+                        _ => Action::None,
                     };
 
                     if let Some(service) = self.service.as_mut() {
@@ -63,8 +130,35 @@ impl TrayModule {
                 }
                 ServiceEvent::Error(_) => Action::None,
             },
-            Message::ToggleMenu(menu_type, id, button_ui_ref) => {
-                Action::ToggleMenu(menu_type, id, button_ui_ref)
+            Message::Click(name, click_action, id, button_ui_ref) => {
+                let is_menu = self
+                    .service
+                    .as_ref()
+                    .and_then(|service| service.iter().find(|item| item.name == name))
+                    .is_some_and(|item| item.is_menu);
+
+                let (x, y) = (
+                    button_ui_ref.position.x as i32,
+                    button_ui_ref.position.y as i32,
+                );
+
+                match click_action {
+                    TrayClickAction::Activate if !is_menu => {
+                        self.pending_activation = Some((name.clone(), id, button_ui_ref));
+                        self.command(TrayCommand::Activate(name, x, y))
+                    }
+                    TrayClickAction::Activate | TrayClickAction::Menu => {
+                        Action::ToggleMenu(name, id, button_ui_ref)
+                    }
+                    TrayClickAction::SecondaryActivate => {
+                        self.command(TrayCommand::SecondaryActivate(name, x, y))
+                    }
+                    TrayClickAction::None => Action::None,
+                }
+            }
+            Message::ConfigReloaded(config) => {
+                self.config = config;
+                Action::None
             }
             Message::ToggleSubmenu(index) => {
                 if self.submenus.contains(&index) {
@@ -86,6 +180,12 @@ impl TrayModule {
                 }
                 _ => Action::None,
             },
+            Message::Scroll(name, delta) => match scroll_delta(delta) {
+                Some((delta, orientation)) => {
+                    self.command(TrayCommand::Scroll(name, delta, orientation))
+                }
+                _ => Action::None,
+            },
             Message::MenuOpened(name) => {
                 if let Some(_tray) = self
                     .service
@@ -132,11 +232,14 @@ impl TrayModule {
                     .push(
                         button(row!(
                             text(label.replace("_", "").to_owned()).width(Length::Fill),
-                            icon(if is_open {
-                                StaticIcon::MenuOpen
-                            } else {
-                                StaticIcon::MenuClosed
-                            })
+                            icon(
+                                theme,
+                                if is_open {
+                                    StaticIcon::MenuOpen
+                                } else {
+                                    StaticIcon::MenuClosed
+                                }
+                            )
                         ))
                         .style(theme.ghost_button_style())
                         .padding(theme.space.xs)
@@ -178,36 +281,74 @@ impl TrayModule {
             .as_ref()
             .filter(|s| !s.data.is_empty())
             .map(|service| {
-                Into::<Element<_>>::into(
-                    Row::with_children(
-                        service
-                            .data
-                            .iter()
-                            .map(|item| {
-                                position_button(match &item.icon {
-                                    Some(TrayIcon::Image(handle)) => Into::<Element<_>>::into(
-                                        Image::new(handle.clone())
-                                            .height(Length::Fixed(theme.font_size.md as f32 - 2.0)),
-                                    ),
-                                    Some(TrayIcon::Svg(handle)) => Into::<Element<_>>::into(
-                                        Svg::new(handle.clone())
-                                            .height(Length::Fixed(theme.font_size.md as f32 + 2.))
-                                            .width(Length::Fixed(theme.font_size.md as f32 + 2.))
-                                            .content_fit(iced::ContentFit::Cover),
-                                    ),
-                                    _ => icon(StaticIcon::Point).into(),
-                                })
-                                .on_press_with_position(move |button_ui_ref| {
-                                    Message::ToggleMenu(item.name.to_owned(), id, button_ui_ref)
-                                })
-                                .padding(theme.space.xxs)
-                                .style(theme.ghost_button_style())
-                                .into()
-                            })
-                            .collect::<Vec<_>>(),
-                    )
-                    .align_y(Alignment::Center),
-                )
+                let items = service
+                    .data
+                    .iter()
+                    .map(|item| {
+                        let button = position_button(match &item.icon {
+                            Some(TrayIcon::Image(handle)) => Into::<Element<_>>::into(
+                                Image::new(handle.clone())
+                                    .height(Length::Fixed(theme.font_size.md as f32 - 2.0)),
+                            ),
+                            Some(TrayIcon::Svg(handle)) => Into::<Element<_>>::into(
+                                Svg::new(handle.clone())
+                                    .height(Length::Fixed(theme.font_size.md as f32 + 2.))
+                                    .width(Length::Fixed(theme.font_size.md as f32 + 2.))
+                                    .content_fit(iced::ContentFit::Cover),
+                            ),
+                            _ => icon(theme, StaticIcon::Point).into(),
+                        })
+                        .on_press_with_position(move |button_ui_ref| {
+                            Message::Click(
+                                item.name.to_owned(),
+                                self.config.left_click,
+                                id,
+                                button_ui_ref,
+                            )
+                        })
+                        .on_right_press_with_position(move |button_ui_ref| {
+                            Message::Click(
+                                item.name.to_owned(),
+                                self.config.right_click,
+                                id,
+                                button_ui_ref,
+                            )
+                        })
+                        .on_middle_press_with_position(move |button_ui_ref| {
+                            Message::Click(
+                                item.name.to_owned(),
+                                self.config.middle_click,
+                                id,
+                                button_ui_ref,
+                            )
+                        })
+                        .padding(theme.space.xxs)
+                        .style(theme.ghost_button_style());
+
+                        let content = mouse_area(button)
+                            .on_scroll(move |delta| Message::Scroll(item.name.to_owned(), delta));
+
+                        match &item.tooltip {
+                            Some(tip) => tooltip(
+                                content,
+                                container(text(tip.clone()).size(theme.font_size.sm))
+                                    .padding([theme.space.xxs, theme.space.xs])
+                                    .style(container::rounded_box),
+                                theme.bar_position.tooltip_position(),
+                            )
+                            .into(),
+                            None => content.into(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                if theme.bar_position.is_vertical() {
+                    Column::with_children(items)
+                        .align_x(Alignment::Center)
+                        .into()
+                } else {
+                    Row::with_children(items).align_y(Alignment::Center).into()
+                }
             })
     }
 
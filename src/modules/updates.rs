@@ -3,7 +3,7 @@ use crate::{
     config::UpdatesModuleConfig,
     menu::MenuSize,
     theme::AshellTheme,
-    utils::truncate_chars,
+    utils::{launcher::command_output, truncate_chars},
 };
 use iced::{
     Alignment, Element, Length, Subscription, Task,
@@ -12,9 +12,12 @@ use iced::{
     widget::{Column, button, column, container, horizontal_rule, row, scrollable, text},
     window::Id,
 };
-use log::error;
 use serde::Deserialize;
-use std::{any::TypeId, convert, process::Stdio, time::Duration};
+use std::{
+    any::TypeId,
+    convert,
+    time::{Duration, Instant},
+};
 use tokio::{process, time::sleep};
 
 #[derive(Deserialize, Debug, Clone)]
@@ -24,43 +27,34 @@ pub struct Update {
     pub to: String,
 }
 
-async fn check_update_now(check_cmd: &str) -> Vec<Update> {
-    let check_update_cmd = process::Command::new("bash")
-        .arg("-c")
-        .arg(check_cmd)
-        .stdout(Stdio::piped())
-        .output()
-        .await;
-
-    match check_update_cmd {
-        Ok(check_update_cmd) => {
-            let cmd_output = String::from_utf8_lossy(&check_update_cmd.stdout);
-            let mut new_updates: Vec<Update> = Vec::new();
-            for update in cmd_output.split('\n') {
-                if update.is_empty() {
-                    continue;
-                }
-
-                let data = update.split(' ').collect::<Vec<&str>>();
-                if data.len() < 4 {
-                    continue;
-                }
-                new_updates.push(Update {
-                    package: data[0].to_string(),
-                    from: data[1].to_string(),
-                    to: data[3].to_string(),
-                });
-            }
-
-            new_updates
-        }
-        Err(e) => {
-            error!("Error: {e:?}");
-            vec![]
-        }
+fn parse_update(line: &str) -> Update {
+    match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [package, from, "->", to] => Update {
+            package: package.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+        },
+        _ => Update {
+            package: line.trim().to_string(),
+            from: String::new(),
+            to: String::new(),
+        },
     }
 }
 
+async fn check_update_now(check_cmd: &str) -> Vec<Update> {
+    command_output(check_cmd)
+        .await
+        .map(|output| {
+            output
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(parse_update)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 async fn update(update_cmd: &str) {
     let _ = process::Command::new("bash")
         .arg("-c")
@@ -77,6 +71,7 @@ pub enum Message {
     ToggleUpdatesList,
     CheckNow,
     Update(Id),
+    ConfigReloaded(UpdatesModuleConfig),
 }
 
 pub enum Action {
@@ -98,6 +93,7 @@ pub struct Updates {
     state: State,
     updates: Vec<Update>,
     is_updates_list_open: bool,
+    last_check: Option<Instant>,
 }
 
 impl Updates {
@@ -107,14 +103,23 @@ impl Updates {
             state: State::default(),
             updates: Vec::new(),
             is_updates_list_open: false,
+            last_check: None,
         }
     }
 
+    fn can_check_now(&self) -> bool {
+        self.state != State::Checking
+            && self.last_check.is_none_or(|last_check| {
+                last_check.elapsed() >= Duration::from_secs(self.config.check_cooldown)
+            })
+    }
+
     pub fn update(&mut self, message: Message) -> Action {
         match message {
             Message::UpdatesCheckCompleted(updates) => {
                 self.updates = updates;
                 self.state = State::Ready;
+                self.last_check = Some(Instant::now());
 
                 Action::None
             }
@@ -138,6 +143,10 @@ impl Updates {
                 Action::None
             }
             Message::CheckNow => {
+                if !self.can_check_now() {
+                    return Action::None;
+                }
+
                 self.state = State::Checking;
                 let check_command = self.config.check_cmd.clone();
 
@@ -159,15 +168,23 @@ impl Updates {
                     ),
                 )
             }
+            Message::ConfigReloaded(config) => {
+                self.config = config;
+
+                Action::None
+            }
         }
     }
 
     pub fn view(&'_ self, theme: &AshellTheme) -> Element<'_, Message> {
-        let mut content = row!(container(icon(match self.state {
-            State::Checking => StaticIcon::Refresh,
-            State::Ready if self.updates.is_empty() => StaticIcon::NoUpdatesAvailable,
-            _ => StaticIcon::UpdatesAvailable,
-        })))
+        let mut content = row!(container(icon(
+            theme,
+            match self.state {
+                State::Checking => StaticIcon::Refresh,
+                State::Ready if self.updates.is_empty() => StaticIcon::NoUpdatesAvailable,
+                _ => StaticIcon::UpdatesAvailable,
+            }
+        )))
         .align_y(Alignment::Center)
         .spacing(theme.space.xxs);
 
@@ -189,11 +206,14 @@ impl Updates {
                     button(row!(
                         text(format!("{} Updates available", self.updates.len()))
                             .width(Length::Fill),
-                        icon(if self.is_updates_list_open {
-                            StaticIcon::MenuClosed
-                        } else {
-                            StaticIcon::MenuOpen
-                        })
+                        icon(
+                            theme,
+                            if self.is_updates_list_open {
+                                StaticIcon::MenuClosed
+                            } else {
+                                StaticIcon::MenuOpen
+                            }
+                        )
                     ))
                     .style(theme.ghost_button_style())
                     .padding(theme.space.xs)
@@ -209,20 +229,26 @@ impl Updates {
                                 self.updates
                                     .iter()
                                     .map(|update| {
-                                        column!(
-                                            text(update.package.clone())
-                                                .size(theme.font_size.xs)
-                                                .width(Length::Fill),
-                                            text(format!(
-                                                "{} -> {}",
-                                                truncate_chars(&update.from, 18),
-                                                truncate_chars(&update.to, 18),
-                                            ))
-                                            .width(Length::Fill)
-                                            .align_x(Horizontal::Right)
-                                            .size(theme.font_size.xs)
-                                        )
-                                        .into()
+                                        Column::new()
+                                            .push(
+                                                text(update.package.clone())
+                                                    .size(theme.font_size.xs)
+                                                    .width(Length::Fill),
+                                            )
+                                            .push_maybe(
+                                                (!update.from.is_empty() || !update.to.is_empty())
+                                                    .then(|| {
+                                                        text(format!(
+                                                            "{} -> {}",
+                                                            truncate_chars(&update.from, 18),
+                                                            truncate_chars(&update.to, 18),
+                                                        ))
+                                                        .width(Length::Fill)
+                                                        .align_x(Horizontal::Right)
+                                                        .size(theme.font_size.xs)
+                                                    }),
+                                            )
+                                            .into()
                                     })
                                     .collect::<Vec<Element<'_, _, _>>>(),
                             )
@@ -250,14 +276,14 @@ impl Updates {
                     let mut content = row!(text("Check now").width(Length::Fill),);
 
                     if self.state == State::Checking {
-                        content = content.push(icon(StaticIcon::Refresh));
+                        content = content.push(icon(theme, StaticIcon::Refresh));
                     }
 
                     content
                 })
                 .style(theme.ghost_button_style())
                 .padding(theme.space.xs)
-                .on_press(Message::CheckNow)
+                .on_press_maybe(self.can_check_now().then_some(Message::CheckNow))
                 .width(Length::Fill)
             ),
         )
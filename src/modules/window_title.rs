@@ -2,35 +2,67 @@ use crate::{
     config::{WindowTitleConfig, WindowTitleMode},
     services::{ReadOnlyService, ServiceEvent, compositor::CompositorService},
     theme::AshellTheme,
-    utils::truncate_text,
+    utils::{launcher::execute_command, truncate_text},
 };
 use iced::{
     Element, Subscription,
-    widget::{container, text},
+    widget::{container, mouse_area, text},
 };
+use log::warn;
+use regex::Regex;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     ServiceEvent(Box<ServiceEvent<CompositorService>>),
     ConfigReloaded(WindowTitleConfig),
+    Click,
+    CopyTitle,
+}
+
+pub enum Action {
+    None,
+    CopyToClipboard(String),
 }
 
 pub struct WindowTitle {
     config: WindowTitleConfig,
+    rewrite_rules: Vec<(Regex, String)>,
     service: Option<CompositorService>,
     value: Option<String>,
 }
 
+fn compile_rewrite_rules(config: &WindowTitleConfig) -> Vec<(Regex, String)> {
+    config
+        .rewrite_rules
+        .iter()
+        .filter_map(|rule| match Regex::new(&rule.pattern) {
+            Ok(regex) => Some((regex, rule.replace.clone())),
+            Err(e) => {
+                warn!(
+                    "Skipping invalid window title rewrite rule {:?}: {e}",
+                    rule.pattern
+                );
+                None
+            }
+        })
+        .collect()
+}
+
 impl WindowTitle {
     pub fn new(config: WindowTitleConfig) -> Self {
         Self {
+            rewrite_rules: compile_rewrite_rules(&config),
             config,
             service: None,
             value: None,
         }
     }
 
-    pub fn update(&mut self, message: Message) {
+    pub fn has_click_cmd(&self) -> bool {
+        self.config.click_cmd.is_some()
+    }
+
+    pub fn update(&mut self, message: Message) -> Action {
         match message {
             Message::ServiceEvent(event) => match *event {
                 ServiceEvent::Init(service) => {
@@ -46,10 +78,28 @@ impl WindowTitle {
                 _ => {}
             },
             Message::ConfigReloaded(cfg) => {
+                self.rewrite_rules = compile_rewrite_rules(&cfg);
                 self.config = cfg;
                 self.recalculate_value();
             }
+            Message::Click => {
+                if let Some(cmd) = &self.config.click_cmd {
+                    execute_command(cmd.clone());
+                }
+            }
+            Message::CopyTitle => {
+                // The full title, the displayed one may be rewritten or truncated
+                if let Some(window) = self
+                    .service
+                    .as_ref()
+                    .and_then(|service| service.active_window.as_ref())
+                {
+                    return Action::CopyToClipboard(window.title().to_string());
+                }
+            }
         }
+
+        Action::None
     }
 
     fn recalculate_value(&mut self) {
@@ -74,27 +124,43 @@ impl WindowTitle {
                     },
                 };
 
+                let title = self.rewrite_rules.iter().fold(
+                    raw_title.to_string(),
+                    |title, (regex, replace)| {
+                        regex.replace_all(&title, replace.as_str()).into_owned()
+                    },
+                );
+
                 if self.config.truncate_title_after_length > 0 {
-                    truncate_text(raw_title, self.config.truncate_title_after_length)
+                    truncate_text(&title, self.config.truncate_title_after_length)
                 } else {
-                    raw_title.to_string()
+                    title
                 }
             });
         }
     }
 
     pub fn get_value(&self) -> Option<String> {
-        self.value.clone()
+        self.value
+            .clone()
+            .filter(|title| !self.config.hide_when_empty || !title.trim().is_empty())
     }
 
     pub fn view(&'_ self, theme: &AshellTheme, title: String) -> Element<'_, Message> {
-        container(
+        let content = container(
             text(title)
                 .size(theme.font_size.sm)
                 .wrapping(text::Wrapping::None),
         )
-        .clip(true)
-        .into()
+        .clip(true);
+
+        if self.config.middle_click_copy {
+            mouse_area(content)
+                .on_middle_press(Message::CopyTitle)
+                .into()
+        } else {
+            content.into()
+        }
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
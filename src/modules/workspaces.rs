@@ -1,6 +1,7 @@
 use crate::{
     config::{WorkspaceVisibilityMode, WorkspacesModuleConfig},
     outputs::Outputs,
+    popup::ease_out_cubic,
     services::{
         ReadOnlyService, Service, ServiceEvent,
         compositor::{CompositorCommand, CompositorService, CompositorState},
@@ -8,12 +9,15 @@ use crate::{
     theme::AshellTheme,
 };
 use iced::{
-    Element, Length, Subscription, alignment,
-    widget::{MouseArea, Row, button, container, text},
+    Alignment, Element, Length, Subscription, alignment,
+    widget::{Column, MouseArea, Row, button, container, text},
     window::Id,
 };
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum Displayed {
@@ -47,6 +51,17 @@ pub enum Message {
     Scroll(i32),
     ConfigReloaded(WorkspacesModuleConfig),
     ScrollAccumulator(f32),
+    AnimationTick,
+}
+
+/// Workspace switch in progress: the newly active workspace grows
+/// while the previously active one shrinks.
+#[derive(Debug, Clone)]
+struct ActiveTransition {
+    from: Option<i32>,
+    to: i32,
+    to_start: Displayed,
+    started: Instant,
 }
 
 pub struct Workspaces {
@@ -54,6 +69,7 @@ pub struct Workspaces {
     service: Option<CompositorService>,
     ui_workspaces: Vec<UiWorkspace>,
     scroll_accumulator: f32,
+    transition: Option<ActiveTransition>,
 }
 
 fn calculate_ui_workspaces(
@@ -247,6 +263,7 @@ impl Workspaces {
             service: None,
             ui_workspaces: Vec::new(),
             scroll_accumulator: 0.,
+            transition: None,
         }
     }
 
@@ -311,6 +328,12 @@ impl Workspaces {
             Message::Scroll(direction) => {
                 self.scroll_accumulator = 0.;
 
+                let direction = if self.config.invert_scroll {
+                    -direction
+                } else {
+                    direction
+                };
+
                 /* TODO: should we use the native service implementation instead?
                 if let Some(service) = &mut self.service {
                     return service
@@ -356,6 +379,15 @@ impl Workspaces {
                     self.scroll_accumulator += value;
                 }
 
+                iced::Task::none()
+            }
+            Message::AnimationTick => {
+                if self.transition.as_ref().is_some_and(|transition| {
+                    transition.started.elapsed() >= self.animation_duration()
+                }) {
+                    self.transition = None;
+                }
+
                 iced::Task::none()
             }
         }
@@ -363,10 +395,67 @@ impl Workspaces {
 
     fn recalculate_ui_workspaces(&mut self) {
         if let Some(service) = &self.service {
-            self.ui_workspaces = calculate_ui_workspaces(&self.config, service);
+            let ui_workspaces = calculate_ui_workspaces(&self.config, service);
+
+            let active = |workspaces: &[UiWorkspace]| {
+                workspaces
+                    .iter()
+                    .find(|w| w.displayed == Displayed::Active)
+                    .map(|w| w.id)
+            };
+            let previous = active(&self.ui_workspaces);
+            let current = active(&ui_workspaces);
+
+            // Nothing to animate from on the first update
+            if let Some(to) = current
+                && previous != current
+                && !self.ui_workspaces.is_empty()
+                && self.config.animation_ms > 0
+            {
+                self.transition = Some(ActiveTransition {
+                    from: previous,
+                    to,
+                    to_start: self
+                        .ui_workspaces
+                        .iter()
+                        .find(|w| w.id == to)
+                        .map_or(Displayed::Hidden, |w| w.displayed.clone()),
+                    started: Instant::now(),
+                });
+            }
+
+            self.ui_workspaces = ui_workspaces;
         }
     }
 
+    fn animation_duration(&self) -> Duration {
+        Duration::from_millis(self.config.animation_ms)
+    }
+
+    fn transition_progress(&self) -> f32 {
+        let duration = self.animation_duration();
+
+        if duration.is_zero() {
+            return 1.0;
+        }
+
+        self.transition.as_ref().map_or(1.0, |transition| {
+            ease_out_cubic(
+                (transition.started.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0),
+            )
+        })
+    }
+
+    /// Label configured in `label_map` for the workspace id or name,
+    /// falling back to the workspace name.
+    fn label<'a>(&'a self, w: &'a UiWorkspace) -> &'a str {
+        self.config
+            .label_map
+            .get(&w.id.to_string())
+            .or_else(|| self.config.label_map.get(&w.name))
+            .map_or(w.name.as_str(), String::as_str)
+    }
+
     pub fn view<'a>(
         &'a self,
         id: Id,
@@ -374,108 +463,157 @@ impl Workspaces {
         outputs: &Outputs,
     ) -> Element<'a, Message> {
         let monitor_name = outputs.get_monitor_name(id);
+        let vertical = theme.bar_position.is_vertical();
+        let progress = self.transition_progress();
+
+        let pill_size = |displayed: &Displayed| {
+            f32::from(match displayed {
+                Displayed::Active => theme.space.xl,
+                Displayed::Visible => theme.space.lg,
+                Displayed::Hidden => theme.space.md,
+            })
+        };
+
+        let workspaces = self
+            .ui_workspaces
+            .iter()
+            .filter_map(|w| {
+                let show = match self.config.visibility_mode {
+                    WorkspaceVisibilityMode::All => true,
+                    WorkspaceVisibilityMode::MonitorSpecific => {
+                        monitor_name
+                            .unwrap_or_else(|| &w.monitor)
+                            .contains(&w.monitor)
+                            || !outputs.has_name(&w.monitor)
+                    }
+                    WorkspaceVisibilityMode::MonitorSpecificExclusive => monitor_name
+                        .unwrap_or_else(|| &w.monitor)
+                        .contains(&w.monitor),
+                };
 
-        MouseArea::new(
-            Row::with_children(
-                self.ui_workspaces
-                    .iter()
-                    .filter_map(|w| {
-                        let show = match self.config.visibility_mode {
-                            WorkspaceVisibilityMode::All => true,
-                            WorkspaceVisibilityMode::MonitorSpecific => {
-                                monitor_name
-                                    .unwrap_or_else(|| &w.monitor)
-                                    .contains(&w.monitor)
-                                    || !outputs.has_name(&w.monitor)
-                            }
-                            WorkspaceVisibilityMode::MonitorSpecificExclusive => monitor_name
-                                .unwrap_or_else(|| &w.monitor)
-                                .contains(&w.monitor),
-                        };
-
-                        if show {
-                            let empty = w.windows == 0;
-                            let color_index = if self.config.enable_virtual_desktops {
-                                Some(w.id as i128)
-                            } else {
-                                w.monitor_id
-                            };
-
-                            let color = color_index.map(|i| {
-                                if w.id > 0 {
-                                    theme.workspace_colors.get(i as usize).copied()
-                                } else {
-                                    theme
-                                        .special_workspace_colors
-                                        .as_ref()
-                                        .unwrap_or(&theme.workspace_colors)
-                                        .get(i as usize)
-                                        .copied()
-                                }
-                            });
-
-                            Some(
-                                button(
-                                    container(text(w.name.as_str()).size(theme.font_size.xs))
-                                        .align_x(alignment::Horizontal::Center)
-                                        .align_y(alignment::Vertical::Center),
-                                )
-                                .style(theme.workspace_button_style(empty, color))
-                                .padding(if w.id < 0 {
-                                    match w.displayed {
-                                        Displayed::Active => [0, theme.space.md],
-                                        Displayed::Visible => [0, theme.space.sm],
-                                        Displayed::Hidden => [0, theme.space.xs],
-                                    }
-                                } else {
-                                    [0, 0]
-                                })
-                                .on_press(if w.id > 0 {
-                                    Message::ChangeWorkspace(w.id)
-                                } else {
-                                    Message::ToggleSpecialWorkspace(w.id)
-                                })
-                                .width(match (w.id < 0, &w.displayed) {
-                                    (true, _) => Length::Shrink,
-                                    (_, Displayed::Active) => Length::Fixed(theme.space.xl as f32),
-                                    (_, Displayed::Visible) => Length::Fixed(theme.space.lg as f32),
-                                    (_, Displayed::Hidden) => Length::Fixed(theme.space.md as f32),
-                                })
-                                .height(theme.space.md)
-                                .into(),
-                            )
+                if show {
+                    let empty = w.windows == 0;
+                    let color_index = if self.config.enable_virtual_desktops {
+                        Some(w.id as i128)
+                    } else {
+                        w.monitor_id
+                    };
+
+                    let color = color_index.map(|i| {
+                        if w.id > 0 {
+                            theme.workspace_colors.get(i as usize).copied()
                         } else {
-                            None
+                            theme
+                                .special_workspace_colors
+                                .as_ref()
+                                .unwrap_or(&theme.workspace_colors)
+                                .get(i as usize)
+                                .copied()
                         }
-                    })
-                    .collect::<Vec<_>>(),
-            )
-            .spacing(theme.space.xxs),
-        )
-        .on_scroll(move |direction| match direction {
-            iced::mouse::ScrollDelta::Lines { y, .. } => {
-                if y < 0. {
-                    Message::Scroll(-1)
+                    });
+
+                    let length = if w.id < 0 {
+                        Length::Shrink
+                    } else {
+                        let size = pill_size(&w.displayed);
+                        let start = self.transition.as_ref().and_then(|transition| {
+                            if transition.to == w.id {
+                                Some(pill_size(&transition.to_start))
+                            } else if transition.from == Some(w.id) {
+                                Some(pill_size(&Displayed::Active))
+                            } else {
+                                None
+                            }
+                        });
+
+                        Length::Fixed(start.map_or(size, |start| start + (size - start) * progress))
+                    };
+                    // Workspace pills grow along the bar
+                    let (width, height) = if vertical && w.id > 0 {
+                        (Length::Fixed(theme.space.md as f32), length)
+                    } else {
+                        (length, Length::Fixed(theme.space.md as f32))
+                    };
+
+                    Some(
+                        button(
+                            container(text(self.label(w)).size(theme.font_size.xs))
+                                .align_x(alignment::Horizontal::Center)
+                                .align_y(alignment::Vertical::Center),
+                        )
+                        .style(theme.workspace_button_style(empty, color))
+                        .padding(if w.id < 0 {
+                            match w.displayed {
+                                Displayed::Active => [0, theme.space.md],
+                                Displayed::Visible => [0, theme.space.sm],
+                                Displayed::Hidden => [0, theme.space.xs],
+                            }
+                        } else {
+                            [0, 0]
+                        })
+                        .on_press(if w.id > 0 {
+                            Message::ChangeWorkspace(w.id)
+                        } else {
+                            Message::ToggleSpecialWorkspace(w.id)
+                        })
+                        .width(width)
+                        .height(height)
+                        .into(),
+                    )
                 } else {
-                    Message::Scroll(1)
+                    None
                 }
-            }
-            iced::mouse::ScrollDelta::Pixels { y, .. } => {
-                let sensibility = 3.;
+            })
+            .collect::<Vec<_>>();
+
+        let workspaces = MouseArea::new(if vertical {
+            Into::<Element<_>>::into(
+                Column::with_children(workspaces)
+                    .align_x(Alignment::Center)
+                    .spacing(theme.space.xxs),
+            )
+        } else {
+            Row::with_children(workspaces)
+                .spacing(theme.space.xxs)
+                .into()
+        });
 
-                if self.scroll_accumulator.abs() < sensibility {
-                    Message::ScrollAccumulator(y)
-                } else if self.scroll_accumulator.is_sign_positive() {
-                    Message::Scroll(-1)
-                } else {
-                    Message::Scroll(1)
+        if !self.config.scroll_to_switch {
+            return workspaces.into();
+        }
+
+        workspaces
+            .on_scroll(move |direction| match direction {
+                iced::mouse::ScrollDelta::Lines { y, .. } => {
+                    if y < 0. {
+                        Message::Scroll(-1)
+                    } else {
+                        Message::Scroll(1)
+                    }
                 }
-            }
-        })
-        .into()
+                iced::mouse::ScrollDelta::Pixels { y, .. } => {
+                    let sensibility = 3.;
+
+                    if self.scroll_accumulator.abs() < sensibility {
+                        Message::ScrollAccumulator(y)
+                    } else if self.scroll_accumulator.is_sign_positive() {
+                        Message::Scroll(-1)
+                    } else {
+                        Message::Scroll(1)
+                    }
+                }
+            })
+            .into()
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        CompositorService::subscribe().map(Message::ServiceEvent)
+        Subscription::batch([
+            CompositorService::subscribe().map(Message::ServiceEvent),
+            if self.transition.is_some() {
+                iced::time::every(Duration::from_millis(16)).map(|_| Message::AnimationTick)
+            } else {
+                Subscription::none()
+            },
+        ])
     }
 }
@@ -7,25 +7,145 @@ use iced::{
     runtime::platform_specific::wayland::layer_surface::{IcedMargin, IcedOutput, SctkLayerSurfaceSettings},
     window::Id,
 };
-use log::debug;
+use log::{debug, warn};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 use wayland_client::protocol::wl_output::WlOutput;
 
 use crate::{
     HEIGHT,
-    config::{self, AppearanceStyle, Position},
+    config::{self, AppearanceMargin, AppearanceStyle, Config, ExclusiveZone, Position},
     menu::{Menu, MenuType},
+    popup::ease_out_cubic,
     widgets::ButtonUIRef,
 };
 
+/// Thickness of the strip left on screen by a hidden bar, used to reveal it again
+const AUTO_HIDE_STRIP: f64 = 2.;
+/// Step of the slide animation per tick, the tick runs every 16ms
+const AUTO_HIDE_FRAME: Duration = Duration::from_millis(16);
+/// How long the pointer has to be away before the bar slides out
+const AUTO_HIDE_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone)]
+struct AutoHide {
+    hovered: bool,
+    /// When `hovered` last changed
+    since: Instant,
+    /// Visible fraction of the bar, 1.0 is fully shown
+    shown: f32,
+}
+
+impl Default for AutoHide {
+    fn default() -> Self {
+        Self {
+            hovered: false,
+            since: Instant::now(),
+            shown: 1.0,
+        }
+    }
+}
+
+impl AutoHide {
+    // Bars stay shown while hovered or while their menu is open
+    fn target(&self, menu_open: bool) -> f32 {
+        if self.hovered || menu_open { 1.0 } else { 0.0 }
+    }
+
+    fn set_hovered(&mut self, hovered: bool, now: Instant) {
+        if self.hovered != hovered {
+            self.hovered = hovered;
+            self.since = now;
+        }
+    }
+
+    /// Move `shown` one animation step towards the target, once the pointer stayed
+    /// on the reveal strip for `reveal_delay` or away for `AUTO_HIDE_DELAY`.
+    /// An open menu skips the delay. Returns whether the bar has to be resized.
+    fn advance(
+        &mut self,
+        menu_open: bool,
+        reveal_delay: Duration,
+        step: f32,
+        now: Instant,
+    ) -> bool {
+        let target = self.target(menu_open);
+        let delay = if target > self.shown {
+            reveal_delay
+        } else {
+            AUTO_HIDE_DELAY
+        };
+        if self.shown == target || (!menu_open && now.duration_since(self.since) < delay) {
+            return false;
+        }
+
+        self.shown = if target > self.shown {
+            (self.shown + step).min(target)
+        } else {
+            (self.shown - step).max(target)
+        };
+        true
+    }
+}
+
+/// Configuration shared by the layer surfaces of every output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceSettings {
+    pub style: AppearanceStyle,
+    pub margin: AppearanceMargin,
+    pub menu_blur: bool,
+    pub exclusive_zone: ExclusiveZone,
+    pub position: Position,
+    pub layer: config::Layer,
+    pub namespace: String,
+    pub keyboard_interactivity: config::KeyboardInteractivity,
+    pub scale_factor: f64,
+    pub output_scale_factors: HashMap<String, f64>,
+}
+
+impl SurfaceSettings {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            style: config.appearance.style,
+            margin: config.appearance.margin,
+            menu_blur: config.appearance.menu.blur,
+            exclusive_zone: config.appearance.bar_exclusive_zone(),
+            position: config.position,
+            layer: config.layer,
+            namespace: config.namespace.clone(),
+            keyboard_interactivity: config.keyboard_interactivity,
+            scale_factor: config.appearance.scale_factor,
+            output_scale_factors: config.appearance.output_scale_factors.clone(),
+        }
+    }
+
+    /// The settings of the surfaces of the output `name`, with its own scale factor.
+    /// Like the `outputs` targets, keys match any part of the output name, and
+    /// the longest match wins so `eDP-1` isn't shadowed by `DP-1`.
+    fn for_output(&self, name: &str) -> Self {
+        let scale_factor = self
+            .output_scale_factors
+            .iter()
+            .filter(|(output, _)| name.contains(output.as_str()))
+            .max_by_key(|(output, _)| output.len())
+            .map_or(self.scale_factor, |(_, scale_factor)| *scale_factor);
+
+        Self {
+            scale_factor,
+            ..self.clone()
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct ShellInfo {
     id: Id,
-    position: Position,
-    layer: config::Layer,
-    style: AppearanceStyle,
+    settings: SurfaceSettings,
     menu: Menu,
     popup_id: Id,
-    scale_factor: f64,
+    auto_hide: AutoHide,
 }
 
 #[derive(Debug, Clone)]
@@ -38,26 +158,18 @@ pub enum HasOutput<'a> {
 }
 
 impl Outputs {
-    pub fn new<Message: 'static>(
-        style: AppearanceStyle,
-        position: Position,
-        layer: config::Layer,
-        scale_factor: f64,
-    ) -> (Self, Task<Message>) {
-        let (id, menu_id, popup_id, task) =
-            Self::create_output_layers(style, None, position, layer, scale_factor);
+    pub fn new<Message: 'static>(settings: &SurfaceSettings) -> (Self, Task<Message>) {
+        let (id, menu_id, popup_id, task) = Self::create_output_layers(settings, None);
 
         (
             Self(vec![(
                 "Fallback".to_string(),
                 Some(ShellInfo {
                     id,
+                    settings: settings.clone(),
                     menu: Menu::new(menu_id),
                     popup_id,
-                    position,
-                    layer,
-                    style,
-                    scale_factor,
+                    auto_hide: AutoHide::default(),
                 }),
                 None,
             )]),
@@ -74,43 +186,126 @@ impl Outputs {
             * scale_factor
     }
 
+    // The exclusive zone already starts after the margin of the anchored edge,
+    // so only the inner one has to be reserved to keep windows away from the bar
+    fn get_exclusive_zone(
+        height: f64,
+        margin: AppearanceMargin,
+        position: Position,
+        exclusive_zone: ExclusiveZone,
+    ) -> i32 {
+        match exclusive_zone {
+            ExclusiveZone::Enabled(true) => {
+                height as i32
+                    + match position {
+                        Position::Top => margin.bottom,
+                        Position::Bottom => margin.top,
+                        Position::Left => margin.right,
+                        Position::Right => margin.left,
+                    } as i32
+            }
+            // Windows are laid out underneath the bar
+            ExclusiveZone::Enabled(false) => 0,
+            ExclusiveZone::Pixels(pixels) => pixels as i32,
+        }
+    }
+
+    // On the bottom layer windows are drawn over a bar that doesn't reserve
+    // its space, but the configured layer is still honored
+    fn get_layer(layer: config::Layer, exclusive_zone: ExclusiveZone) -> Layer {
+        match layer {
+            config::Layer::Bottom => {
+                if matches!(
+                    exclusive_zone,
+                    ExclusiveZone::Enabled(false) | ExclusiveZone::Pixels(0)
+                ) {
+                    warn!(
+                        "The bar doesn't reserve its space on the bottom layer, so windows will cover it. Use layer = \"Top\" to keep it above them"
+                    );
+                }
+                Layer::Bottom
+            }
+            config::Layer::Top => Layer::Top,
+            config::Layer::Overlay => Layer::Overlay,
+        }
+    }
+
+    fn get_anchor(position: Position) -> Anchor {
+        match position {
+            Position::Top => Anchor::TOP | Anchor::LEFT | Anchor::RIGHT,
+            Position::Bottom => Anchor::BOTTOM | Anchor::LEFT | Anchor::RIGHT,
+            Position::Left => Anchor::LEFT | Anchor::TOP | Anchor::BOTTOM,
+            Position::Right => Anchor::RIGHT | Anchor::TOP | Anchor::BOTTOM,
+        }
+    }
+
+    // The bar thickness is the height of horizontal bars and the width of vertical ones
+    fn get_size(height: f64, position: Position) -> (Option<u32>, Option<u32>) {
+        if position.is_vertical() {
+            (Some(height as u32), None)
+        } else {
+            (None, Some(height as u32))
+        }
+    }
+
+    fn get_keyboard_interactivity(
+        keyboard_interactivity: config::KeyboardInteractivity,
+    ) -> KeyboardInteractivity {
+        match keyboard_interactivity {
+            config::KeyboardInteractivity::None => KeyboardInteractivity::None,
+            config::KeyboardInteractivity::OnDemand => KeyboardInteractivity::OnDemand,
+        }
+    }
+
     fn create_output_layers<Message: 'static>(
-        style: AppearanceStyle,
+        settings: &SurfaceSettings,
         wl_output: Option<WlOutput>,
-        position: Position,
-        layer: config::Layer,
-        scale_factor: f64,
     ) -> (Id, Id, Id, Task<Message>) {
+        let SurfaceSettings {
+            style,
+            margin,
+            menu_blur,
+            exclusive_zone,
+            position,
+            layer,
+            ref namespace,
+            keyboard_interactivity,
+            scale_factor,
+            ..
+        } = *settings;
         let id = Id::unique();
         let height = Self::get_height(style, scale_factor);
 
-        let iced_layer = match layer {
-            config::Layer::Bottom => Layer::Bottom,
-            config::Layer::Overlay => Layer::Overlay,
-        };
-
         let task = get_layer_surface(SctkLayerSurfaceSettings {
             id,
-            namespace: "ashell-main-layer".to_string(),
-            size: Some((None, Some(height as u32))),
-            layer: iced_layer,
-            keyboard_interactivity: KeyboardInteractivity::None,
-            exclusive_zone: height as i32,
+            namespace: format!("{namespace}-main-layer"),
+            size: Some(Self::get_size(height, position)),
+            layer: Self::get_layer(layer, exclusive_zone),
+            keyboard_interactivity: Self::get_keyboard_interactivity(keyboard_interactivity),
+            exclusive_zone: Self::get_exclusive_zone(height, margin, position, exclusive_zone),
             output: wl_output.clone().map_or(IcedOutput::Active, |wl_output| {
                 IcedOutput::Output(wl_output)
             }),
-            anchor: match position {
-                Position::Top => Anchor::TOP,
-                Position::Bottom => Anchor::BOTTOM,
-            } | Anchor::LEFT
-                | Anchor::RIGHT,
+            anchor: Self::get_anchor(position),
+            margin: IcedMargin {
+                top: margin.top as i32,
+                right: margin.right as i32,
+                bottom: margin.bottom as i32,
+                left: margin.left as i32,
+            },
             ..Default::default()
         });
 
         let menu_id = Id::unique();
         let menu_task = get_layer_surface(SctkLayerSurfaceSettings {
             id: menu_id,
-            namespace: "ashell-main-layer".to_string(),
+            // Compositors that blur layer surfaces by namespace need a dedicated one
+            // to blur behind the menus without blurring the bar itself
+            namespace: if menu_blur {
+                format!("{namespace}-menu-layer")
+            } else {
+                format!("{namespace}-main-layer")
+            },
             size: Some((None, None)),
             layer: Layer::Background,
             keyboard_interactivity: KeyboardInteractivity::None,
@@ -124,12 +319,11 @@ impl Outputs {
         let popup_id = Id::unique();
         let popup_task = get_layer_surface(SctkLayerSurfaceSettings {
             id: popup_id,
-            namespace: "ashell-popup-layer".to_string(),
+            namespace: format!("{namespace}-popup-layer"),
             size: None,
             size_limits: Limits::NONE
                 .min_width(1.0)
                 .min_height(1.0)
-                .max_width(500.0)
                 .max_height(600.0),
             layer: Layer::Overlay,
             keyboard_interactivity: KeyboardInteractivity::None,
@@ -138,18 +332,30 @@ impl Outputs {
                 IcedOutput::Output(wl_output)
             }),
             anchor: match position {
-                Position::Top => Anchor::TOP,
                 Position::Bottom => Anchor::BOTTOM,
+                Position::Top | Position::Left | Position::Right => Anchor::TOP,
             } | Anchor::RIGHT,
             margin: {
                 let gap = if style == AppearanceStyle::Islands { 4 } else { 0 };
                 match position {
                     Position::Top => IcedMargin {
-                        top: height as i32 + gap,
+                        top: height as i32 + margin.top as i32 + margin.bottom as i32 + gap,
+                        right: margin.right as i32,
                         ..Default::default()
                     },
                     Position::Bottom => IcedMargin {
-                        bottom: height as i32 + gap,
+                        bottom: height as i32 + margin.top as i32 + margin.bottom as i32 + gap,
+                        right: margin.right as i32,
+                        ..Default::default()
+                    },
+                    Position::Left => IcedMargin {
+                        top: margin.top as i32,
+                        right: margin.right as i32,
+                        ..Default::default()
+                    },
+                    Position::Right => IcedMargin {
+                        top: margin.top as i32,
+                        right: height as i32 + margin.left as i32 + margin.right as i32 + gap,
                         ..Default::default()
                     },
                 }
@@ -167,6 +373,9 @@ impl Outputs {
             config::Outputs::Targets(request_outputs) => {
                 request_outputs.iter().any(|output| name.contains(output))
             }
+            config::Outputs::Exclude(excluded_outputs) => {
+                !excluded_outputs.iter().any(|output| name.contains(output))
+            }
         }
     }
 
@@ -186,6 +395,24 @@ impl Outputs {
         })
     }
 
+    /// Scale factor of the output owning the surface `id`.
+    pub fn scale_factor(&self, id: Id) -> Option<f64> {
+        self.0.iter().find_map(|(_, info, _)| {
+            info.as_ref()
+                .filter(|info| info.id == id || info.menu.id == id || info.popup_id == id)
+                .map(|info| info.settings.scale_factor)
+        })
+    }
+
+    /// The popup surface of the first active output, used when popups are
+    /// not mirrored on every output.
+    pub fn is_primary_popup(&self, id: Id) -> bool {
+        self.0
+            .iter()
+            .find_map(|(_, info, _)| info.as_ref())
+            .is_some_and(|info| info.popup_id == id)
+    }
+
     pub fn get_monitor_name(&self, id: Id) -> Option<&str> {
         self.0.iter().find_map(|(name, info, _)| {
             info.as_ref().and_then(|info| {
@@ -204,29 +431,21 @@ impl Outputs {
             .any(|(n, info, _)| info.is_some() && n.as_str().contains(name))
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub fn add<Message: 'static>(
         &mut self,
-        style: AppearanceStyle,
+        settings: &SurfaceSettings,
         request_outputs: &config::Outputs,
-        position: Position,
-        layer: config::Layer,
         name: &str,
         wl_output: WlOutput,
-        scale_factor: f64,
     ) -> Task<Message> {
         let target = Self::name_in_config(name, request_outputs);
 
         if target {
             debug!("Found target output, creating a new layer surface");
 
-            let (id, menu_id, popup_id, task) = Self::create_output_layers(
-                style,
-                Some(wl_output.clone()),
-                position,
-                layer,
-                scale_factor,
-            );
+            let settings = settings.for_output(name);
+            let (id, menu_id, popup_id, task) =
+                Self::create_output_layers(&settings, Some(wl_output.clone()));
 
             let destroy_task = match self.0.iter().position(|(key, _, _)| key.as_str() == name) {
                 Some(index) => {
@@ -250,12 +469,10 @@ impl Outputs {
                 name.to_owned(),
                 Some(ShellInfo {
                     id,
+                    settings,
                     menu: Menu::new(menu_id),
                     popup_id,
-                    position,
-                    layer,
-                    style,
-                    scale_factor,
+                    auto_hide: AutoHide::default(),
                 }),
                 Some(wl_output),
             ));
@@ -297,17 +514,21 @@ impl Outputs {
 
     pub fn remove<Message: 'static>(
         &mut self,
-        style: AppearanceStyle,
-        position: Position,
-        layer: config::Layer,
+        settings: &SurfaceSettings,
         wl_output: WlOutput,
-        scale_factor: f64,
     ) -> Task<Message> {
         match self.0.iter().position(|(_, _, assigned_wl_output)| {
             assigned_wl_output
                 .as_ref()
                 .is_some_and(|assigned_wl_output| *assigned_wl_output == wl_output)
         }) {
+            Some(index_to_remove) if self.0[index_to_remove].1.is_none() => {
+                debug!("Removing output without a layer surface");
+
+                self.0.swap_remove(index_to_remove);
+
+                Task::none()
+            }
             Some(index_to_remove) => {
                 debug!("Removing layer surface for output");
 
@@ -330,20 +551,17 @@ impl Outputs {
                 } else {
                     debug!("No outputs left, creating a fallback layer surface");
 
-                    let (id, menu_id, popup_id, task) =
-                        Self::create_output_layers(style, None, position, layer, scale_factor);
+                    let (id, menu_id, popup_id, task) = Self::create_output_layers(settings, None);
 
                     self.0.push((
                         "Fallback".to_string(),
                         Some(ShellInfo {
                             id,
+                            settings: settings.clone(),
                             menu: Menu::new(menu_id),
                             popup_id,
-                            position,
-                            layer,
-                            style,
-                            scale_factor,
-                        }),
+                            auto_hide: AutoHide::default(),
+                                }),
                         None,
                     ));
 
@@ -356,11 +574,8 @@ impl Outputs {
 
     pub fn sync<Message: 'static>(
         &mut self,
-        style: AppearanceStyle,
+        settings: &SurfaceSettings,
         request_outputs: &config::Outputs,
-        position: Position,
-        layer: config::Layer,
-        scale_factor: f64,
     ) -> Task<Message> {
         debug!("Syncing outputs: {self:?}, request_outputs: {request_outputs:?}");
 
@@ -395,70 +610,64 @@ impl Outputs {
 
         for (name, wl_output) in to_add {
             if let Some(wl_output) = wl_output {
-                tasks.push(self.add(
-                    style,
-                    request_outputs,
-                    position,
-                    layer,
-                    name.as_str(),
-                    wl_output,
-                    scale_factor,
-                ));
+                tasks.push(self.add(settings, request_outputs, name.as_str(), wl_output));
             }
         }
 
         for wl_output in to_remove {
-            tasks.push(self.remove(style, position, layer, wl_output, scale_factor));
+            tasks.push(self.remove(settings, wl_output));
         }
 
         for shell_info in self.0.iter_mut().filter_map(|(_, shell_info, _)| {
             if let Some(shell_info) = shell_info
-                && shell_info.position != position
+                && shell_info.settings.position != settings.position
             {
                 Some(shell_info)
             } else {
                 None
             }
         }) {
+            let position = settings.position;
             debug!(
                 "Repositioning output: {:?}, new position {:?}",
                 shell_info.id, position
             );
-            shell_info.position = position;
-            tasks.push(set_anchor(
-                shell_info.id,
-                match position {
-                    Position::Top => Anchor::TOP,
-                    Position::Bottom => Anchor::BOTTOM,
-                } | Anchor::LEFT
-                    | Anchor::RIGHT,
-            ));
+            shell_info.settings.position = position;
+            shell_info.auto_hide = AutoHide::default();
+            let (width, height) = Self::get_size(
+                Self::get_height(shell_info.settings.style, shell_info.settings.scale_factor),
+                position,
+            );
+            tasks.push(Task::batch(vec![
+                set_anchor(shell_info.id, Self::get_anchor(position)),
+                set_size(shell_info.id, width, height),
+            ]));
         }
 
-        // Handle layer changes - only recreate surfaces when layer actually changes
-        for (_name, shell_info, wl_output) in &mut self.0 {
+        // Handle layer, namespace, keyboard, margin and menu blur changes - only recreate surfaces when they actually change
+        for (name, shell_info, wl_output) in &mut self.0 {
             if let Some(shell_info) = shell_info
-                && shell_info.layer != layer
+                && (shell_info.settings.layer != settings.layer
+                    || shell_info.settings.namespace != settings.namespace
+                    || shell_info.settings.keyboard_interactivity
+                        != settings.keyboard_interactivity
+                    || shell_info.settings.margin != settings.margin
+                    || shell_info.settings.menu_blur != settings.menu_blur
+                    || shell_info.settings.exclusive_zone != settings.exclusive_zone)
             {
                 let destroy_main_task = destroy_layer_surface(shell_info.id);
                 let destroy_menu_task = destroy_layer_surface(shell_info.menu.id);
                 let destroy_popup_task = destroy_layer_surface(shell_info.popup_id);
 
-                let (id, menu_id, popup_id, task) = Self::create_output_layers(
-                    style,
-                    wl_output.clone(),
-                    position,
-                    layer,
-                    scale_factor,
-                );
+                let settings = settings.for_output(name);
+                let (id, menu_id, popup_id, task) =
+                    Self::create_output_layers(&settings, wl_output.clone());
 
                 shell_info.id = id;
+                shell_info.settings = settings;
                 shell_info.menu = Menu::new(menu_id);
                 shell_info.popup_id = popup_id;
-                shell_info.position = position;
-                shell_info.layer = layer;
-                shell_info.style = style;
-                shell_info.scale_factor = scale_factor;
+                shell_info.auto_hide = AutoHide::default();
 
                 tasks.push(Task::batch(vec![
                     destroy_main_task,
@@ -469,40 +678,176 @@ impl Outputs {
             }
         }
 
-        for shell_info in self.0.iter_mut().filter_map(|(_, shell_info, _)| {
+        for (shell_info, scale_factor) in self.0.iter_mut().filter_map(|(name, shell_info, _)| {
+            let scale_factor = settings.for_output(name).scale_factor;
+
             if let Some(shell_info) = shell_info
-                && (shell_info.style != style || shell_info.scale_factor != scale_factor)
+                && (shell_info.settings.style != settings.style
+                    || shell_info.settings.scale_factor != scale_factor)
             {
-                Some(shell_info)
+                Some((shell_info, scale_factor))
             } else {
                 None
             }
         }) {
+            let style = settings.style;
             debug!(
                 "Change style or scale_factor for output: {:?}, new style {:?}, new scale_factor {:?}",
                 shell_info.id, style, scale_factor
             );
-            shell_info.style = style;
-            shell_info.scale_factor = scale_factor;
+            shell_info.settings.style = style;
+            shell_info.settings.scale_factor = scale_factor;
+            shell_info.auto_hide = AutoHide::default();
             let height = Self::get_height(style, scale_factor);
+            let (surface_width, surface_height) =
+                Self::get_size(height, shell_info.settings.position);
             tasks.push(Task::batch(vec![
-                set_size(shell_info.id, None, Some(height as u32)),
-                set_exclusive_zone(shell_info.id, height as i32),
+                set_size(shell_info.id, surface_width, surface_height),
+                set_exclusive_zone(
+                    shell_info.id,
+                    Self::get_exclusive_zone(
+                        height,
+                        shell_info.settings.margin,
+                        shell_info.settings.position,
+                        shell_info.settings.exclusive_zone,
+                    ),
+                ),
             ]));
         }
 
         Task::batch(tasks)
     }
 
+    // The bar surface goes back to the configured mode once no menu needs the keyboard
+    fn idle_keyboard_interactivity(&self, id: Id) -> KeyboardInteractivity {
+        self.0
+            .iter()
+            .find_map(|(_, shell_info, _)| {
+                shell_info
+                    .as_ref()
+                    .filter(|shell_info| shell_info.id == id)
+                    .map(|shell_info| {
+                        Self::get_keyboard_interactivity(shell_info.settings.keyboard_interactivity)
+                    })
+            })
+            .unwrap_or(KeyboardInteractivity::None)
+    }
+
     pub fn menu_is_open(&self) -> bool {
         self.0.iter().any(|(_, shell_info, _)| {
             shell_info
                 .as_ref()
-                .map(|shell_info| shell_info.menu.menu_info.is_some())
+                .map(|shell_info| shell_info.menu.is_open())
                 .unwrap_or_default()
         })
     }
 
+    pub fn menu_is_animating(&self) -> bool {
+        self.0.iter().any(|(_, shell_info, _)| {
+            shell_info
+                .as_ref()
+                .is_some_and(|shell_info| shell_info.menu.is_animating())
+        })
+    }
+
+    pub fn menu_progress(&self, id: Id, animation_duration: Duration) -> f32 {
+        self.0
+            .iter()
+            .find_map(|(_, shell_info, _)| {
+                shell_info
+                    .as_ref()
+                    .filter(|shell_info| shell_info.menu.id == id)
+                    .map(|shell_info| shell_info.menu.progress(animation_duration))
+            })
+            .unwrap_or(1.0)
+    }
+
+    pub fn tick_menus<Message: 'static>(&mut self, animation_duration: Duration) -> Task<Message> {
+        Task::batch(
+            self.0
+                .iter_mut()
+                .filter_map(|(_, shell_info, _)| shell_info.as_mut())
+                .map(|shell_info| shell_info.menu.tick(animation_duration))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Track the pointer over a bar surface, other surfaces are ignored.
+    pub fn set_bar_hovered(&mut self, id: Id, hovered: bool) {
+        if let Some(shell_info) = self
+            .0
+            .iter_mut()
+            .filter_map(|(_, shell_info, _)| shell_info.as_mut())
+            .find(|shell_info| shell_info.id == id)
+        {
+            shell_info.auto_hide.set_hovered(hovered, Instant::now());
+        }
+    }
+
+    pub fn auto_hide_is_animating(&self) -> bool {
+        self.0.iter().any(|(_, shell_info, _)| {
+            shell_info.as_ref().is_some_and(|shell_info| {
+                shell_info.auto_hide.shown != shell_info.auto_hide.target(shell_info.menu.is_open())
+            })
+        })
+    }
+
+    /// Slide bars in once the pointer stayed on the reveal strip for `reveal_delay`,
+    /// and out once it stayed away for a fixed delay.
+    pub fn tick_auto_hide<Message: 'static>(
+        &mut self,
+        reveal_delay: Duration,
+        animation_duration: Duration,
+    ) -> Task<Message> {
+        let step = AUTO_HIDE_FRAME.as_secs_f32() / animation_duration.as_secs_f32().max(0.001);
+        let now = Instant::now();
+
+        Task::batch(
+            self.0
+                .iter_mut()
+                .filter_map(|(_, shell_info, _)| shell_info.as_mut())
+                .filter_map(|shell_info| {
+                    let auto_hide = &mut shell_info.auto_hide;
+                    if !auto_hide.advance(shell_info.menu.is_open(), reveal_delay, step, now) {
+                        return None;
+                    }
+
+                    let full = Self::get_height(
+                        shell_info.settings.style,
+                        shell_info.settings.scale_factor,
+                    );
+                    let thickness = AUTO_HIDE_STRIP
+                        + (full - AUTO_HIDE_STRIP) * f64::from(ease_out_cubic(auto_hide.shown));
+                    let (width, height) = Self::get_size(thickness, shell_info.settings.position);
+
+                    Some(set_size(shell_info.id, width, height))
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Bring every bar back to full size, used when auto hide gets disabled.
+    pub fn reset_auto_hide<Message: 'static>(&mut self) -> Task<Message> {
+        Task::batch(
+            self.0
+                .iter_mut()
+                .filter_map(|(_, shell_info, _)| shell_info.as_mut())
+                .filter(|shell_info| shell_info.auto_hide.shown != 1.0)
+                .map(|shell_info| {
+                    shell_info.auto_hide = AutoHide::default();
+                    let (width, height) = Self::get_size(
+                        Self::get_height(
+                            shell_info.settings.style,
+                            shell_info.settings.scale_factor,
+                        ),
+                        shell_info.settings.position,
+                    );
+                    set_size(shell_info.id, width, height)
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
     pub fn toggle_menu<Message: 'static>(
         &mut self,
         id: Id,
@@ -550,7 +895,7 @@ impl Outputs {
             } else {
                 Task::batch(vec![
                     task,
-                    set_keyboard_interactivity(id, KeyboardInteractivity::None),
+                    set_keyboard_interactivity(id, self.idle_keyboard_interactivity(id)),
                 ])
             }
         } else {
@@ -574,7 +919,7 @@ impl Outputs {
         if esc_button_enabled && !self.menu_is_open() {
             Task::batch(vec![
                 task,
-                set_keyboard_interactivity(id, KeyboardInteractivity::None),
+                set_keyboard_interactivity(id, self.idle_keyboard_interactivity(id)),
             ])
         } else {
             task
@@ -598,7 +943,7 @@ impl Outputs {
         if esc_button_enabled && !self.menu_is_open() {
             Task::batch(vec![
                 task,
-                set_keyboard_interactivity(id, KeyboardInteractivity::None),
+                set_keyboard_interactivity(id, self.idle_keyboard_interactivity(id)),
             ])
         } else {
             task
@@ -629,7 +974,12 @@ impl Outputs {
                 .iter()
                 .map(|(_, shell_info, _)| {
                     shell_info.as_ref().map_or_else(Task::none, |shell_info| {
-                        set_keyboard_interactivity(shell_info.id, KeyboardInteractivity::None)
+                        set_keyboard_interactivity(
+                            shell_info.id,
+                            Self::get_keyboard_interactivity(
+                                shell_info.settings.keyboard_interactivity,
+                            ),
+                        )
                     })
                 })
                 .collect::<Vec<_>>();
@@ -645,7 +995,7 @@ impl Outputs {
                 .iter_mut()
                 .map(|(_, shell_info, _)| {
                     if let Some(shell_info) = shell_info {
-                        if shell_info.menu.menu_info.is_some() {
+                        if shell_info.menu.is_open() {
                             shell_info.menu.close()
                         } else {
                             Task::none()
@@ -663,7 +1013,12 @@ impl Outputs {
                 .iter()
                 .map(|(_, shell_info, _)| {
                     shell_info.as_ref().map_or_else(Task::none, |shell_info| {
-                        set_keyboard_interactivity(shell_info.id, KeyboardInteractivity::None)
+                        set_keyboard_interactivity(
+                            shell_info.id,
+                            Self::get_keyboard_interactivity(
+                                shell_info.settings.keyboard_interactivity,
+                            ),
+                        )
                     })
                 })
                 .collect::<Vec<_>>();
@@ -693,15 +1048,136 @@ impl Outputs {
         }
     }
 
-    pub fn notification_menu_is_open(&self) -> bool {
+    pub fn menu_type_is_open(&self, menu_type: &MenuType) -> bool {
         self.0.iter().any(|(_, shell_info, _)| {
             shell_info.as_ref().is_some_and(|shell_info| {
-                shell_info
-                    .menu
-                    .menu_info
-                    .as_ref()
-                    .is_some_and(|(t, _)| *t == MenuType::Notifications)
+                shell_info.menu.is_open()
+                    && shell_info
+                        .menu
+                        .menu_info
+                        .as_ref()
+                        .is_some_and(|(t, _)| t == menu_type)
             })
         })
     }
+
+    pub fn notification_menu_is_open(&self) -> bool {
+        self.menu_type_is_open(&MenuType::Notifications)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REVEAL_DELAY: Duration = Duration::from_millis(200);
+    const STEP: f32 = 0.5;
+
+    fn shown_bar(start: Instant) -> AutoHide {
+        AutoHide {
+            hovered: false,
+            since: start,
+            shown: 1.0,
+        }
+    }
+
+    #[test]
+    fn bar_hides_after_the_pointer_left() {
+        let start = Instant::now();
+        let mut auto_hide = shown_bar(start);
+
+        assert!(!auto_hide.advance(false, REVEAL_DELAY, STEP, start + AUTO_HIDE_DELAY / 2));
+        assert_eq!(auto_hide.shown, 1.0);
+
+        let later = start + AUTO_HIDE_DELAY;
+        assert!(auto_hide.advance(false, REVEAL_DELAY, STEP, later));
+        assert_eq!(auto_hide.shown, 0.5);
+        assert!(auto_hide.advance(false, REVEAL_DELAY, STEP, later));
+        assert_eq!(auto_hide.shown, 0.0);
+        assert!(!auto_hide.advance(false, REVEAL_DELAY, STEP, later));
+    }
+
+    #[test]
+    fn bar_reveals_after_the_reveal_delay() {
+        let start = Instant::now();
+        let mut auto_hide = AutoHide {
+            shown: 0.0,
+            ..shown_bar(start)
+        };
+        auto_hide.set_hovered(true, start);
+
+        assert!(!auto_hide.advance(false, REVEAL_DELAY, STEP, start + REVEAL_DELAY / 2));
+        assert_eq!(auto_hide.shown, 0.0);
+
+        let later = start + REVEAL_DELAY;
+        assert!(auto_hide.advance(false, REVEAL_DELAY, STEP, later));
+        assert!(auto_hide.advance(false, REVEAL_DELAY, STEP, later));
+        assert_eq!(auto_hide.shown, 1.0);
+        assert!(!auto_hide.advance(false, REVEAL_DELAY, STEP, later));
+    }
+
+    #[test]
+    fn hover_changes_restart_the_delay() {
+        let start = Instant::now();
+        let mut auto_hide = shown_bar(start);
+
+        auto_hide.set_hovered(true, start + AUTO_HIDE_DELAY);
+        auto_hide.set_hovered(true, start + AUTO_HIDE_DELAY * 2);
+        assert_eq!(auto_hide.since, start + AUTO_HIDE_DELAY);
+
+        auto_hide.set_hovered(false, start + AUTO_HIDE_DELAY * 2);
+        assert!(!auto_hide.advance(false, REVEAL_DELAY, STEP, start + AUTO_HIDE_DELAY * 2));
+        assert_eq!(auto_hide.shown, 1.0);
+    }
+
+    #[test]
+    fn open_menu_keeps_the_bar_shown_without_delay() {
+        let start = Instant::now();
+        let mut auto_hide = AutoHide {
+            shown: 0.0,
+            ..shown_bar(start)
+        };
+
+        assert_eq!(auto_hide.target(true), 1.0);
+        assert!(auto_hide.advance(true, REVEAL_DELAY, STEP, start));
+        assert_eq!(auto_hide.shown, 0.5);
+    }
+
+    #[test]
+    fn configured_layer_is_kept_without_exclusive_zone() {
+        assert_eq!(
+            Outputs::get_layer(config::Layer::Bottom, ExclusiveZone::Enabled(true)),
+            Layer::Bottom
+        );
+        assert_eq!(
+            Outputs::get_layer(config::Layer::Bottom, ExclusiveZone::Enabled(false)),
+            Layer::Bottom
+        );
+        assert_eq!(
+            Outputs::get_layer(config::Layer::Bottom, ExclusiveZone::Pixels(0)),
+            Layer::Bottom
+        );
+        assert_eq!(
+            Outputs::get_layer(config::Layer::Top, ExclusiveZone::Enabled(false)),
+            Layer::Top
+        );
+        assert_eq!(
+            Outputs::get_layer(config::Layer::Overlay, ExclusiveZone::Enabled(false)),
+            Layer::Overlay
+        );
+    }
+
+    #[test]
+    fn output_scale_factor_overrides_the_global_one() {
+        let mut config = Config::default();
+        config.appearance.scale_factor = 1.25;
+        config.appearance.output_scale_factors =
+            HashMap::from([("DP-1".to_string(), 1.5), ("eDP-1".to_string(), 1.0)]);
+        let settings = SurfaceSettings::new(&config);
+
+        assert_eq!(settings.for_output("Dell U2720Q (DP-1)").scale_factor, 1.5);
+        assert_eq!(settings.for_output("BOE 0x095F (eDP-1)").scale_factor, 1.0);
+        assert_eq!(settings.for_output("LG (HDMI-A-1)").scale_factor, 1.25);
+        assert_eq!(settings.for_output("Fallback").scale_factor, 1.25);
+    }
 }
@@ -3,7 +3,7 @@ use crate::{
     theme::AshellTheme,
 };
 use iced::{
-    Alignment, Element, Length,
+    Alignment, Element, Length, Theme,
     alignment::Vertical,
     widget::{button, column, horizontal_space, row, text, text_input},
     window::Id,
@@ -21,15 +21,23 @@ pub fn view<'a>(
     theme: &'a AshellTheme,
     wifi_ssid: &str,
     current_password: &str,
+    error: Option<&'a str>,
 ) -> Element<'a, Message> {
     column!(
         row!(
-            icon(StaticIcon::WifiLock4).size(theme.font_size.xxl),
+            icon(theme, StaticIcon::WifiLock4).size(theme.font_size.xxl),
             text("Authentication required").size(theme.font_size.xl),
         )
         .spacing(theme.space.md)
         .align_y(Alignment::Center),
         text(format!("Insert password to connect to: {wifi_ssid}")),
+    )
+    .push_maybe(error.map(|error| {
+        text(error).style(|theme: &Theme| text::Style {
+            color: Some(theme.palette().danger),
+        })
+    }))
+    .push(
         text_input("", current_password)
             .secure(true)
             .size(theme.font_size.md)
@@ -37,6 +45,8 @@ pub fn view<'a>(
             .style(theme.text_input_style())
             .on_input(Message::PasswordChanged)
             .on_submit(Message::DialogConfirmed(id)),
+    )
+    .push(
         row!(
             horizontal_space(),
             button(text("Cancel").align_y(Vertical::Center))
@@ -51,7 +61,7 @@ pub fn view<'a>(
                 .on_press(Message::DialogConfirmed(id))
         )
         .spacing(theme.space.xs)
-        .width(Length::Fill)
+        .width(Length::Fill),
     )
     .spacing(theme.space.md)
     .padding(theme.space.md)
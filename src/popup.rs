@@ -1,7 +1,17 @@
-use std::time::{Duration, Instant};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
 
 use crate::{config::NotificationsModuleConfig, services::notifications::Notification};
 
+/// Height of a popup entry with the default icon size
+const ENTRY_HEIGHT: f32 = 80.0;
+/// Height of a compact (summary only) popup entry
+const COMPACT_ENTRY_HEIGHT: f32 = 48.0;
+/// Vertical padding and separator around an entry's content
+const ENTRY_PADDING: f32 = 17.0;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PopupPhase {
     SlideIn,
@@ -19,41 +29,87 @@ pub struct PopupEntry {
 
 pub struct PopupState {
     pub entries: Vec<PopupEntry>,
+    /// Notifications waiting for a visible slot, oldest first
+    pending: VecDeque<(Notification, Duration)>,
     pub max_visible: usize,
+    pub queue_size: usize,
     pub animation_duration: Duration,
+    pub opacity: Option<f32>,
+    pub max_width: f32,
+    pub icon_size: f32,
+    pub compact: bool,
 }
 
 impl PopupState {
     pub fn new(config: &NotificationsModuleConfig) -> Self {
         Self {
             entries: Vec::new(),
+            pending: VecDeque::new(),
             max_visible: config.popup_max_visible,
+            queue_size: config.popup_queue_size,
             animation_duration: Duration::from_millis(config.popup_animation_ms),
+            opacity: config.popup_opacity,
+            max_width: config.popup_max_width as f32,
+            icon_size: f32::from(config.icon_size),
+            compact: config.popup_compact,
         }
     }
 
     pub fn update_config(&mut self, config: &NotificationsModuleConfig) {
         self.max_visible = config.popup_max_visible;
+        self.queue_size = config.popup_queue_size;
+        // Like a full queue, a smaller one drops the oldest pending notifications
+        let excess = self.pending.len().saturating_sub(self.queue_size);
+        self.pending.drain(..excess);
         self.animation_duration = Duration::from_millis(config.popup_animation_ms);
+        self.opacity = config.popup_opacity;
+        self.max_width = config.popup_max_width as f32;
+        self.icon_size = f32::from(config.icon_size);
+        self.compact = config.popup_compact;
     }
 
     pub fn enqueue(&mut self, notification: Notification, display_duration: Duration) {
-        // If this notification replaces an existing one, remove the old entry
-        self.entries
-            .retain(|e| e.notification.id != notification.id);
-
         let now = Instant::now();
-        self.entries.push(PopupEntry {
-            notification,
-            phase: PopupPhase::SlideIn,
-            phase_started: now,
-            display_duration,
-        });
 
-        // If we exceed max_visible, transition oldest to SlideOut
-        while self.entries.iter().filter(|e| e.phase != PopupPhase::SlideOut).count()
-            > self.max_visible
+        // Replacements update the visible entry in place instead of stacking
+        if let Some(entry) = self.entries.iter_mut().find(|e| {
+            e.phase != PopupPhase::SlideOut && e.notification.is_replaced_by(&notification)
+        }) {
+            entry.notification = notification;
+            entry.display_duration = display_duration;
+            if entry.phase == PopupPhase::Display {
+                entry.phase_started = now;
+            }
+            return;
+        }
+        if let Some(pending) = self
+            .pending
+            .iter_mut()
+            .find(|(queued, _)| queued.is_replaced_by(&notification))
+        {
+            *pending = (notification, display_duration);
+            return;
+        }
+
+        // Drop replaced entries that are already sliding out
+        self.entries.retain(|e| !e.notification.is_replaced_by(&notification));
+
+        // Wait for a free slot instead of pushing out a notification that may not have been
+        // read, and keep arrival order while older ones are still queued
+        if self.queue_size > 0
+            && (self.visible_count() >= self.max_visible || !self.pending.is_empty())
         {
+            if self.pending.len() >= self.queue_size {
+                self.pending.pop_front();
+            }
+            self.pending.push_back((notification, display_duration));
+            return;
+        }
+
+        self.push_entry(notification, display_duration, now);
+
+        // If we exceed max_visible, transition oldest to SlideOut
+        while self.visible_count() > self.max_visible {
             if let Some(oldest) = self
                 .entries
                 .iter_mut()
@@ -67,6 +123,22 @@ impl PopupState {
         }
     }
 
+    fn push_entry(&mut self, notification: Notification, display_duration: Duration, now: Instant) {
+        self.entries.push(PopupEntry {
+            notification,
+            phase: PopupPhase::SlideIn,
+            phase_started: now,
+            display_duration,
+        });
+    }
+
+    fn visible_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.phase != PopupPhase::SlideOut)
+            .count()
+    }
+
     /// Advance phases, remove completed entries. Returns true if entries changed.
     pub fn tick(&mut self) -> bool {
         let now = Instant::now();
@@ -109,6 +181,15 @@ impl PopupState {
             changed = true;
         }
 
+        // Slide queued notifications into slots freed up above
+        while self.visible_count() < self.max_visible {
+            let Some((notification, display_duration)) = self.pending.pop_front() else {
+                break;
+            };
+            self.push_entry(notification, display_duration, now);
+            changed = true;
+        }
+
         changed
     }
 
@@ -118,10 +199,47 @@ impl PopupState {
             entry.phase = PopupPhase::SlideOut;
             entry.phase_started = now;
         }
+        self.pending.retain(|(queued, _)| queued.id != id);
+    }
+
+    /// Drop every popup, including queued ones.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.pending.clear();
     }
 
     pub fn is_active(&self) -> bool {
-        !self.entries.is_empty()
+        !self.entries.is_empty() || !self.pending.is_empty()
+    }
+
+    /// The newest entry that is not sliding out, i.e. the one the user just saw appear.
+    fn front_entry(&self) -> Option<&PopupEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|e| e.phase != PopupPhase::SlideOut)
+    }
+
+    /// Id of the front-most notification, the one keyboard shortcuts act on.
+    pub fn front_id(&self) -> Option<u32> {
+        self.front_entry().map(|e| e.notification.id)
+    }
+
+    /// Whether the front-most entry has actions that can be invoked from the keyboard.
+    pub fn has_actions(&self) -> bool {
+        self.front_action(0).is_some()
+    }
+
+    /// The notification id and key of the `index`-th non-default action of the front-most entry.
+    pub fn front_action(&self, index: usize) -> Option<(u32, String)> {
+        let entry = self.front_entry()?;
+        entry
+            .notification
+            .actions
+            .iter()
+            .filter(|(k, _)| k != "default")
+            .nth(index)
+            .map(|(k, _)| (entry.notification.id, k.clone()))
     }
 
     /// Overall bubble visibility progress (0.0-1.0).
@@ -202,9 +320,19 @@ impl PopupState {
         } else {
             let count = self.entries.len() as f32;
             let spacing = (self.entries.len().saturating_sub(1)) as f32 * 2.0;
-            count * 80.0 + top_pad + bottom_pad + spacing
+            count * self.entry_height() + top_pad + bottom_pad + spacing
         }
     }
+
+    /// Entries grow when the icon is taller than the text block.
+    pub fn entry_height(&self) -> f32 {
+        let base = if self.compact {
+            COMPACT_ENTRY_HEIGHT
+        } else {
+            ENTRY_HEIGHT
+        };
+        base.max(self.icon_size + ENTRY_PADDING)
+    }
 }
 
 fn ease_out_back(t: f32) -> f32 {
@@ -213,43 +341,30 @@ fn ease_out_back(t: f32) -> f32 {
     1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
 }
 
-fn ease_out_cubic(t: f32) -> f32 {
+pub fn ease_out_cubic(t: f32) -> f32 {
     1.0 - (1.0 - t).powi(3)
 }
 
-fn ease_in_cubic(t: f32) -> f32 {
+pub fn ease_in_cubic(t: f32) -> f32 {
     t * t * t
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::services::notifications::{Notification, Urgency};
+    use crate::services::notifications::Notification;
     use std::thread;
 
     fn test_config() -> NotificationsModuleConfig {
         NotificationsModuleConfig {
-            max_notifications: 50,
-            default_timeout: 5000,
-            popup_enabled: true,
-            popup_max_visible: 3,
-            popup_duration_ms: 5000,
+            popup_queue_size: 0,
             popup_animation_ms: 100, // short for fast tests
+            ..NotificationsModuleConfig::default()
         }
     }
 
     fn make_notification(id: u32) -> Notification {
-        Notification {
-            id,
-            app_name: format!("App{id}"),
-            icon: None,
-            summary: format!("Title {id}"),
-            body: format!("Body {id}"),
-            actions: vec![],
-            urgency: Urgency::Normal,
-            timestamp: chrono::Local::now(),
-            transient: false,
-        }
+        Notification::test(id)
     }
 
     // --- Easing functions ---
@@ -349,6 +464,50 @@ mod tests {
         assert_eq!(state.entries[0].notification.id, 1);
     }
 
+    #[test]
+    fn enqueue_replaces_notification_with_same_sync_key() {
+        let config = test_config();
+        let mut state = PopupState::new(&config);
+
+        let mut volume = make_notification(1);
+        volume.sync_key = Some("volume".to_string());
+        state.enqueue(volume.clone(), Duration::from_secs(5));
+
+        volume.id = 2;
+        volume.body = "Volume 40%".to_string();
+        state.enqueue(volume, Duration::from_secs(5));
+
+        assert_eq!(state.entries.len(), 1);
+        assert_eq!(state.entries[0].notification.id, 2);
+        assert_eq!(state.entries[0].notification.body, "Volume 40%");
+    }
+
+    #[test]
+    fn front_action_uses_newest_entry_and_skips_default() {
+        let config = test_config();
+        let mut state = PopupState::new(&config);
+
+        let mut first = make_notification(1);
+        first.actions = vec![("open".to_string(), "Open".to_string())];
+        state.enqueue(first, Duration::from_secs(5));
+        assert!(state.has_actions());
+
+        let mut second = make_notification(2);
+        second.actions = vec![
+            ("default".to_string(), String::new()),
+            ("reply".to_string(), "Reply".to_string()),
+            ("mute".to_string(), "Mute".to_string()),
+        ];
+        state.enqueue(second, Duration::from_secs(5));
+
+        assert_eq!(state.front_action(0), Some((2, "reply".to_string())));
+        assert_eq!(state.front_action(1), Some((2, "mute".to_string())));
+        assert_eq!(state.front_action(2), None);
+
+        state.dismiss(2);
+        assert_eq!(state.front_action(0), Some((1, "open".to_string())));
+    }
+
     #[test]
     fn enqueue_respects_max_visible() {
         let config = test_config(); // max_visible = 3
@@ -371,6 +530,90 @@ mod tests {
         assert_eq!(oldest.phase, PopupPhase::SlideOut);
     }
 
+    #[test]
+    fn enqueue_beyond_max_visible_waits_in_queue() {
+        let config = NotificationsModuleConfig {
+            popup_queue_size: 5,
+            ..test_config()
+        };
+        let mut state = PopupState::new(&config);
+
+        for i in 1..=5 {
+            state.enqueue(make_notification(i), Duration::from_secs(5));
+        }
+
+        // Nothing is pushed out; the extra two wait for a slot
+        assert_eq!(state.entries.len(), 3);
+        assert!(state.entries.iter().all(|e| e.phase == PopupPhase::SlideIn));
+
+        state.dismiss(1);
+        state.tick();
+
+        let visible: Vec<u32> = state
+            .entries
+            .iter()
+            .filter(|e| e.phase != PopupPhase::SlideOut)
+            .map(|e| e.notification.id)
+            .collect();
+        assert_eq!(visible, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn full_queue_drops_oldest_pending() {
+        let config = NotificationsModuleConfig {
+            popup_max_visible: 1,
+            popup_queue_size: 2,
+            ..test_config()
+        };
+        let mut state = PopupState::new(&config);
+
+        for i in 1..=4 {
+            state.enqueue(make_notification(i), Duration::from_secs(5));
+        }
+
+        let queued: Vec<u32> = state.pending.iter().map(|(n, _)| n.id).collect();
+        assert_eq!(queued, vec![3, 4]);
+    }
+
+    #[test]
+    fn shrinking_the_queue_drops_oldest_pending() {
+        let config = NotificationsModuleConfig {
+            popup_max_visible: 1,
+            popup_queue_size: 3,
+            ..test_config()
+        };
+        let mut state = PopupState::new(&config);
+
+        for i in 1..=4 {
+            state.enqueue(make_notification(i), Duration::from_secs(5));
+        }
+        state.update_config(&NotificationsModuleConfig {
+            popup_queue_size: 1,
+            ..config
+        });
+
+        let queued: Vec<u32> = state.pending.iter().map(|(n, _)| n.id).collect();
+        assert_eq!(queued, vec![4]);
+    }
+
+    #[test]
+    fn queue_keeps_popups_active_and_clear_empties_it() {
+        let config = NotificationsModuleConfig {
+            popup_max_visible: 1,
+            popup_queue_size: 2,
+            ..test_config()
+        };
+        let mut state = PopupState::new(&config);
+
+        state.enqueue(make_notification(1), Duration::from_secs(5));
+        state.enqueue(make_notification(2), Duration::from_secs(5));
+        state.entries.clear();
+        assert!(state.is_active());
+
+        state.clear();
+        assert!(!state.is_active());
+    }
+
     // --- PopupState: tick phase transitions ---
 
     #[test]
@@ -679,6 +922,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn target_surface_height_grows_with_large_icons() {
+        let config = NotificationsModuleConfig {
+            icon_size: 96,
+            ..test_config()
+        };
+        let mut state = PopupState::new(&config);
+
+        state.enqueue(make_notification(1), Duration::from_secs(5));
+
+        // 96 icon + 17 padding + 16 top + 16 bottom
+        let height = state.target_surface_height(16.0, 16.0);
+        assert!(
+            (height - 145.0).abs() < f32::EPSILON,
+            "expected 145, got {height}"
+        );
+    }
+
+    #[test]
+    fn target_surface_height_shrinks_in_compact_mode() {
+        let config = NotificationsModuleConfig {
+            popup_compact: true,
+            ..test_config()
+        };
+        let mut state = PopupState::new(&config);
+
+        state.enqueue(make_notification(1), Duration::from_secs(5));
+
+        // 48 compact entry + 16 top + 16 bottom
+        let height = state.target_surface_height(16.0, 16.0);
+        assert!(
+            (height - 80.0).abs() < f32::EPSILON,
+            "expected 80, got {height}"
+        );
+    }
+
     #[test]
     fn target_surface_height_is_zero_when_empty() {
         let config = test_config();
@@ -37,6 +37,19 @@ pub struct Device {
     pub ports: Vec<Port>,
 }
 
+impl Device {
+    /// Virtual devices (e.g. filter chains or network sinks) don't expose any port,
+    /// they're active as soon as they are the default one.
+    pub fn is_active(&self, default_name: &str) -> bool {
+        self.name == default_name && (self.ports.is_empty() || self.ports.iter().any(|p| p.active))
+    }
+
+    /// Number of entries needed to list every selectable port of the device.
+    pub fn entries_count(&self) -> usize {
+        self.ports.len().max(1)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Port {
     pub name: String,
@@ -96,7 +109,7 @@ pub trait Sinks {
 impl Sinks for Vec<Device> {
     fn get_icon(&self, default_sink: &str) -> StaticIcon {
         match self.iter().find_map(|s| {
-            if s.ports.iter().any(|p| p.active) && s.name == default_sink {
+            if s.is_active(default_sink) {
                 Some((s.is_mute, s.volume.get_volume()))
             } else {
                 None
@@ -124,7 +137,7 @@ pub trait Sources {
 impl Sources for Vec<Device> {
     fn get_icon(&self, default_source: &str) -> StaticIcon {
         match self.iter().find_map(|s| {
-            if s.ports.iter().any(|p| p.active) && s.name == default_source {
+            if s.is_active(default_source) {
                 Some(s.is_mute)
             } else {
                 None
@@ -263,11 +276,7 @@ impl ReadOnlyService for AudioService {
                     .sinks
                     .iter()
                     .find_map(|sink| {
-                        if sink
-                            .ports
-                            .iter()
-                            .any(|p| p.active && sink.name == self.server_info.default_sink)
-                        {
+                        if sink.is_active(&self.server_info.default_sink) {
                             Some(if sink.is_mute {
                                 0.
                             } else {
@@ -286,11 +295,7 @@ impl ReadOnlyService for AudioService {
                     .sources
                     .iter()
                     .find_map(|source| {
-                        if source
-                            .ports
-                            .iter()
-                            .any(|p| p.active && source.name == self.server_info.default_source)
-                        {
+                        if source.is_active(&self.server_info.default_source) {
                             Some(if source.is_mute {
                                 0.
                             } else {
@@ -309,11 +314,7 @@ impl ReadOnlyService for AudioService {
                     .sinks
                     .iter()
                     .find_map(|sink| {
-                        if sink
-                            .ports
-                            .iter()
-                            .any(|p| p.active && sink.name == self.server_info.default_sink)
-                        {
+                        if sink.is_active(&self.server_info.default_sink) {
                             Some(if sink.is_mute {
                                 0.
                             } else {
@@ -329,11 +330,7 @@ impl ReadOnlyService for AudioService {
                     .sources
                     .iter()
                     .find_map(|source| {
-                        if source
-                            .ports
-                            .iter()
-                            .any(|p| p.active && source.name == self.server_info.default_source)
-                        {
+                        if source.is_active(&self.server_info.default_source) {
                             Some(if source.is_mute {
                                 0.
                             } else {
@@ -738,10 +735,11 @@ impl PulseAudioServer {
     ) {
         match info {
             ListResult::Item(data) => {
-                if data
-                    .ports
-                    .iter()
-                    .any(|port| port.available != PortAvailable::No)
+                if data.ports.is_empty()
+                    || data
+                        .ports
+                        .iter()
+                        .any(|port| port.available != PortAvailable::No)
                 {
                     debug!("Adding sink data: {data:?}");
                     sinks.push(data.into());
@@ -770,6 +768,11 @@ impl PulseAudioServer {
                     .as_ref()
                     .map(|name| !name.contains("monitor"))
                     .unwrap_or_default()
+                    && (data.ports.is_empty()
+                        || data
+                            .ports
+                            .iter()
+                            .any(|port| port.available != PortAvailable::No))
                 {
                     debug!("Adding source data: {data:?}");
                     sources.push(data.into());
@@ -816,6 +819,10 @@ impl PulseAudioServer {
         let op = self.context.set_default_sink(name, |_| {});
         self.wait_for_response(op)?;
 
+        if port.is_empty() {
+            return Ok(());
+        }
+
         let op = self.introspector.set_sink_port_by_name(name, port, None);
         self.wait_for_response(op)
     }
@@ -824,6 +831,10 @@ impl PulseAudioServer {
         let op = self.context.set_default_source(name, |_| {});
         self.wait_for_response(op)?;
 
+        if port.is_empty() {
+            return Ok(());
+        }
+
         let op = self.introspector.set_source_port_by_name(name, port, None);
         self.wait_for_response(op)
     }
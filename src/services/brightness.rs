@@ -7,11 +7,14 @@ use iced::{
 use log::{debug, error, info, warn};
 use std::{
     any::TypeId,
-    fs,
     ops::Deref,
     path::{Path, PathBuf},
 };
-use tokio::io::{Interest, unix::AsyncFd};
+use tokio::{
+    fs,
+    io::{Interest, unix::AsyncFd},
+    process::Command,
+};
 use zbus::proxy;
 
 #[derive(Debug, Clone, Default)]
@@ -24,6 +27,8 @@ pub struct BrightnessData {
 pub struct BrightnessService {
     data: BrightnessData,
     device_path: PathBuf,
+    // Other backlight devices, with their max brightness, kept in sync with the main one
+    secondary_devices: Vec<(PathBuf, u32)>,
     conn: zbus::Connection,
 }
 
@@ -37,14 +42,14 @@ impl Deref for BrightnessService {
 
 impl BrightnessService {
     async fn get_max_brightness(device_path: &Path) -> anyhow::Result<u32> {
-        let max_brightness = fs::read_to_string(device_path.join("max_brightness"))?;
+        let max_brightness = fs::read_to_string(device_path.join("max_brightness")).await?;
         let max_brightness = max_brightness.trim().parse::<u32>()?;
 
         Ok(max_brightness)
     }
 
     async fn get_actual_brightness(device_path: &Path) -> anyhow::Result<u32> {
-        let actual_brightness = fs::read_to_string(device_path.join("actual_brightness"))?;
+        let actual_brightness = fs::read_to_string(device_path.join("actual_brightness")).await?;
         let actual_brightness = actual_brightness.trim().parse::<u32>()?;
 
         Ok(actual_brightness)
@@ -62,25 +67,38 @@ impl BrightnessService {
         })
     }
 
-    async fn init_service() -> anyhow::Result<(zbus::Connection, PathBuf)> {
-        let backlight_devices = Self::backlight_enumerate()?;
+    async fn init_service() -> anyhow::Result<(zbus::Connection, PathBuf, Vec<PathBuf>)> {
+        let mut backlight_devices = Self::backlight_enumerate()?
+            .into_iter()
+            .filter(|d| d.subsystem().and_then(|s| s.to_str()) == Some("backlight"))
+            .collect::<Vec<_>>();
+
+        // Same preference order as the kernel documentation suggests:
+        // firmware interfaces first, then platform specific ones, then raw drivers
+        backlight_devices.sort_by_key(|d| {
+            match d.attribute_value("type").and_then(|t| t.to_str()) {
+                Some("firmware") => 0,
+                Some("platform") => 1,
+                _ => 2,
+            }
+        });
 
-        match backlight_devices
+        let mut device_paths = backlight_devices
             .iter()
-            .find(|d| d.subsystem().and_then(|s| s.to_str()) == Some("backlight"))
-        {
-            Some(device) => {
-                let device_path = device.syspath().to_path_buf();
-
-                let conn = zbus::Connection::system().await?;
+            .map(|d| d.syspath().to_path_buf())
+            .collect::<Vec<_>>();
 
-                Ok((conn, device_path))
-            }
-            _ => {
-                warn!("No backlight devices found");
-                Err(anyhow::anyhow!("No backlight devices found"))
-            }
+        if device_paths.is_empty() {
+            warn!("No backlight devices found");
+            return Err(anyhow::anyhow!("No backlight devices found"));
         }
+
+        let device_path = device_paths.remove(0);
+        debug!("Using backlight device {device_path:?}, secondary devices: {device_paths:?}");
+
+        let conn = zbus::Connection::system().await?;
+
+        Ok((conn, device_path, device_paths))
     }
 
     pub async fn backlight_monitor_listener() -> anyhow::Result<AsyncFd<udev::MonitorSocket>> {
@@ -104,15 +122,24 @@ impl BrightnessService {
     async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
         match state {
             State::Init => match Self::init_service().await {
-                Ok((conn, device_path)) => {
+                Ok((conn, device_path, secondary_paths)) => {
                     let data = BrightnessService::initialize_data(&device_path).await;
 
                     match data {
                         Ok(data) => {
+                            let mut secondary_devices = Vec::new();
+                            for path in secondary_paths {
+                                match Self::get_max_brightness(&path).await {
+                                    Ok(max) if max > 0 => secondary_devices.push((path, max)),
+                                    _ => warn!("Skipping backlight device {path:?}"),
+                                }
+                            }
+
                             let _ = output
                                 .send(ServiceEvent::Init(BrightnessService {
                                     data,
                                     device_path: device_path.to_path_buf(),
+                                    secondary_devices,
                                     conn,
                                 }))
                                 .await;
@@ -213,18 +240,41 @@ impl BrightnessService {
         device_path: &Path,
         value: u32,
     ) -> anyhow::Result<()> {
-        let brightness_ctrl = BrightnessCtrlProxy::new(conn).await?;
         let device_name = device_path
             .iter()
             .next_back()
             .and_then(|d| d.to_str())
             .unwrap_or_default();
 
-        brightness_ctrl
-            .set_brightness("backlight", device_name, value)
+        let logind_result = async {
+            let brightness_ctrl = BrightnessCtrlProxy::new(conn).await?;
+
+            brightness_ctrl
+                .set_brightness("backlight", device_name, value)
+                .await
+        }
+        .await;
+
+        let Err(err) = logind_result else {
+            return Ok(());
+        };
+        debug!("Failed to set brightness through logind: {err}, writing to sysfs");
+
+        let Err(err) = fs::write(device_path.join("brightness"), value.to_string()).await else {
+            return Ok(());
+        };
+        debug!("Failed to write brightness to sysfs: {err}, falling back to brightnessctl");
+
+        let status = Command::new("brightnessctl")
+            .args(["--device", device_name, "set", &value.to_string()])
+            .status()
             .await?;
 
-        Ok(())
+        if status.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("brightnessctl exited with {status}"))
+        }
     }
 }
 
@@ -275,12 +325,27 @@ impl Service for BrightnessService {
             {
                 let conn = self.conn.clone();
                 let device_path = self.device_path.clone();
+                let secondary_devices = self.secondary_devices.clone();
+                let max = self.data.max.max(1);
 
                 async move {
                     match command {
                         BrightnessCommand::Set(v) => {
                             debug!("Setting brightness to {v}");
-                            let _ = BrightnessService::set_brightness(&conn, &device_path, v).await;
+                            if let Err(err) =
+                                BrightnessService::set_brightness(&conn, &device_path, v).await
+                            {
+                                error!("Failed to set brightness: {err}");
+                            }
+
+                            for (path, device_max) in secondary_devices {
+                                let value = (v as u64 * device_max as u64 / max as u64) as u32;
+                                if let Err(err) =
+                                    BrightnessService::set_brightness(&conn, &path, value).await
+                                {
+                                    warn!("Failed to set brightness of {path:?}: {err}");
+                                }
+                            }
 
                             v
                         }
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
-use zbus::{Result, proxy, zvariant::OwnedValue};
+use zbus::{
+    Result, proxy,
+    zvariant::{ObjectPath, OwnedValue},
+};
 
 #[proxy(
     interface = "org.mpris.MediaPlayer2.Player",
@@ -9,6 +12,7 @@ pub trait MprisPlayer {
     fn next(&self) -> Result<()>;
     fn play_pause(&self) -> Result<()>;
     fn previous(&self) -> Result<()>;
+    fn set_position(&self, track_id: &ObjectPath<'_>, position: i64) -> Result<()>;
 
     #[zbus(property)]
     fn playback_status(&self) -> Result<String>;
@@ -19,5 +23,9 @@ pub trait MprisPlayer {
     #[zbus(property)]
     fn volume(&self) -> Result<f64>;
     #[zbus(property)]
+    fn position(&self) -> Result<i64>;
+    #[zbus(property)]
+    fn can_seek(&self) -> Result<bool>;
+    #[zbus(property)]
     fn can_control(&self) -> Result<bool>;
 }
@@ -12,7 +12,10 @@ use iced::{
 };
 use log::{debug, error, info};
 use std::{any::TypeId, collections::HashMap, fmt::Display, ops::Deref, sync::Arc};
-use zbus::{fdo::DBusProxy, zvariant::OwnedValue};
+use zbus::{
+    fdo::DBusProxy,
+    zvariant::{OwnedObjectPath, OwnedValue},
+};
 
 mod dbus;
 
@@ -40,6 +43,9 @@ pub struct MprisPlayerData {
     pub metadata: Option<MprisPlayerMetadata>,
     pub volume: Option<f64>,
     pub state: PlaybackStatus,
+    /// Playback position in microseconds
+    pub position: Option<i64>,
+    pub can_seek: bool,
     proxy: MprisPlayerProxy<'static>,
 }
 
@@ -47,6 +53,11 @@ pub struct MprisPlayerData {
 pub struct MprisPlayerMetadata {
     pub artists: Option<Vec<String>>,
     pub title: Option<String>,
+    pub album: Option<String>,
+    /// Track length in microseconds
+    pub length: Option<i64>,
+    pub track_id: Option<OwnedObjectPath>,
+    pub art_url: Option<String>,
 }
 
 impl Display for MprisPlayerMetadata {
@@ -71,8 +82,38 @@ impl From<HashMap<String, OwnedValue>> for MprisPlayerMetadata {
             Some(v) => v.clone().try_into().ok(),
             None => None,
         };
+        let album = match value.get("xesam:album") {
+            Some(v) => v.clone().try_into().ok(),
+            None => None,
+        };
+        // Some players send the length as an unsigned integer
+        let length = value.get("mpris:length").and_then(|v| {
+            i64::try_from(v.clone())
+                .ok()
+                .or_else(|| u64::try_from(v.clone()).ok().map(|l| l as i64))
+        });
+        // and the track id as a plain string instead of an object path
+        let track_id = value.get("mpris:trackid").and_then(|v| {
+            OwnedObjectPath::try_from(v.clone()).ok().or_else(|| {
+                String::try_from(v.clone())
+                    .ok()
+                    .and_then(|s| OwnedObjectPath::try_from(s).ok())
+            })
+        });
+
+        let art_url = match value.get("mpris:artUrl") {
+            Some(v) => v.clone().try_into().ok(),
+            None => None,
+        };
 
-        Self { artists, title }
+        Self {
+            artists,
+            title,
+            album,
+            length,
+            track_id,
+            art_url,
+        }
     }
 }
 
@@ -161,12 +202,16 @@ impl MprisPlayerService {
                         .await
                         .map(PlaybackStatus::from)
                         .unwrap_or_default();
+                    let position = proxy.position().await.ok();
+                    let can_seek = proxy.can_seek().await.unwrap_or_default();
 
                     Some(MprisPlayerData {
                         service: s.to_string(),
                         metadata,
                         volume,
                         state,
+                        position,
+                        can_seek,
                         proxy,
                     })
                 }
@@ -179,6 +224,18 @@ impl MprisPlayerService {
         .collect()
     }
 
+    /// Re-read the players data, used to keep the playback position up to date
+    /// since players don't signal position changes.
+    pub fn refresh(&self) -> iced::Task<ServiceEvent<Self>> {
+        let names: Vec<String> = self.data.iter().map(|d| d.service.clone()).collect();
+        let conn = self.conn.clone();
+
+        iced::Task::perform(
+            async move { Self::get_mpris_player_data(&conn, &names).await },
+            ServiceEvent::Update,
+        )
+    }
+
     async fn events(conn: &zbus::Connection) -> anyhow::Result<impl Stream<Item = ()> + use<>> {
         let dbus = DBusProxy::new(conn).await?;
         let data = Self::initialize_data(conn).await?;
@@ -353,6 +410,8 @@ pub enum PlayerCommand {
     PlayPause,
     Next,
     Volume(f64),
+    /// Seek to the given position in microseconds
+    SetPosition(i64),
 }
 
 impl Service for MprisPlayerService {
@@ -365,6 +424,7 @@ impl Service for MprisPlayerService {
 
             if let Some(s) = s {
                 let mpris_player_proxy = s.proxy.clone();
+                let track_id = s.metadata.as_ref().and_then(|m| m.track_id.clone());
                 let conn = self.conn.clone();
                 iced::Task::perform(
                     async move {
@@ -393,6 +453,14 @@ impl Service for MprisPlayerService {
                                     .await
                                     .inspect_err(|e| error!("Set volume command error: {e}"));
                             }
+                            PlayerCommand::SetPosition(position) => {
+                                if let Some(track_id) = track_id {
+                                    let _ = mpris_player_proxy
+                                        .set_position(&track_id, position)
+                                        .await
+                                        .inspect_err(|e| error!("Set position command error: {e}"));
+                                }
+                            }
                         }
                         Self::get_mpris_player_data(&conn, &names).await
                     },
@@ -70,6 +70,8 @@ pub enum NetworkEvent {
     WirelessAccessPoint(Vec<AccessPoint>),
     Strength((String, u8)),
     RequestPasswordForSSID(String),
+    /// SSID, error and the known connections, which a failed attempt can still change
+    ConnectionFailed(String, String, Vec<KnownConnection>),
     ScanningNearbyWifi,
 }
 
@@ -190,7 +192,8 @@ impl ReadOnlyService for NetworkService {
             NetworkEvent::ActiveConnections(active_connections) => {
                 self.data.active_connections = active_connections;
             }
-            NetworkEvent::KnownConnections(known_connections) => {
+            NetworkEvent::KnownConnections(known_connections)
+            | NetworkEvent::ConnectionFailed(_, _, known_connections) => {
                 self.data.known_connections = known_connections;
             }
             NetworkEvent::Strength((ssid, new_strength)) => {
@@ -555,14 +558,23 @@ impl Service for NetworkService {
             }
             NetworkCommand::SelectAccessPoint((access_point, password)) => Task::perform(
                 async move {
-                    bc.select_access_point(&access_point, password)
-                        .await
-                        .unwrap_or_default();
-                    bc.known_connections().await.unwrap_or_default()
-                },
-                |known_connections| {
-                    ServiceEvent::Update(NetworkEvent::KnownConnections(known_connections))
+                    let result = bc.select_access_point(&access_point, password).await;
+                    let known_connections = bc.known_connections().await.unwrap_or_default();
+
+                    match result {
+                        Ok(()) => NetworkEvent::KnownConnections(known_connections),
+                        Err(err) => {
+                            error!("Failed to connect to {}: {err}", access_point.ssid);
+
+                            NetworkEvent::ConnectionFailed(
+                                access_point.ssid,
+                                err.to_string(),
+                                known_connections,
+                            )
+                        }
+                    }
                 },
+                ServiceEvent::Update,
             ),
             NetworkCommand::ToggleVpn(vpn) => {
                 let mut active_vpn = self.active_connections.iter().find_map(|kc| match kc {
@@ -1,57 +1,83 @@
 use log::{debug, info};
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicI32, Ordering},
+    },
+};
 use tokio::sync::mpsc::Sender;
-use zbus::{interface, object_server::SignalEmitter, zvariant::Value};
+use zbus::{interface, message::Header, object_server::SignalEmitter, zvariant::Value};
 
-use crate::utils::strip_markup_tags;
+use crate::utils::{launcher::desktop_entry_exec, strip_markup_tags};
 
-use super::{CloseReason, Notification, NotificationEvent, Urgency, resolve_icon};
+use super::{
+    CloseReason, Notification, NotificationEvent, NotificationSound, Urgency, resolve_icon,
+};
 
 pub const BUS_NAME: &str = "org.freedesktop.Notifications";
 pub const OBJECT_PATH: &str = "/org/freedesktop/Notifications";
 
+/// Settings read on every `Notify`, shared with the service so config reloads reach the
+/// running daemon.
+#[derive(Debug, Default)]
+pub struct DaemonConfig {
+    pub default_timeout: AtomicI32,
+    /// Look up the sender's desktop entry, only needed to open the app on click
+    pub resolve_desktop_exec: AtomicBool,
+    /// Ask the bus for the sending process when the `sender-pid` hint is missing
+    pub resolve_sender: AtomicBool,
+}
+
+/// Arguments of a `Notify` call, with `expire_timeout` already resolved with
+/// [`NotificationDaemon::resolve_timeout`].
+struct NotifyArgs<'a, 'v> {
+    app_name: &'a str,
+    app_icon: &'a str,
+    summary: &'a str,
+    body: &'a str,
+    actions: &'a [&'a str],
+    hints: &'a HashMap<&'a str, Value<'v>>,
+    expire_timeout: i32,
+}
+
 pub struct NotificationDaemon {
     next_id: u32,
     sender: Sender<NotificationEvent>,
-    default_timeout: i32,
+    config: Arc<DaemonConfig>,
 }
 
 impl NotificationDaemon {
-    pub fn new(sender: Sender<NotificationEvent>, default_timeout: i32) -> Self {
+    pub fn new(sender: Sender<NotificationEvent>, config: Arc<DaemonConfig>) -> Self {
         Self {
             next_id: 1,
             sender,
-            default_timeout,
+            config,
         }
     }
-}
 
-#[interface(name = "org.freedesktop.Notifications")]
-impl NotificationDaemon {
-    fn get_capabilities(&self) -> Vec<&str> {
-        vec!["body", "body-markup", "actions"]
-    }
-
-    #[allow(clippy::too_many_arguments)]
-    async fn notify(
-        &mut self,
-        app_name: &str,
-        replaces_id: u32,
-        app_icon: &str,
-        summary: &str,
-        body: &str,
-        actions: Vec<&str>,
-        hints: HashMap<&str, Value<'_>>,
-        expire_timeout: i32,
-        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
-    ) -> u32 {
-        let id = if replaces_id > 0 {
+    /// Per spec a non-zero `replaces_id` reuses that id, otherwise a fresh one is allocated.
+    fn assign_id(&mut self, replaces_id: u32) -> u32 {
+        if replaces_id > 0 {
             replaces_id
         } else {
             let id = self.next_id;
             self.next_id = self.next_id.wrapping_add(1).max(1);
             id
-        };
+        }
+    }
+
+    /// Build a `Notification` from the raw `Notify` arguments.
+    fn build_notification(id: u32, args: NotifyArgs<'_, '_>) -> Notification {
+        let NotifyArgs {
+            app_name,
+            app_icon,
+            summary,
+            body,
+            actions,
+            hints,
+            expire_timeout,
+        } = args;
 
         let urgency = hints
             .get("urgency")
@@ -74,6 +100,30 @@ impl NotificationDaemon {
             })
             .unwrap_or(false);
 
+        let sync_key = ["x-canonical-private-synchronous", "synchronous"]
+            .iter()
+            .find_map(|key| match hints.get(key) {
+                Some(Value::Str(s)) => Some(s.to_string()),
+                _ => None,
+            });
+
+        let sender_pid = match hints.get("sender-pid") {
+            Some(Value::I64(pid)) => u32::try_from(*pid).ok(),
+            Some(Value::U64(pid)) => u32::try_from(*pid).ok(),
+            Some(Value::I32(pid)) => u32::try_from(*pid).ok(),
+            Some(Value::U32(pid)) => Some(*pid),
+            _ => None,
+        };
+
+        let sound = match (hints.get("sound-file"), hints.get("sound-name")) {
+            (Some(Value::Str(path)), _) => Some(NotificationSound::File(path.to_string())),
+            (_, Some(Value::Str(name))) => Some(NotificationSound::Name(name.to_string())),
+            _ => None,
+        };
+        let suppress_sound = matches!(hints.get("suppress-sound"), Some(Value::Bool(true)));
+
+        let app_name = Self::derive_app_name(app_name, desktop_entry(hints), app_icon);
+
         let parsed_actions: Vec<(String, String)> = actions
             .chunks(2)
             .filter_map(|chunk| {
@@ -88,9 +138,9 @@ impl NotificationDaemon {
         let icon = resolve_icon(app_icon);
         let clean_body = strip_markup_tags(body);
 
-        let notification = Notification {
+        Notification {
             id,
-            app_name: app_name.to_string(),
+            app_name,
             icon,
             summary: summary.to_string(),
             body: clean_body,
@@ -98,7 +148,101 @@ impl NotificationDaemon {
             urgency,
             timestamp: chrono::Local::now(),
             transient,
-        };
+            sync_key,
+            expire_timeout: if urgency == Urgency::Critical {
+                0
+            } else {
+                expire_timeout
+            },
+            sender_pid,
+            sender_process: None,
+            sound,
+            suppress_sound,
+            desktop_exec: None,
+        }
+    }
+
+    /// Ask the bus daemon which process owns the connection that sent the message.
+    async fn bus_sender_pid(connection: &zbus::Connection, header: &Header<'_>) -> Option<u32> {
+        let sender = header.sender()?.to_owned();
+        let proxy = zbus::fdo::DBusProxy::new(connection).await.ok()?;
+        proxy
+            .get_connection_unix_process_id(sender.into())
+            .await
+            .ok()
+    }
+
+    /// Senders may leave `app_name` empty; derive one from the `desktop-entry` hint or a
+    /// named (non-path) `app_icon` so headers don't end up blank.
+    fn derive_app_name(app_name: &str, desktop_entry: Option<&str>, app_icon: &str) -> String {
+        if !app_name.trim().is_empty() {
+            return app_name.to_string();
+        }
+
+        desktop_entry
+            .map(|entry| entry.trim_end_matches(".desktop"))
+            .map(|entry| entry.rsplit('.').next().unwrap_or(entry))
+            .filter(|name| !name.is_empty())
+            .or_else(|| Some(app_icon).filter(|icon| !icon.is_empty() && !icon.contains('/')))
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Per spec: -1 = server decides, 0 = never expire, >0 = timeout in ms
+    fn resolve_timeout(&self, expire_timeout: i32) -> i32 {
+        match expire_timeout {
+            t if t < 0 => self.config.default_timeout.load(Ordering::Relaxed),
+            0 => 0, // never expire
+            t => t,
+        }
+    }
+}
+
+#[interface(name = "org.freedesktop.Notifications")]
+impl NotificationDaemon {
+    fn get_capabilities(&self) -> Vec<&str> {
+        vec!["body", "body-markup", "actions"]
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn notify(
+        &mut self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: Vec<&str>,
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+        #[zbus(header)] header: Header<'_>,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+    ) -> u32 {
+        let id = self.assign_id(replaces_id);
+        let mut notification = Self::build_notification(
+            id,
+            NotifyArgs {
+                app_name,
+                app_icon,
+                summary,
+                body,
+                actions: &actions,
+                hints: &hints,
+                expire_timeout: self.resolve_timeout(expire_timeout),
+            },
+        );
+        let timeout_ms = notification.expire_timeout;
+
+        if self.config.resolve_sender.load(Ordering::Relaxed) {
+            if notification.sender_pid.is_none() {
+                notification.sender_pid = Self::bus_sender_pid(emitter.connection(), &header).await;
+            }
+            notification.sender_process = notification.sender_pid.and_then(process_name);
+        }
+
+        if self.config.resolve_desktop_exec.load(Ordering::Relaxed) {
+            notification.desktop_exec = desktop_entry(&hints).and_then(desktop_entry_exec);
+        }
 
         info!("Notification received: id={id}, summary={summary}");
         debug!("Notification details: {notification:?}");
@@ -109,40 +253,31 @@ impl NotificationDaemon {
             .await;
 
         // Auto-expiry: spawn a timer to close the notification
-        // Per spec: -1 = server decides, 0 = never expire, >0 = timeout in ms
-        if urgency != Urgency::Critical {
-            let timeout_ms = match expire_timeout {
-                t if t < 0 => self.default_timeout,
-                0 => 0, // never expire
-                t => t,
-            };
-
-            if timeout_ms > 0 {
-                let sender = self.sender.clone();
-                let emitter_conn = emitter
-                    .connection()
-                    .clone();
-                tokio::spawn(async move {
-                    tokio::time::sleep(std::time::Duration::from_millis(timeout_ms as u64)).await;
-                    let _ = sender
-                        .send(NotificationEvent::Closed(id, CloseReason::Expired))
-                        .await;
-                    // Emit the D-Bus signal from the spawned task
-                    if let Ok(iface) = emitter_conn
-                        .object_server()
-                        .interface::<_, NotificationDaemon>(OBJECT_PATH)
-                        .await
-                    {
-                        let emitter = iface.signal_emitter();
-                        let _ = NotificationDaemon::notification_closed(
-                            emitter,
-                            id,
-                            CloseReason::Expired as u32,
-                        )
-                        .await;
-                    }
-                });
-            }
+        if timeout_ms > 0 {
+            let sender = self.sender.clone();
+            let emitter_conn = emitter
+                .connection()
+                .clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(timeout_ms as u64)).await;
+                let _ = sender
+                    .send(NotificationEvent::Closed(id, CloseReason::Expired))
+                    .await;
+                // Emit the D-Bus signal from the spawned task
+                if let Ok(iface) = emitter_conn
+                    .object_server()
+                    .interface::<_, NotificationDaemon>(OBJECT_PATH)
+                    .await
+                {
+                    let emitter = iface.signal_emitter();
+                    let _ = NotificationDaemon::notification_closed(
+                        emitter,
+                        id,
+                        CloseReason::Expired as u32,
+                    )
+                    .await;
+                }
+            });
         }
 
         id
@@ -179,3 +314,218 @@ impl NotificationDaemon {
         action_key: &str,
     ) -> zbus::Result<()>;
 }
+
+fn desktop_entry<'a>(hints: &'a HashMap<&str, Value<'_>>) -> Option<&'a str> {
+    match hints.get("desktop-entry") {
+        Some(Value::Str(s)) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+fn process_name(pid: u32) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{pid}/comm"))
+        .ok()
+        .map(|comm| comm.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NotificationsModuleConfig;
+
+    fn daemon(config: &NotificationsModuleConfig) -> NotificationDaemon {
+        let (tx, _rx) = tokio::sync::mpsc::channel(1);
+        NotificationDaemon::new(
+            tx,
+            Arc::new(DaemonConfig {
+                default_timeout: AtomicI32::new(config.default_timeout),
+                ..Default::default()
+            }),
+        )
+    }
+
+    #[test]
+    fn server_decided_timeout_uses_config() {
+        let config = NotificationsModuleConfig {
+            default_timeout: 12000,
+            ..Default::default()
+        };
+
+        assert_eq!(daemon(&config).resolve_timeout(-1), 12000);
+    }
+
+    #[test]
+    fn explicit_timeouts_are_kept() {
+        let daemon = daemon(&NotificationsModuleConfig::default());
+
+        assert_eq!(daemon.resolve_timeout(0), 0);
+        assert_eq!(daemon.resolve_timeout(3000), 3000);
+    }
+
+    #[test]
+    fn reloaded_timeout_reaches_daemon() {
+        let daemon = daemon(&NotificationsModuleConfig::default());
+        daemon.config.default_timeout.store(800, Ordering::Relaxed);
+
+        assert_eq!(daemon.resolve_timeout(-1), 800);
+    }
+
+    fn args<'a, 'v>(
+        actions: &'a [&'a str],
+        hints: &'a HashMap<&'a str, Value<'v>>,
+    ) -> NotifyArgs<'a, 'v> {
+        NotifyArgs {
+            app_name: "app",
+            app_icon: "",
+            summary: "summary",
+            body: "body",
+            actions,
+            hints,
+            expire_timeout: 5000,
+        }
+    }
+
+    fn build(actions: &[&str], hints: &HashMap<&str, Value<'_>>) -> Notification {
+        NotificationDaemon::build_notification(1, args(actions, hints))
+    }
+
+    fn urgency_of(value: Value<'_>) -> Urgency {
+        build(&[], &HashMap::from([("urgency", value)])).urgency
+    }
+
+    #[test]
+    fn urgency_hint_is_parsed() {
+        assert_eq!(urgency_of(Value::U8(0)), Urgency::Low);
+        assert_eq!(urgency_of(Value::U8(1)), Urgency::Normal);
+        assert_eq!(urgency_of(Value::U8(2)), Urgency::Critical);
+    }
+
+    #[test]
+    fn invalid_or_missing_urgency_is_normal() {
+        assert_eq!(urgency_of(Value::U8(7)), Urgency::Normal);
+        assert_eq!(urgency_of(Value::from("critical")), Urgency::Normal);
+        assert_eq!(build(&[], &HashMap::new()).urgency, Urgency::Normal);
+    }
+
+    #[test]
+    fn transient_hint_is_parsed() {
+        let transient = |value| build(&[], &HashMap::from([("transient", value)])).transient;
+
+        assert!(transient(Value::Bool(true)));
+        assert!(!transient(Value::Bool(false)));
+        assert!(!build(&[], &HashMap::new()).transient);
+    }
+
+    #[test]
+    fn stored_timeout_is_the_effective_one() {
+        let critical = HashMap::from([("urgency", Value::U8(2))]);
+
+        assert_eq!(build(&[], &HashMap::new()).expire_timeout, 5000);
+        assert_eq!(build(&[], &critical).expire_timeout, 0);
+    }
+
+    #[test]
+    fn sender_pid_hint_is_read_without_resolving_the_process() {
+        let pid = std::process::id();
+        let hints = HashMap::from([("sender-pid", Value::I64(i64::from(pid)))]);
+        let notification = build(&[], &hints);
+
+        assert_eq!(notification.sender_pid, Some(pid));
+        assert_eq!(notification.sender_process, None);
+        assert_eq!(build(&[], &HashMap::new()).sender_pid, None);
+    }
+
+    #[test]
+    fn pid_is_resolved_to_a_process_name() {
+        assert!(process_name(std::process::id()).is_some_and(|name| !name.is_empty()));
+    }
+
+    #[test]
+    fn sound_hints_are_parsed() {
+        let hints = HashMap::from([
+            ("sound-name", Value::from("message-new-email")),
+            ("sound-file", Value::from("/usr/share/sounds/chime.oga")),
+            ("suppress-sound", Value::Bool(true)),
+        ]);
+        let notification = build(&[], &hints);
+
+        // A file takes precedence over a theme name
+        assert_eq!(
+            notification.sound,
+            Some(NotificationSound::File(
+                "/usr/share/sounds/chime.oga".to_string()
+            ))
+        );
+        assert!(notification.suppress_sound);
+        assert!(!build(&[], &HashMap::new()).suppress_sound);
+    }
+
+    #[test]
+    fn actions_are_paired_and_odd_trailing_key_is_dropped() {
+        let notification = build(
+            &["default", "", "reply", "Reply", "dangling"],
+            &HashMap::new(),
+        );
+
+        assert_eq!(
+            notification.actions,
+            vec![
+                ("default".to_string(), String::new()),
+                ("reply".to_string(), "Reply".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn body_markup_is_stripped() {
+        let hints = HashMap::new();
+        let notification = NotificationDaemon::build_notification(
+            1,
+            NotifyArgs {
+                body: "<b>bold</b> text",
+                ..args(&[], &hints)
+            },
+        );
+
+        assert_eq!(notification.body, "bold text");
+    }
+
+    #[test]
+    fn replaces_id_is_reused_and_new_ids_increment() {
+        let mut daemon = daemon(&NotificationsModuleConfig::default());
+
+        assert_eq!(daemon.assign_id(0), 1);
+        assert_eq!(daemon.assign_id(0), 2);
+        assert_eq!(daemon.assign_id(1), 1);
+        assert_eq!(daemon.assign_id(0), 3);
+    }
+
+    #[test]
+    fn id_allocation_skips_zero_on_wrap() {
+        let mut daemon = daemon(&NotificationsModuleConfig::default());
+        daemon.next_id = u32::MAX;
+
+        assert_eq!(daemon.assign_id(0), u32::MAX);
+        assert_eq!(daemon.assign_id(0), 1);
+    }
+
+    #[test]
+    fn empty_app_name_is_derived_from_hints() {
+        assert_eq!(
+            NotificationDaemon::derive_app_name("Firefox", Some("org.mozilla.firefox"), ""),
+            "Firefox"
+        );
+        assert_eq!(
+            NotificationDaemon::derive_app_name("", Some("org.gnome.Nautilus"), "folder"),
+            "Nautilus"
+        );
+        assert_eq!(
+            NotificationDaemon::derive_app_name("", None, "spotify"),
+            "spotify"
+        );
+        assert_eq!(
+            NotificationDaemon::derive_app_name("", None, "/tmp/icon.png"),
+            ""
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use super::{ReadOnlyService, ServiceEvent};
-use dbus::{BUS_NAME, NotificationDaemon, OBJECT_PATH};
+use crate::config::NotificationsModuleConfig;
+use dbus::{BUS_NAME, DaemonConfig, NotificationDaemon, OBJECT_PATH};
 use freedesktop_icons::lookup;
 use iced::{
     Subscription,
@@ -9,11 +10,25 @@ use iced::{
 };
 use linicon_theme::get_icon_theme;
 use log::{debug, error, info, warn};
-use std::{any::TypeId, path::Path};
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet, VecDeque},
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicI32, Ordering},
+    },
+};
 use zbus::fdo::RequestNameFlags;
 
 pub mod dbus;
 
+// How many dismissed notifications can be restored
+const RECENTLY_CLOSED_CAP: usize = 5;
+
+// Plays notification sounds, shipped by libcanberra
+const SOUND_PLAYER: &str = "canberra-gtk-play";
+
 #[derive(Debug, Clone)]
 pub enum NotificationIcon {
     Image(image::Handle),
@@ -31,9 +46,168 @@ pub struct Notification {
     pub urgency: Urgency,
     pub timestamp: chrono::DateTime<chrono::Local>,
     pub transient: bool,
+    /// Value of the `x-canonical-private-synchronous` hint, used by OSDs
+    pub sync_key: Option<String>,
+    /// Effective timeout in ms: the sender's `-1` is resolved to the configured default and
+    /// critical notifications never expire. `0` means the notification stays until dismissed.
+    pub expire_timeout: i32,
+    /// Pid of the sending process, from the `sender-pid` hint or the D-Bus connection
+    pub sender_pid: Option<u32>,
+    /// Process name of `sender_pid` as found in `/proc/<pid>/comm`
+    pub sender_process: Option<String>,
+    /// Sound requested with the `sound-name` or `sound-file` hint
+    pub sound: Option<NotificationSound>,
+    /// Set by the `suppress-sound` hint
+    pub suppress_sound: bool,
+    /// Command to launch the sender, from the `Exec` line of its `desktop-entry` hint
+    pub desktop_exec: Option<String>,
 }
 
+/// Popups raised by ashell itself instead of a D-Bus sender. Their ids sit at the top of
+/// the id range, far from the ones handed out by the daemon.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InternalPopup {
+    LowBattery = u32::MAX,
+    /// Reports an invalid config file on reload
+    ConfigError = u32::MAX - 1,
+    /// Announces media track changes, a new track replaces the previous popup
+    TrackChange = u32::MAX - 2,
+}
+
+impl InternalPopup {
+    pub fn id(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        [Self::LowBattery, Self::ConfigError, Self::TrackChange]
+            .into_iter()
+            .find(|popup| popup.id() == id)
+    }
+}
+
+impl Notification {
+    /// A popup raised by ashell itself. It is never stored in the notification list and stays
+    /// until dismissed or replaced by the next popup of the same kind.
+    pub fn internal(
+        popup: InternalPopup,
+        app_name: String,
+        summary: String,
+        body: String,
+        icon: Option<NotificationIcon>,
+    ) -> Self {
+        Self {
+            id: popup.id(),
+            app_name,
+            icon,
+            summary,
+            body,
+            actions: Vec::new(),
+            urgency: match popup {
+                InternalPopup::TrackChange => Urgency::Low,
+                InternalPopup::LowBattery | InternalPopup::ConfigError => Urgency::Critical,
+            },
+            timestamp: chrono::Local::now(),
+            transient: true,
+            sync_key: None,
+            expire_timeout: 0,
+            sender_pid: None,
+            sender_process: None,
+            sound: None,
+            suppress_sound: false,
+            desktop_exec: None,
+        }
+    }
+
+    /// Whether `other` takes the place of this notification, either through
+    /// `replaces_id` or by sharing the same synchronous key.
+    pub fn is_replaced_by(&self, other: &Notification) -> bool {
+        self.id == other.id || (self.sync_key.is_some() && self.sync_key == other.sync_key)
+    }
+
+    /// The app name to show in headers, `fallback` when the sender left it blank.
+    pub fn display_app_name<'a>(&'a self, fallback: &'a str) -> &'a str {
+        if self.app_name.trim().is_empty() {
+            fallback
+        } else {
+            &self.app_name
+        }
+    }
+}
+
+#[cfg(test)]
+impl Notification {
+    /// A plain notification for tests, override fields with struct update syntax.
+    pub fn test(id: u32) -> Self {
+        Self {
+            id,
+            app_name: format!("App{id}"),
+            icon: None,
+            summary: format!("Title {id}"),
+            body: format!("Body {id}"),
+            actions: Vec::new(),
+            urgency: Urgency::Normal,
+            timestamp: chrono::Local::now(),
+            transient: false,
+            sync_key: None,
+            expire_timeout: 5000,
+            sender_pid: None,
+            sender_process: None,
+            sound: None,
+            suppress_sound: false,
+            desktop_exec: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationSound {
+    /// Name from the freedesktop sound theme
+    Name(String),
+    /// Path to a sound file
+    File(String),
+}
+
+impl NotificationSound {
+    /// Config values are theme sound names unless they are absolute paths; empty means silence.
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim() {
+            "" => None,
+            path if path.starts_with('/') => Some(Self::File(path.to_string())),
+            name => Some(Self::Name(name.to_string())),
+        }
+    }
+
+    /// Play the sound in the background through libcanberra.
+    pub fn play(&self) {
+        let (flag, value) = match self {
+            Self::Name(name) => ("--id", name.clone()),
+            Self::File(path) => ("--file", path.clone()),
+        };
+        tokio::spawn(async move {
+            match tokio::process::Command::new(SOUND_PLAYER)
+                .arg(flag)
+                .arg(&value)
+                .status()
+                .await
+            {
+                Ok(status) if !status.success() => {
+                    warn!("{SOUND_PLAYER} failed to play notification sound {value}: {status}");
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    error!(
+                        "Can't play notification sounds, {SOUND_PLAYER} is not installed. It's usually shipped by the libcanberra package"
+                    );
+                }
+                Err(e) => error!("Failed to play notification sound {value}: {e}"),
+            }
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Urgency {
     Low,
     Normal,
@@ -98,18 +272,129 @@ pub enum NotificationEvent {
 pub struct NotificationService {
     pub notifications: Vec<Notification>,
     pub max_notifications: usize,
+    pub recently_closed: VecDeque<Notification>,
+    /// Pinned notifications are kept by "Clear all" and never trimmed by `max_notifications`
+    pinned: HashSet<u32>,
+    /// Decides which apps' transient notifications are still kept in the list
+    config: NotificationsModuleConfig,
+    daemon_config: Arc<DaemonConfig>,
     conn: Option<zbus::Connection>,
 }
 
 impl NotificationService {
-    fn new(max_notifications: usize, conn: zbus::Connection) -> Self {
+    fn new(
+        max_notifications: usize,
+        daemon_config: Arc<DaemonConfig>,
+        conn: zbus::Connection,
+    ) -> Self {
         Self {
             notifications: Vec::new(),
             max_notifications,
+            recently_closed: VecDeque::new(),
+            pinned: HashSet::new(),
+            config: NotificationsModuleConfig::default(),
+            daemon_config,
             conn: Some(conn),
         }
     }
 
+    /// Apply new limits to the running service without restarting the daemon.
+    pub fn update_config(&mut self, config: &NotificationsModuleConfig) {
+        self.max_notifications = config.max_notifications;
+        self.dismiss_overflow();
+        self.daemon_config
+            .default_timeout
+            .store(config.default_timeout, Ordering::Relaxed);
+        self.daemon_config
+            .resolve_desktop_exec
+            .store(config.open_app_on_click, Ordering::Relaxed);
+        self.daemon_config
+            .resolve_sender
+            .store(config.show_sender, Ordering::Relaxed);
+        self.config = config.clone();
+    }
+
+    pub fn is_pinned(&self, id: u32) -> bool {
+        self.pinned.contains(&id)
+    }
+
+    pub fn toggle_pin(&mut self, id: u32) {
+        if !self.pinned.remove(&id) && self.notifications.iter().any(|n| n.id == id) {
+            self.pinned.insert(id);
+        }
+    }
+
+    /// Remove a notification from the list, keeping it around so it can be restored.
+    pub fn dismiss(&mut self, id: u32) {
+        if let Some(pos) = self.notifications.iter().position(|n| n.id == id) {
+            let notification = self.notifications.remove(pos);
+            self.pinned.remove(&id);
+            self.recently_closed.push_front(notification);
+            self.recently_closed.truncate(RECENTLY_CLOSED_CAP);
+        }
+    }
+
+    /// Drop the oldest unpinned notifications beyond `max_notifications`, telling their
+    /// senders they were closed. The user didn't dismiss them, so they can't be restored.
+    fn dismiss_overflow(&mut self) {
+        let mut excess = self
+            .notifications
+            .len()
+            .saturating_sub(self.max_notifications);
+        if excess == 0 {
+            return;
+        }
+        let mut overflow = Vec::new();
+        // The list is newest first, so walk it backwards to trim the oldest ones
+        for pos in (0..self.notifications.len()).rev() {
+            if excess == 0 {
+                break;
+            }
+            if !self.pinned.contains(&self.notifications[pos].id) {
+                overflow.push(self.notifications.remove(pos).id);
+                excess -= 1;
+            }
+        }
+
+        if let Some(conn) = self.conn.clone() {
+            tokio::spawn(async move {
+                for id in overflow {
+                    emit_closed_signal(&conn, id, CloseReason::Dismissed).await;
+                }
+            });
+        }
+    }
+
+    /// Dismiss every notification but the pinned ones.
+    pub fn dismiss_all(&mut self) {
+        let ids: Vec<u32> = self
+            .notifications
+            .iter()
+            .rev()
+            .map(|n| n.id)
+            .filter(|id| !self.pinned.contains(id))
+            .collect();
+        for id in ids {
+            self.dismiss(id);
+        }
+    }
+
+    /// Put the most recently dismissed notification back into the list.
+    ///
+    /// Its sender was already told it was closed and won't handle its actions anymore,
+    /// so the restored notification has none.
+    pub fn restore_last(&mut self) -> Option<u32> {
+        let mut notification = self.recently_closed.pop_front()?;
+        let id = notification.id;
+        notification.actions.clear();
+
+        self.notifications.retain(|n| n.id != id);
+        self.notifications.insert(0, notification);
+        self.dismiss_overflow();
+
+        Some(id)
+    }
+
     pub async fn emit_action_invoked_signal(&self, id: u32, action_key: &str) {
         if let Some(conn) = &self.conn {
             let _ = conn
@@ -126,19 +411,50 @@ impl NotificationService {
 
     pub async fn emit_closed_signal(&self, id: u32, reason: CloseReason) {
         if let Some(conn) = &self.conn {
-            let _ = conn
-                .emit_signal(
-                    None::<zbus::names::BusName>,
-                    OBJECT_PATH,
-                    "org.freedesktop.Notifications",
-                    "NotificationClosed",
-                    &(id, reason as u32),
-                )
-                .await;
+            emit_closed_signal(conn, id, reason).await;
         }
     }
 }
 
+async fn emit_closed_signal(conn: &zbus::Connection, id: u32, reason: CloseReason) {
+    let _ = conn
+        .emit_signal(
+            None::<zbus::names::BusName>,
+            OBJECT_PATH,
+            "org.freedesktop.Notifications",
+            "NotificationClosed",
+            &(id, reason as u32),
+        )
+        .await;
+}
+
+/// Send a sample notification to the daemon owning the notifications bus name, for
+/// `--notify-test`.
+pub async fn send_test_notification() -> zbus::Result<u32> {
+    let conn = zbus::Connection::session().await?;
+    let hints: HashMap<&str, zbus::zvariant::Value> = HashMap::new();
+    let reply = conn
+        .call_method(
+            Some(BUS_NAME),
+            OBJECT_PATH,
+            Some(BUS_NAME),
+            "Notify",
+            &(
+                "ashell",
+                0u32,
+                "dialog-information",
+                "ashell test notification",
+                "If you can read this, notifications are working.",
+                Vec::<&str>::new(),
+                hints,
+                -1i32,
+            ),
+        )
+        .await?;
+
+    reply.body().deserialize()
+}
+
 enum State {
     Init {
         max_notifications: usize,
@@ -158,7 +474,11 @@ impl NotificationService {
                 info!("Initializing notification service");
 
                 let (tx, rx) = tokio::sync::mpsc::channel::<NotificationEvent>(100);
-                let daemon = NotificationDaemon::new(tx, default_timeout);
+                let daemon_config = Arc::new(DaemonConfig {
+                    default_timeout: AtomicI32::new(default_timeout),
+                    ..Default::default()
+                });
+                let daemon = NotificationDaemon::new(tx, daemon_config.clone());
 
                 match zbus::connection::Connection::session().await {
                     Ok(conn) => {
@@ -186,6 +506,7 @@ impl NotificationService {
                                 let _ = output
                                     .send(ServiceEvent::Init(NotificationService::new(
                                         max_notifications,
+                                        daemon_config,
                                         service_conn,
                                     )))
                                     .await;
@@ -251,37 +572,100 @@ impl ReadOnlyService for NotificationService {
     fn update(&mut self, event: Self::UpdateEvent) {
         match event {
             NotificationEvent::Notify(notification) => {
-                // If replaces_id, remove old
-                if let Some(pos) = self
-                    .notifications
-                    .iter()
-                    .position(|n| n.id == notification.id)
-                {
-                    self.notifications.remove(pos);
-                }
+                // If replaces_id or synchronous, remove old
+                self.notifications.retain(|n| !n.is_replaced_by(&notification));
 
                 // Transient notifications with a timeout are not stored in the list
-                if notification.transient && notification.urgency != Urgency::Critical {
+                if notification.transient
+                    && notification.urgency != Urgency::Critical
+                    && !self.config.is_always_popup(&notification.app_name)
+                {
                     return;
                 }
 
                 self.notifications.insert(0, notification);
-
-                // Trim to max
-                if self.notifications.len() > self.max_notifications {
-                    self.notifications.truncate(self.max_notifications);
-                }
+                self.dismiss_overflow();
+                // A replacement keeps the pin only if it reuses the id
+                self.pinned
+                    .retain(|id| self.notifications.iter().any(|n| n.id == *id));
             }
             NotificationEvent::Closed(id, reason) => {
                 // Expired notifications stay in the center until user dismisses them
                 if !matches!(reason, CloseReason::Expired) {
                     self.notifications.retain(|n| n.id != id);
+                    self.pinned.remove(&id);
                 }
             }
         }
     }
 
     fn subscribe() -> Subscription<ServiceEvent<Self>> {
-        Self::subscribe_with_config(50, 5000)
+        let config = NotificationsModuleConfig::default();
+
+        Self::subscribe_with_config(config.max_notifications, config.default_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(
+        max_notifications: usize,
+        ids: impl IntoIterator<Item = u32>,
+    ) -> NotificationService {
+        NotificationService {
+            // Newest first, like the notifications received by the daemon
+            notifications: ids.into_iter().map(Notification::test).rev().collect(),
+            max_notifications,
+            recently_closed: VecDeque::new(),
+            pinned: HashSet::new(),
+            config: NotificationsModuleConfig::default(),
+            daemon_config: Arc::default(),
+            conn: None,
+        }
+    }
+
+    fn ids(notifications: &[Notification]) -> Vec<u32> {
+        notifications.iter().map(|n| n.id).collect()
+    }
+
+    #[test]
+    fn overflow_is_not_restorable() {
+        let mut service = service(2, 1..=2);
+
+        service.update(NotificationEvent::Notify(Notification::test(3)));
+
+        assert_eq!(ids(&service.notifications), vec![3, 2]);
+        assert!(service.recently_closed.is_empty());
+        assert_eq!(service.restore_last(), None);
+    }
+
+    #[test]
+    fn restored_notification_has_no_actions_and_respects_the_limit() {
+        let mut service = service(2, 1..=2);
+        service.notifications[0].actions = vec![("default".to_string(), "Open".to_string())];
+
+        service.dismiss(2);
+        service.update(NotificationEvent::Notify(Notification::test(3)));
+        assert_eq!(service.restore_last(), Some(2));
+
+        assert_eq!(ids(&service.notifications), vec![2, 3]);
+        assert!(service.notifications[0].actions.is_empty());
+    }
+
+    #[test]
+    fn pinned_notifications_are_not_trimmed() {
+        let mut service = service(2, 1..=2);
+        service.toggle_pin(1);
+
+        service.update(NotificationEvent::Notify(Notification::test(3)));
+        service.update(NotificationEvent::Notify(Notification::test(4)));
+
+        assert_eq!(ids(&service.notifications), vec![4, 1]);
+        assert!(service.is_pinned(1));
+
+        service.dismiss_all();
+        assert_eq!(ids(&service.notifications), vec![1]);
     }
 }
@@ -16,7 +16,8 @@ const WEBCAM_DEVICE_PATH: &str = "/dev/video0";
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Media {
-    Video,
+    Screencast,
+    Camera,
     Audio,
 }
 
@@ -49,11 +50,11 @@ impl PrivacyData {
     }
 
     pub fn webcam_access(&self) -> bool {
-        self.webcam_access > 0
+        self.webcam_access > 0 || self.nodes.iter().any(|n| n.media == Media::Camera)
     }
 
     pub fn screenshare_access(&self) -> bool {
-        self.nodes.iter().any(|n| n.media == Media::Video)
+        self.nodes.iter().any(|n| n.media == Media::Screencast)
     }
 }
 
@@ -103,13 +104,16 @@ impl PrivacyService {
                             })
                         {
                             debug!("New global: {global:?}");
+                            // Cameras accessed through the portal are also video
+                            // input streams, they are tagged with the Camera role.
+                            let media = match media {
+                                "Stream/Input/Audio" => Media::Audio,
+                                _ if props.get("media.role") == Some("Camera") => Media::Camera,
+                                _ => Media::Screencast,
+                            };
                             let _ = tx.send(PrivacyEvent::AddNode(ApplicationNode {
                                 id: global.id,
-                                media: if media == "Stream/Input/Video" {
-                                    Media::Video
-                                } else {
-                                    Media::Audio
-                                },
+                                media,
                             }));
                         }
                     }
@@ -236,6 +236,14 @@ pub struct Icon {
     pub bytes: Vec<u8>,
 }
 
+#[derive(Clone, Debug, zvariant::Value)]
+pub struct ToolTip {
+    pub icon_name: String,
+    pub icon_pixmap: Vec<Icon>,
+    pub title: String,
+    pub description: String,
+}
+
 #[proxy(interface = "org.kde.StatusNotifierItem")]
 pub trait StatusNotifierItem {
     #[zbus(property)]
@@ -246,6 +254,23 @@ pub trait StatusNotifierItem {
 
     #[zbus(property)]
     fn menu(&self) -> zbus::Result<OwnedObjectPath>;
+
+    // Items announce changes with NewToolTip instead of PropertiesChanged,
+    // so the value must not be cached
+    #[zbus(property(emits_changed_signal = "false"))]
+    fn tool_tip(&self) -> zbus::Result<ToolTip>;
+
+    #[zbus(property)]
+    fn item_is_menu(&self) -> zbus::Result<bool>;
+
+    fn activate(&self, x: i32, y: i32) -> zbus::Result<()>;
+
+    fn secondary_activate(&self, x: i32, y: i32) -> zbus::Result<()>;
+
+    fn scroll(&self, delta: i32, orientation: &str) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn new_tool_tip(&self) -> zbus::Result<()>;
 }
 
 #[derive(Clone, Debug, Type)]
@@ -1,7 +1,8 @@
 use super::{ReadOnlyService, Service, ServiceEvent};
+use crate::utils::strip_markup_tags;
 use dbus::{
     DBusMenuProxy, Layout, StatusNotifierItemProxy, StatusNotifierWatcher,
-    StatusNotifierWatcherProxy,
+    StatusNotifierWatcherProxy, ToolTip,
 };
 use freedesktop_icons::lookup;
 use iced::{
@@ -211,6 +212,17 @@ fn icon_directories() -> Vec<PathBuf> {
     dirs
 }
 
+fn format_tooltip(tooltip: ToolTip) -> Option<String> {
+    let description = strip_markup_tags(&tooltip.description);
+
+    match (tooltip.title.trim(), description.trim()) {
+        ("", "") => None,
+        (title, "") => Some(title.to_owned()),
+        ("", description) => Some(description.to_owned()),
+        (title, description) => Some(format!("{title}\n{description}")),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum TrayIcon {
     Image(image::Handle),
@@ -222,6 +234,9 @@ pub enum TrayEvent {
     Registered(StatusNotifierItem),
     IconChanged(String, TrayIcon),
     MenuLayoutChanged(String, Layout),
+    TooltipChanged(String, Option<String>),
+    Activated(String),
+    ActivateFailed(String),
     Unregistered(String),
     None,
 }
@@ -231,6 +246,8 @@ pub struct StatusNotifierItem {
     pub name: String,
     pub icon: Option<TrayIcon>,
     pub menu: Layout,
+    pub tooltip: Option<String>,
+    pub is_menu: bool,
     item_proxy: StatusNotifierItemProxy<'static>,
     menu_proxy: DBusMenuProxy<'static>,
 }
@@ -282,6 +299,9 @@ impl StatusNotifierItem {
                 .and_then(get_icon_from_name),
         };
 
+        let tooltip = item_proxy.tool_tip().await.ok().and_then(format_tooltip);
+        let is_menu = item_proxy.item_is_menu().await.unwrap_or_default();
+
         let menu_path = item_proxy.menu().await?;
         let menu_proxy = dbus::DBusMenuProxy::builder(conn)
             .destination(dest.to_owned())?
@@ -295,6 +315,8 @@ impl StatusNotifierItem {
             name,
             icon,
             menu,
+            tooltip,
+            is_menu,
             item_proxy,
             menu_proxy,
         })
@@ -391,6 +413,7 @@ impl TrayService {
         let mut icon_pixel_change = Vec::with_capacity(items.len());
         let mut icon_name_change = Vec::with_capacity(items.len());
         let mut menu_layout_change = Vec::with_capacity(items.len());
+        let mut tooltip_change = Vec::with_capacity(items.len());
 
         for name in items {
             let item = StatusNotifierItem::new(conn, name.to_string()).await?;
@@ -453,6 +476,27 @@ impl TrayService {
                     .boxed(),
             );
 
+            if let Ok(new_tooltip) = item.item_proxy.receive_new_tool_tip().await {
+                tooltip_change.push(
+                    new_tooltip
+                        .then({
+                            let name = name.clone();
+                            let item_proxy = item.item_proxy.clone();
+                            move |_| {
+                                let name = name.clone();
+                                let item_proxy = item_proxy.clone();
+                                async move {
+                                    let tooltip =
+                                        item_proxy.tool_tip().await.ok().and_then(format_tooltip);
+
+                                    TrayEvent::TooltipChanged(name, tooltip)
+                                }
+                            }
+                        })
+                        .boxed(),
+                );
+            }
+
             let layout_updated = item.menu_proxy.receive_layout_updated().await;
             if let Ok(layout_updated) = layout_updated {
                 menu_layout_change.push(
@@ -484,7 +528,8 @@ impl TrayService {
             unregistered,
             select_all(icon_pixel_change),
             select_all(icon_name_change),
-            select_all(menu_layout_change)
+            select_all(menu_layout_change),
+            select_all(tooltip_change)
         )
         .boxed())
     }
@@ -607,6 +652,12 @@ impl ReadOnlyService for TrayService {
                     item.menu = layout;
                 }
             }
+            TrayEvent::TooltipChanged(name, tooltip) => {
+                if let Some(item) = self.data.0.iter_mut().find(|item| item.name == name) {
+                    item.tooltip = tooltip;
+                }
+            }
+            TrayEvent::Activated(_) | TrayEvent::ActivateFailed(_) => {}
             TrayEvent::Unregistered(name) => {
                 self.data.0.retain(|item| item.name != name);
             }
@@ -633,6 +684,25 @@ impl ReadOnlyService for TrayService {
 #[derive(Debug, Clone)]
 pub enum TrayCommand {
     MenuSelected(String, i32),
+    /// Activate an item, with the position of the click on the bar
+    Activate(String, i32, i32),
+    SecondaryActivate(String, i32, i32),
+    Scroll(String, i32, ScrollOrientation),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ScrollOrientation {
+    Vertical,
+    Horizontal,
+}
+
+impl ScrollOrientation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ScrollOrientation::Vertical => "vertical",
+            ScrollOrientation::Horizontal => "horizontal",
+        }
+    }
 }
 
 impl Service for TrayService {
@@ -665,6 +735,64 @@ impl Service for TrayService {
                     Task::none()
                 }
             }
+            TrayCommand::Activate(name, x, y) => {
+                let item = self.data.iter().find(|item| item.name == name);
+                if let Some(item) = item {
+                    let proxy = item.item_proxy.clone();
+
+                    Task::perform(
+                        async move { proxy.activate(x, y).await },
+                        move |res| match res {
+                            Ok(_) => ServiceEvent::Update(TrayEvent::Activated(name.clone())),
+                            Err(e) => {
+                                debug!("Tray item {name} activate failed: {e}");
+                                ServiceEvent::Update(TrayEvent::ActivateFailed(name.clone()))
+                            }
+                        },
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            TrayCommand::SecondaryActivate(name, x, y) => {
+                let item = self.data.iter().find(|item| item.name == name);
+                if let Some(item) = item {
+                    let proxy = item.item_proxy.clone();
+
+                    Task::perform(
+                        async move { proxy.secondary_activate(x, y).await },
+                        move |res| {
+                            if let Err(e) = res {
+                                debug!("Tray item {name} secondary activate failed: {e}");
+                            }
+
+                            ServiceEvent::Update(TrayEvent::None)
+                        },
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            TrayCommand::Scroll(name, delta, orientation) => {
+                let item = self.data.iter().find(|item| item.name == name);
+                if let Some(item) = item {
+                    let proxy = item.item_proxy.clone();
+
+                    Task::perform(
+                        async move { proxy.scroll(delta, orientation.as_str()).await },
+                        move |res| {
+                            // Not every item implements Scroll, ignore those
+                            if let Err(e) = res {
+                                debug!("Tray item {name} scroll failed: {e}");
+                            }
+
+                            ServiceEvent::Update(TrayEvent::None)
+                        },
+                    )
+                } else {
+                    Task::none()
+                }
+            }
         }
     }
 }
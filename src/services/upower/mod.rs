@@ -69,7 +69,7 @@ pub struct BatteryData {
 }
 
 impl BatteryData {
-    pub fn get_indicator_state(&self) -> IndicatorState {
+    pub fn get_indicator_state(&self, warn_threshold: u8, alert_threshold: u8) -> IndicatorState {
         match self {
             BatteryData {
                 status: BatteryStatus::Charging(_),
@@ -78,7 +78,11 @@ impl BatteryData {
             BatteryData {
                 status: BatteryStatus::Discharging(_),
                 capacity,
-            } if *capacity < 20 => IndicatorState::Danger,
+            } if *capacity < i64::from(alert_threshold) => IndicatorState::Danger,
+            BatteryData {
+                status: BatteryStatus::Discharging(_),
+                capacity,
+            } if *capacity < i64::from(warn_threshold) => IndicatorState::Warning,
             _ => IndicatorState::Normal,
         }
     }
@@ -1,12 +1,39 @@
-use crate::config::{Appearance, AppearanceColor, AppearanceStyle, MenuAppearance, Position};
+use crate::{
+    components::icons::IconTheme,
+    config::{
+        Appearance, AppearanceColor, AppearanceMargin, AppearanceStyle, ExclusiveZone,
+        MenuAppearance, ModuleName, PaletteSource, Position, SectionsAppearance,
+    },
+};
+use hex_color::HexColor;
 use iced::{
-    Background, Border, Color, Theme,
+    Background, Border, Color, Font, Theme,
     theme::{Palette, palette},
     widget::{
         button::{self, Status},
         text_input::{self},
     },
 };
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+static FONT_NAMES: Lazy<Mutex<HashMap<String, &'static str>>> = Lazy::new(Default::default);
+
+/// Font names must be `'static` for iced. Each distinct name is leaked once,
+/// so reloading the config doesn't leak it again.
+pub fn intern_font_name(name: &str) -> &'static str {
+    let mut names = FONT_NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(interned) = names.get(name) {
+        return interned;
+    }
+
+    let interned: &'static str = Box::leak(name.to_owned().into_boxed_str());
+    names.insert(name.to_owned(), interned);
+    interned
+}
 
 #[allow(unused)]
 #[derive(Debug, Copy, Clone)]
@@ -88,10 +115,20 @@ pub struct AshellTheme {
     pub font_size: FontSize,
     pub bar_position: Position,
     pub bar_style: AppearanceStyle,
+    pub bar_margin: AppearanceMargin,
+    pub bar_exclusive_zone: ExclusiveZone,
+    pub bar_radius: [f32; 4],
+    pub bar_auto_hide: bool,
+    pub auto_hide_reveal_delay_ms: u64,
+    pub sections: SectionsAppearance,
+    pub palette_source: PaletteSource,
     pub opacity: f32,
     pub menu: MenuAppearance,
     pub workspace_colors: Vec<AppearanceColor>,
     pub special_workspace_colors: Option<Vec<AppearanceColor>>,
+    pub module_colors: HashMap<ModuleName, HexColor>,
+    pub monospace_font: Font,
+    pub icons: IconTheme,
     pub scale_factor: f64,
 }
 
@@ -103,10 +140,27 @@ impl AshellTheme {
             font_size: FontSize::default(),
             bar_position: position,
             bar_style: appearance.style,
+            bar_margin: appearance.margin,
+            bar_exclusive_zone: appearance.bar_exclusive_zone(),
+            bar_radius: appearance.bar_radius,
+            bar_auto_hide: appearance.auto_hide,
+            auto_hide_reveal_delay_ms: appearance.auto_hide_reveal_delay_ms,
+            sections: appearance.sections,
+            palette_source: appearance.palette_source.clone(),
             opacity: appearance.opacity,
             menu: appearance.menu,
             workspace_colors: appearance.workspace_colors.clone(),
             special_workspace_colors: appearance.special_workspace_colors.clone(),
+            module_colors: appearance.module_colors.clone(),
+            // Falls back to the bar font so metrics keep the configured family
+            monospace_font: appearance
+                .monospace_font_name
+                .as_ref()
+                .or(appearance.font_name.as_ref())
+                .map_or(Font::DEFAULT, |name| {
+                    Font::with_name(intern_font_name(name))
+                }),
+            icons: IconTheme::new(&appearance.icons),
             scale_factor: appearance.scale_factor,
             iced_theme: Theme::custom_with_fn(
                 "local".to_string(),
@@ -247,6 +301,38 @@ impl AshellTheme {
         }
     }
 
+    /// Filled counterpart of `ghost_button_style` for the action to draw attention to.
+    pub fn primary_button_style(&self) -> impl Fn(&Theme, Status) -> button::Style {
+        move |theme, status| {
+            let mut base = button::Style {
+                background: Some(theme.palette().primary.scale_alpha(self.opacity).into()),
+                border: Border {
+                    width: 0.0,
+                    radius: self.radius.sm.into(),
+                    color: Color::TRANSPARENT,
+                },
+                text_color: theme.extended_palette().primary.base.text,
+                ..button::Style::default()
+            };
+            match status {
+                Status::Active => base,
+                Status::Hovered => {
+                    base.background = Some(
+                        theme
+                            .extended_palette()
+                            .primary
+                            .weak
+                            .color
+                            .scale_alpha(self.opacity)
+                            .into(),
+                    );
+                    base
+                }
+                _ => base,
+            }
+        }
+    }
+
     pub fn settings_button_style(&self) -> impl Fn(&Theme, Status) -> button::Style {
         move |theme, status| {
             let mut base = button::Style {
@@ -1,4 +1,7 @@
-use std::process::Command;
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
 
 pub fn execute_command(command: String) {
     tokio::spawn(async move {
@@ -15,6 +18,40 @@ pub fn execute_command(command: String) {
     });
 }
 
+/// Run a command and return its captured stdout.
+pub async fn command_output(command: &str) -> Option<String> {
+    match tokio::process::Command::new("bash")
+        .arg("-c")
+        .arg(command)
+        .stdout(Stdio::piped())
+        .output()
+        .await
+    {
+        Ok(output) => Some(String::from_utf8_lossy(&output.stdout).into_owned()),
+        Err(e) => {
+            log::error!("Failed to execute command {command}: {e}");
+            None
+        }
+    }
+}
+
+/// Spawn a long running command and hand its process back to the caller.
+/// The shell is replaced with `exec` so killing the child stops the command itself.
+/// Dropped children are reaped in the background by the tokio runtime.
+pub fn spawn_command(command: &str) -> Option<tokio::process::Child> {
+    match tokio::process::Command::new("bash")
+        .arg("-c")
+        .arg(format!("exec {command}"))
+        .spawn()
+    {
+        Ok(child) => Some(child),
+        Err(e) => {
+            log::error!("Failed to execute command {command}: {e}");
+            None
+        }
+    }
+}
+
 pub fn suspend(cmd: String) {
     tokio::spawn(async move {
         match Command::new("bash").arg("-c").arg(&cmd).spawn() {
@@ -69,3 +106,112 @@ pub fn logout(cmd: String) {
         }
     });
 }
+
+/// Open `url` with the default handler. The url is passed as a single argument, without a
+/// shell, since it usually comes from a notification.
+pub fn open_url(url: String) {
+    tokio::spawn(async move {
+        if let Err(e) = tokio::process::Command::new("xdg-open")
+            .arg(&url)
+            .status()
+            .await
+        {
+            log::error!("Failed to open {url}: {e}");
+        }
+    });
+}
+
+/// Look up the `Exec` line of an installed desktop entry, with field codes like `%U` removed.
+/// `desktop_id` comes from the notification sender, so ids that could point outside the
+/// `applications` directories are rejected.
+pub fn desktop_entry_exec(desktop_id: &str) -> Option<String> {
+    let desktop_id = desktop_id.trim_end_matches(".desktop");
+    if desktop_id.is_empty() || desktop_id.contains('/') || desktop_id.contains("..") {
+        return None;
+    }
+    let file_name = format!("{desktop_id}.desktop");
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")));
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    data_home
+        .into_iter()
+        .chain(data_dirs.split(':').map(PathBuf::from))
+        .find_map(|dir| std::fs::read_to_string(dir.join("applications").join(&file_name)).ok())
+        .and_then(|contents| parse_desktop_exec(&contents))
+}
+
+fn parse_desktop_exec(contents: &str) -> Option<String> {
+    let mut in_main_group = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_main_group = line == "[Desktop Entry]";
+        } else if in_main_group && let Some(exec) = line.strip_prefix("Exec=") {
+            let mut command = String::new();
+            let mut chars = exec.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    // `%%` is a literal percent, every other field code is dropped
+                    '%' => {
+                        if chars.next() == Some('%') {
+                            command.push('%');
+                        }
+                    }
+                    c => command.push(c),
+                }
+            }
+            let command = command.trim();
+            return (!command.is_empty()).then(|| command.to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exec_field_codes_are_dropped() {
+        let entry = "[Desktop Entry]\nName=Files\nExec=nautilus --new-window %U\n";
+
+        assert_eq!(
+            parse_desktop_exec(entry),
+            Some("nautilus --new-window".to_string())
+        );
+    }
+
+    #[test]
+    fn exec_double_percent_is_a_literal_percent() {
+        let entry = "[Desktop Entry]\nExec=printf 100%% %f\n";
+
+        assert_eq!(parse_desktop_exec(entry), Some("printf 100%".to_string()));
+    }
+
+    #[test]
+    fn exec_of_desktop_actions_is_ignored() {
+        let entry = "[Desktop Action new-window]\nExec=firefox --new-window\n\n\
+                     [Desktop Entry]\nExec=firefox %u\n\n\
+                     [Desktop Action private]\nExec=firefox --private-window\n";
+
+        assert_eq!(parse_desktop_exec(entry), Some("firefox".to_string()));
+        assert_eq!(
+            parse_desktop_exec("[Desktop Action new]\nExec=firefox --new-window\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn desktop_ids_outside_applications_are_rejected() {
+        assert_eq!(desktop_entry_exec("../../../tmp/x"), None);
+        assert_eq!(desktop_entry_exec("/tmp/x"), None);
+        assert_eq!(desktop_entry_exec(".."), None);
+        assert_eq!(desktop_entry_exec(".desktop"), None);
+    }
+}
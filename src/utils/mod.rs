@@ -1,8 +1,13 @@
+use iced::{Subscription, futures::SinkExt, stream::channel};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::time::Duration;
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 pub mod launcher;
+pub mod state;
 
 #[derive(Debug, Clone, Copy)]
 pub enum IndicatorState {
@@ -22,6 +27,29 @@ pub fn format_duration(duration: &Duration) -> String {
     }
 }
 
+/// Format a size given in bytes using decimal units.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1000. && unit < UNITS.len() - 1 {
+        value /= 1000.;
+        unit += 1;
+    }
+
+    if unit == 0 || value >= 9.95 {
+        format!("{value:.0} {}", UNITS[unit])
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Format a transfer rate given in bytes per second using decimal units.
+pub fn format_rate(bytes_per_sec: u64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec))
+}
+
 /// Truncate a string to at most `max_chars` characters (not bytes),
 /// avoiding panics on multi-byte UTF-8.
 pub fn truncate_chars(s: &str, max_chars: usize) -> &str {
@@ -31,6 +59,17 @@ pub fn truncate_chars(s: &str, max_chars: usize) -> &str {
     }
 }
 
+/// Keep at most `max_lines` lines, marking the cut with an ellipsis. `0` keeps every line.
+pub fn truncate_lines(s: &str, max_lines: usize) -> Cow<'_, str> {
+    if max_lines == 0 {
+        return Cow::Borrowed(s);
+    }
+    match s.trim_end().match_indices('\n').nth(max_lines - 1) {
+        Some((byte_idx, _)) => Cow::Owned(format!("{}…", s[..byte_idx].trim_end())),
+        None => Cow::Borrowed(s),
+    }
+}
+
 static STRIP_TAGS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>").unwrap());
 
 /// Strip HTML/markup tags from notification body text.
@@ -48,8 +87,10 @@ pub fn strip_markup_tags(s: &str) -> String {
         .replace("&apos;", "'")
 }
 
+/// Truncate a string to `max_length` characters (not bytes) by replacing
+/// the middle part with "...".
 pub fn truncate_text(value: &str, max_length: u32) -> String {
-    let length = value.len();
+    let length = value.chars().count();
 
     if length > max_length as usize {
         let split = max_length as usize / 2;
@@ -60,3 +101,112 @@ pub fn truncate_text(value: &str, max_length: u32) -> String {
         value.to_string()
     }
 }
+
+/// Whether a strftime format shows anything that changes every second.
+pub fn format_has_seconds(format: &str) -> bool {
+    [
+        "%S",  // Seconds (00-60)
+        "%T",  // Hour:Minute:Second
+        "%X",  // Locale time representation with seconds
+        "%r",  // 12-hour clock time with seconds
+        "%:z", // UTC offset with seconds
+        "%s",  // Unix timestamp (seconds since epoch)
+    ]
+    .iter()
+    .any(|spec| format.contains(spec))
+}
+
+/// Time left from `since_epoch` until the next multiple of `period`.
+fn delay_to_boundary(since_epoch: Duration, period: Duration) -> Duration {
+    let period_ms = period.as_millis().max(1);
+    let delay_ms = period_ms - since_epoch.as_millis() % period_ms;
+
+    Duration::from_millis(delay_ms as u64)
+}
+
+/// Like `iced::time::every`, but ticks on wall clock boundaries (the start of
+/// each second or minute) instead of at a fixed offset from startup.
+pub fn every_aligned(period: Duration) -> Subscription<()> {
+    Subscription::run_with_id(
+        ("every_aligned", period),
+        channel(1, async move |mut output| {
+            loop {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                tokio::time::sleep(delay_to_boundary(now, period)).await;
+
+                let _ = output.send(()).await;
+            }
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_text_keeps_short_text() {
+        assert_eq!(truncate_text("Editor", 10), "Editor");
+        assert_eq!(truncate_text("Editor", 6), "Editor");
+    }
+
+    #[test]
+    fn truncate_text_ellipsizes_the_middle() {
+        assert_eq!(
+            truncate_text("Very Long Document Name - Editor", 14),
+            "Very Lo... Editor"
+        );
+    }
+
+    #[test]
+    fn truncate_text_handles_multibyte_chars() {
+        assert_eq!(
+            truncate_text("日本語のウィンドウタイトル", 6),
+            "日本語...イトル"
+        );
+        assert_eq!(truncate_text("🦀🦀🦀🦀🦀", 2), "🦀...🦀");
+    }
+
+    #[test]
+    fn truncate_lines_clamps_line_count() {
+        assert_eq!(truncate_lines("one\ntwo\nthree", 2), "one\ntwo…");
+        assert_eq!(truncate_lines("one\ntwo\n", 2), "one\ntwo\n");
+        assert_eq!(truncate_lines("one\ntwo\nthree", 0), "one\ntwo\nthree");
+    }
+
+    #[test]
+    fn format_bytes_picks_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(120_000_000_000), "120 GB");
+        assert_eq!(format_bytes(2_000_000_000_000), "2.0 TB");
+    }
+
+    #[test]
+    fn format_rate_picks_units() {
+        assert_eq!(format_rate(0), "0 B/s");
+        assert_eq!(format_rate(999), "999 B/s");
+        assert_eq!(format_rate(1_500), "1.5 KB/s");
+        assert_eq!(format_rate(42_000), "42 KB/s");
+        assert_eq!(format_rate(3_300_000), "3.3 MB/s");
+    }
+
+    #[test]
+    fn delay_to_boundary_waits_for_next_minute() {
+        let minute = Duration::from_secs(60);
+
+        assert_eq!(
+            delay_to_boundary(Duration::from_millis(125_500), minute),
+            Duration::from_millis(54_500)
+        );
+        assert_eq!(delay_to_boundary(Duration::from_secs(120), minute), minute);
+    }
+
+    #[test]
+    fn format_has_seconds_detects_second_specifiers() {
+        assert!(format_has_seconds("%H:%M:%S"));
+        assert!(format_has_seconds("%a %T"));
+        assert!(!format_has_seconds("%a %d %b %R"));
+    }
+}
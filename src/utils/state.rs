@@ -0,0 +1,35 @@
+use log::warn;
+use std::{env, fs, path::PathBuf};
+
+fn state_dir() -> Option<PathBuf> {
+    env::var("XDG_STATE_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var("HOME")
+                .ok()
+                .map(|home| PathBuf::from(home).join(".local/state"))
+        })
+        .map(|dir| dir.join("ashell"))
+}
+
+/// Read a small piece of UI state saved with [`write`].
+pub fn read(name: &str) -> Option<String> {
+    let path = state_dir()?.join(name);
+
+    fs::read_to_string(path)
+        .ok()
+        .map(|content| content.trim().to_owned())
+}
+
+pub fn write(name: &str, content: &str) {
+    let Some(dir) = state_dir() else {
+        warn!("Unable to find the state directory, skipping saving {name}");
+        return;
+    };
+
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|_| fs::write(dir.join(name), content)) {
+        warn!("Failed to save {name} state: {e}");
+    }
+}
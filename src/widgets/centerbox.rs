@@ -1,4 +1,4 @@
-//! Distribute content horizontally.
+//! Distribute content horizontally or vertically.
 use iced::advanced::layout::{self, Layout, Limits, Node};
 use iced::advanced::overlay;
 use iced::advanced::renderer;
@@ -8,7 +8,7 @@ use iced::{
     Alignment, Element, Event, Length, Padding, Pixels, Point, Rectangle, Size, Vector, event,
 };
 
-/// A container that distributes its contents horizontally.
+/// A container that distributes its contents horizontally or vertically.
 #[allow(missing_debug_implementations)]
 pub struct Centerbox<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
     spacing: f32,
@@ -16,6 +16,7 @@ pub struct Centerbox<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer
     width: Length,
     height: Length,
     align_items: Alignment,
+    vertical: bool,
     children: [Element<'a, Message, Theme, Renderer>; 3],
 }
 
@@ -31,11 +32,12 @@ where
             width: Length::Shrink,
             height: Length::Shrink,
             align_items: Alignment::Start,
+            vertical: false,
             children,
         }
     }
 
-    /// Sets the horizontal spacing _between_ elements.
+    /// Sets the spacing _between_ elements.
     ///
     /// Custom margins per element do not exist in iced. You should use this
     /// method instead! While less flexible, it helps you keep spacing between
@@ -63,11 +65,17 @@ where
         self
     }
 
-    /// Sets the vertical alignment of the contents of the [`Centerbox`] .
+    /// Sets the cross axis alignment of the contents of the [`Centerbox`] .
     pub fn align_items(mut self, align: Alignment) -> Self {
         self.align_items = align;
         self
     }
+
+    /// Lays out the elements from top to bottom instead of left to right.
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
 }
 
 impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -101,19 +109,51 @@ where
             .height(self.height)
             .shrink(self.padding);
 
+        let vertical = self.vertical;
+        let main_axis = |size: Size| if vertical { size.height } else { size.width };
+        let cross_axis = |size: Size| if vertical { size.width } else { size.height };
+        let point = |main: f32, cross: f32| {
+            if vertical {
+                Point::new(cross, main)
+            } else {
+                Point::new(main, cross)
+            }
+        };
+        let (main_length, cross_length) = if vertical {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let (padding_main_start, padding_main_end, padding_cross_start, padding_main) = if vertical
+        {
+            (
+                self.padding.top,
+                self.padding.bottom,
+                self.padding.left,
+                self.padding.vertical(),
+            )
+        } else {
+            (
+                self.padding.left,
+                self.padding.right,
+                self.padding.top,
+                self.padding.horizontal(),
+            )
+        };
+
         let total_spacing = self.spacing * 3_i32.saturating_sub(1) as f32;
-        let max_cross = limits.max().height;
+        let max_cross = cross_axis(limits.max());
 
-        let mut cross = match self.height {
+        let mut cross = match cross_length {
             Length::Shrink => 0.0,
             _ => max_cross,
         };
 
-        let available = limits.max().width - total_spacing;
+        let available = main_axis(limits.max()) - total_spacing;
 
         let mut nodes = [Node::default(), Node::default(), Node::default()];
 
-        let mut remaining = match self.width {
+        let mut remaining = match main_length {
             Length::Shrink => 0.0,
             _ => available.max(0.0),
         };
@@ -123,25 +163,33 @@ where
                 let fill_cross_factor = {
                     let size = child.as_widget().size();
 
-                    size.height.fill_factor()
+                    if vertical {
+                        size.width.fill_factor()
+                    } else {
+                        size.height.fill_factor()
+                    }
+                };
+
+                let max_child_cross = if fill_cross_factor != 0 {
+                    cross
+                } else {
+                    max_cross
                 };
 
-                let (max_width, max_height) = (
-                    remaining,
-                    if fill_cross_factor != 0 {
-                        cross
+                let child_limits = Limits::new(
+                    Size::ZERO,
+                    if vertical {
+                        Size::new(max_child_cross, remaining)
                     } else {
-                        max_cross
+                        Size::new(remaining, max_child_cross)
                     },
                 );
 
-                let child_limits = Limits::new(Size::ZERO, Size::new(max_width, max_height));
-
                 let layout = child.as_widget().layout(tree, renderer, &child_limits);
                 let size = layout.size();
 
-                remaining -= size.width;
-                cross = cross.max(size.height);
+                remaining -= main_axis(size);
+                cross = cross.max(cross_axis(size));
 
                 nodes[i] = layout;
             };
@@ -150,44 +198,53 @@ where
         calculate_edge_layout(2, (&self.children[2], &mut tree.children[2]));
         calculate_edge_layout(1, (&self.children[1], &mut tree.children[1]));
 
-        nodes[0].move_to_mut(Point::new(self.padding.left, self.padding.top));
-        nodes[0].align_mut(Alignment::Start, self.align_items, Size::new(0.0, cross));
-        nodes[2].move_to_mut(Point::new(
-            limits.max().width + self.padding.right,
-            self.padding.top,
+        let align = |node: &mut Node, main_alignment: Alignment| {
+            if vertical {
+                node.align_mut(self.align_items, main_alignment, Size::new(cross, 0.0));
+            } else {
+                node.align_mut(main_alignment, self.align_items, Size::new(0.0, cross));
+            }
+        };
+
+        nodes[0].move_to_mut(point(padding_main_start, padding_cross_start));
+        align(&mut nodes[0], Alignment::Start);
+        nodes[2].move_to_mut(point(
+            main_axis(limits.max()) + padding_main_end,
+            padding_cross_start,
         ));
-        nodes[2].align_mut(Alignment::End, self.align_items, Size::new(0.0, cross));
+        align(&mut nodes[2], Alignment::End);
 
         let half_available = available / 2.0;
-        let half_center_width = nodes[1].size().width / 2.0;
+        let half_center_main = main_axis(nodes[1].size()) / 2.0;
+        let start_main = main_axis(nodes[0].size());
+        let end_main = main_axis(nodes[2].size());
 
-        if half_available - nodes[0].size().width < half_center_width
-            || half_available - nodes[2].size().width < half_center_width
+        if half_available - start_main < half_center_main
+            || half_available - end_main < half_center_main
         {
-            nodes[1].move_to_mut(Point::new(
-                self.padding.left
+            nodes[1].move_to_mut(point(
+                padding_main_start
                     + self.spacing
-                    + nodes[0].size().width
-                    + (available - nodes[0].size().width - nodes[2].size().width) / 2.0,
-                self.padding.top,
+                    + start_main
+                    + (available - start_main - end_main) / 2.0,
+                padding_cross_start,
             ));
         } else {
-            nodes[1].move_to_mut(Point::new(
-                limits.max().width / 2. + self.padding.horizontal() / 2.0,
-                self.padding.top,
+            nodes[1].move_to_mut(point(
+                main_axis(limits.max()) / 2. + padding_main / 2.0,
+                padding_cross_start,
             ));
         }
-        nodes[1].align_mut(Alignment::Center, self.align_items, Size::new(0.0, cross));
+        align(&mut nodes[1], Alignment::Center);
 
-        let main =
-            nodes[0].size().width + nodes[1].size().width + nodes[2].size().width + total_spacing;
+        let main = start_main + main_axis(nodes[1].size()) + end_main + total_spacing;
 
-        let (intrinsic_width, intrinsic_height) = (main, cross);
-        let size = limits.resolve(
-            self.width,
-            self.height,
-            Size::new(intrinsic_width, intrinsic_height),
-        );
+        let intrinsic_size = if vertical {
+            Size::new(cross, main)
+        } else {
+            Size::new(main, cross)
+        };
+        let size = limits.resolve(self.width, self.height, intrinsic_size);
 
         Node::with_children(size.expand(self.padding), nodes.into())
     }
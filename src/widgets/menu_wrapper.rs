@@ -13,9 +13,13 @@ use iced::{
 pub struct MenuWrapper<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
     id: Id,
     x: f32,
+    y: Option<f32>,
+    offset_x: f32,
+    offset_y: f32,
     content: Element<'a, Message, Theme, Renderer>,
     on_click_outside: Option<Message>,
     padding: Padding,
+    horizontal_alignment: Option<alignment::Horizontal>,
     vertical_alignment: alignment::Vertical,
     backdrop: Option<Color>,
 }
@@ -28,8 +32,12 @@ where
         MenuWrapper {
             id: Id::unique(),
             x,
+            y: None,
+            offset_x: 0.,
+            offset_y: 0.,
             content,
             on_click_outside: None,
+            horizontal_alignment: None,
             vertical_alignment: alignment::Vertical::Top,
             padding: Padding::ZERO,
             backdrop: None,
@@ -41,11 +49,33 @@ where
         self
     }
 
+    /// Centers the content vertically on `y` instead of aligning it, used next to vertical bars
+    pub fn y(mut self, y: f32) -> Self {
+        self.y = Some(y);
+        self
+    }
+
+    /// Aligns the content horizontally instead of centering it on `x`
+    pub fn align_x(mut self, alignment: impl Into<alignment::Horizontal>) -> Self {
+        self.horizontal_alignment = Some(alignment.into());
+        self
+    }
+
     pub fn align_y(mut self, alignment: impl Into<alignment::Vertical>) -> Self {
         self.vertical_alignment = alignment.into();
         self
     }
 
+    pub fn offset_x(mut self, offset_x: f32) -> Self {
+        self.offset_x = offset_x;
+        self
+    }
+
+    pub fn offset_y(mut self, offset_y: f32) -> Self {
+        self.offset_y = offset_y;
+        self
+    }
+
     pub fn on_click_outside(mut self, message: Message) -> Self {
         self.on_click_outside = Some(message);
         self
@@ -104,16 +134,26 @@ where
             },
             |node, size| {
                 let content_size = node.size();
-                let x = f32::min(
-                    f32::max(self.x - content_size.width / 2.0, 8.),
-                    size.width - content_size.width - 8.,
-                );
                 let node = node.align(
-                    iced::Alignment::Center,
+                    self.horizontal_alignment
+                        .map_or(iced::Alignment::Center, Into::into),
                     self.vertical_alignment.into(),
                     size,
                 );
-                let y = node.bounds().y;
+                let x = match self.horizontal_alignment {
+                    Some(_) => node.bounds().x,
+                    None => f32::min(
+                        f32::max(self.x - content_size.width / 2.0, 8.),
+                        size.width - content_size.width - 8.,
+                    ),
+                } + self.offset_x;
+                let y = match self.y {
+                    Some(y) => f32::min(
+                        f32::max(y - content_size.height / 2.0, 8.),
+                        size.height - content_size.height - 8.,
+                    ),
+                    None => node.bounds().y,
+                } + self.offset_y;
                 node.move_to(Point::new(x, y))
             },
         )
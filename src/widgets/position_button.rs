@@ -28,6 +28,8 @@ where
 {
     content: Element<'a, Message, Theme, Renderer>,
     on_press: Option<OnPress<'a, Message>>,
+    on_right_press: Option<Box<dyn Fn(ButtonUIRef) -> Message + 'a>>,
+    on_middle_press: Option<Box<dyn Fn(ButtonUIRef) -> Message + 'a>>,
     id: Id,
     width: Length,
     height: Length,
@@ -49,6 +51,8 @@ where
             content,
             id: Id::unique(),
             on_press: None,
+            on_right_press: None,
+            on_middle_press: None,
             width: size.width.fluid(),
             height: size.height.fluid(),
             padding: DEFAULT_PADDING,
@@ -91,6 +95,24 @@ where
         self
     }
 
+    /// Sets the message produced when the [`Button`] is pressed with the right mouse button.
+    pub fn on_right_press_with_position(
+        mut self,
+        on_press: impl Fn(ButtonUIRef) -> Message + 'a,
+    ) -> Self {
+        self.on_right_press = Some(Box::new(on_press));
+        self
+    }
+
+    /// Sets the message produced when the [`Button`] is pressed with the middle mouse button.
+    pub fn on_middle_press_with_position(
+        mut self,
+        on_press: impl Fn(ButtonUIRef) -> Message + 'a,
+    ) -> Self {
+        self.on_middle_press = Some(Box::new(on_press));
+        self
+    }
+
     /// Sets whether the contents of the [`Button`] should be clipped on
     /// overflow.
     pub fn clip(mut self, clip: bool) -> Self {
@@ -253,6 +275,30 @@ where
                     }
                 }
             }
+            Event::Mouse(mouse::Event::ButtonPressed(
+                button @ (mouse::Button::Right | mouse::Button::Middle),
+            )) => {
+                let on_press = if button == mouse::Button::Right {
+                    self.on_right_press.as_ref()
+                } else {
+                    self.on_middle_press.as_ref()
+                };
+
+                if let Some(on_press) = on_press
+                    && cursor.is_over(layout.bounds())
+                {
+                    let ui_data = ButtonUIRef {
+                        position: Point::new(
+                            layout.bounds().width / 2. + layout.position().x,
+                            layout.bounds().height / 2. + layout.position().y,
+                        ),
+                        viewport: (viewport.width, viewport.height),
+                    };
+                    shell.publish(on_press(ui_data));
+
+                    return event::Status::Captured;
+                }
+            }
             Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) => {
                 if let Some(on_press) = self.on_press.as_ref() {
                     let state = tree.state.downcast_mut::<State>();